@@ -1,6 +1,13 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveTime, Timelike, Utc};
+use chrono_tz::Tz;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
+use crate::data::cities::CityRegistry;
+use crate::data::gamma_api::{Comparison, CompiledTemplate};
+use crate::data::types::{ProbabilityModel, TemperatureUnit, VenueQuote};
+use crate::math::distributions::ErfPrecision;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -17,6 +24,98 @@ pub struct Config {
 pub struct SystemConfig {
     pub dry_run: bool,
     pub database_path: String,
+    /// Named parameter bundle; explicitly set keys still take precedence
+    #[serde(default)]
+    pub profile: Option<Profile>,
+    /// Keep the global pause across restarts
+    #[serde(default)]
+    pub persist_pause: bool,
+    /// New orders halt while this file exists
+    #[serde(default)]
+    pub kill_switch_path: Option<String>,
+    /// Database open attempts at startup before giving up
+    #[serde(default = "default_db_open_attempts")]
+    pub db_open_attempts: u32,
+    /// Backoff after the first failed open, doubled each retry
+    #[serde(default = "default_db_open_backoff_ms")]
+    pub db_open_backoff_ms: u64,
+    /// Wait this long after a market resolves before settling (outcome may still be disputed)
+    #[serde(default = "default_settlement_grace_secs")]
+    pub settlement_grace_secs: u64,
+    /// Also send position writes here (SQLite stays authoritative)
+    #[serde(default)]
+    pub mirror_url: Option<String>,
+    /// Allow a wallet key to be loaded while dry-running (false = refuse to start)
+    #[serde(default = "default_true")]
+    pub allow_key_in_dry_run: bool,
+    /// Only analyze these condition_ids, skipping the usual market screens (empty = all)
+    #[serde(default)]
+    pub whitelist: Vec<String>,
+    /// Per-market (condition_id) token indices for YES/NO, trusted over the outcome labels
+    #[serde(default)]
+    pub outcome_overrides: HashMap<String, OutcomeOverride>,
+    /// Write periodic copies of the database here (unset = no backups)
+    #[serde(default)]
+    pub backup_dir: Option<String>,
+    #[serde(default = "default_backup_interval_secs")]
+    pub backup_interval_secs: u64,
+    /// Backups kept in backup_dir; older ones are deleted
+    #[serde(default = "default_backup_keep")]
+    pub backup_keep: usize,
+    /// Compare open positions against venue holdings this often, between cycles (unset = off)
+    /// The only venue so far is the simulator's own book, so this checks the bot's bookkeeping
+    /// and is skipped when not paper trading
+    #[serde(default)]
+    pub reconcile_interval_secs: Option<u64>,
+}
+
+/// Which of a market's tokens (by index in the Gamma outcomes array) is YES and which is NO
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct OutcomeOverride {
+    pub yes: usize,
+    pub no: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    Conservative,
+    Balanced,
+    Aggressive,
+}
+
+/// Parameters a profile sets together
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileParams {
+    pub min_edge: f64,
+    pub kelly_fraction: f64,
+    pub max_position_pct: f64,
+    pub min_confidence: f64,
+}
+
+impl Profile {
+    pub fn params(self) -> ProfileParams {
+        match self {
+            Profile::Conservative => ProfileParams {
+                min_edge: 0.15,
+                kelly_fraction: 0.15,
+                max_position_pct: 0.05,
+                min_confidence: 0.90,
+            },
+            Profile::Balanced => ProfileParams {
+                min_edge: 0.10,
+                kelly_fraction: 0.25,
+                max_position_pct: 0.10,
+                min_confidence: 0.80,
+            },
+            Profile::Aggressive => ProfileParams {
+                min_edge: 0.08,
+                kelly_fraction: 0.40,
+                max_position_pct: 0.15,
+                min_confidence: 0.70,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -29,10 +128,307 @@ pub struct StrategiesConfig {
 pub struct WeatherStrategyConfig {
     pub enabled: bool,
     pub min_edge: f64,
+    /// Fraction of full Kelly to bet
+    #[serde(default = "default_kelly_fraction")]
+    pub kelly_fraction: f64,
+    /// Skip signals whose blended forecast confidence is below this
+    #[serde(default)]
+    pub min_confidence: f64,
     pub target_cities: Vec<String>,
+    /// Extra names markets use for a city, keyed by canonical name
+    #[serde(default)]
+    pub city_aliases: HashMap<String, Vec<String>>,
+    /// (lat, lon) by city name, for cities beyond the built-in ones (or to move one)
+    #[serde(default)]
+    pub city_coords: HashMap<String, (f64, f64)>,
+    /// IANA time zone by city name, for cities beyond the built-in ones (or to move one)
+    #[serde(default)]
+    pub city_timezones: HashMap<String, Tz>,
     pub forecast_lead_time_hours: u64,
     pub polling_interval_secs: u64,
     pub polling_interval_urgent_secs: u64,
+    /// Upper bound for adaptive backoff when no markets qualify (None = fixed interval)
+    #[serde(default)]
+    pub polling_interval_max_secs: Option<u64>,
+    #[serde(default = "default_polling_backoff_factor")]
+    pub polling_backoff_factor: f64,
+    /// Consecutive empty cycles before the interval starts growing
+    #[serde(default = "default_polling_backoff_after_empty_cycles")]
+    pub polling_backoff_after_empty_cycles: u32,
+    /// Save the backed-off interval so a restart resumes it instead of polling at full rate
+    #[serde(default)]
+    pub persist_polling_state: bool,
+    /// Markets analyzed per cycle, soonest-resolving first; the rest wait for a later cycle
+    #[serde(default = "default_max_markets_per_cycle")]
+    pub max_markets_per_cycle: usize,
+    #[serde(default)]
+    pub degrees_default_unit: TemperatureUnit,
+    /// Settlement rounding at the threshold, in °C (0 = exact threshold)
+    /// Reported temps are whole degrees, so 0.5 for °C markets, ~0.28 for °F
+    #[serde(default)]
+    pub boundary_tolerance_c: f64,
+    #[serde(default = "default_true")]
+    pub parse_number_words: bool,
+    /// Tradable forecast probability band for "above" markets
+    #[serde(default)]
+    pub above_prob_bounds: ProbabilityBounds,
+    /// Tradable forecast probability band for "below" markets
+    #[serde(default)]
+    pub below_prob_bounds: ProbabilityBounds,
+    #[serde(default)]
+    pub probability_model: ProbabilityModel,
+    /// User regexes tried before the built-in question parser
+    #[serde(default)]
+    pub question_templates: Vec<QuestionTemplate>,
+    /// Price the forecast is compared against when computing edge
+    #[serde(default)]
+    pub edge_basis: EdgeBasis,
+    /// Trade on one forecast source when the other fails
+    #[serde(default)]
+    pub allow_single_source: bool,
+    /// What to do when every forecast source fails for a market
+    #[serde(default)]
+    pub on_sources_failed: SourcesFailedAction,
+    /// Markets in a row with every source down before `breaker` trips the circuit breaker
+    #[serde(default = "default_sources_failed_limit")]
+    pub sources_failed_limit: u32,
+    /// Confidence multiplier per day of lead time beyond 24h (1.0 = off)
+    #[serde(default = "default_lead_time_confidence_decay")]
+    pub lead_time_confidence_decay: f64,
+    /// Confidence and size multiplier when trading on a single source
+    #[serde(default = "default_single_source_confidence_factor")]
+    pub single_source_confidence_factor: f64,
+    /// Post resting orders this many ticks inside the ask (0 = take the ask)
+    #[serde(default)]
+    pub price_improvement_ticks: u32,
+    /// Rest entries at a limit, or take the ask
+    #[serde(default)]
+    pub entry_order: EntryOrder,
+    #[serde(default = "default_tick_size")]
+    pub tick_size: f64,
+    /// Clamp orders to this fraction of the book's ask depth on their side (unset = no cap)
+    #[serde(default)]
+    pub max_depth_consumption_pct: Option<f64>,
+    /// Consecutive losing trades before sizing down (0 = off)
+    #[serde(default)]
+    pub loss_streak_trades: u32,
+    /// Size multiplier while on a losing streak; a win restores full size
+    #[serde(default = "default_loss_streak_size_multiplier")]
+    pub loss_streak_size_multiplier: f64,
+    /// Fixed USD size replacing Kelly entirely (for live plumbing tests); risk checks still apply
+    #[serde(default)]
+    pub force_position_size_usd: Option<f64>,
+    /// Sell part of an open position once it's up this much on cost (None = hold to resolution)
+    #[serde(default)]
+    pub scale_out_gain_pct: Option<f64>,
+    /// Share of the position sold when scaling out
+    #[serde(default = "default_scale_out_fraction")]
+    pub scale_out_fraction: f64,
+    /// How long to wait for each forecast provider before going on without it
+    #[serde(default = "default_provider_timeout_ms")]
+    pub provider_timeout_ms: u64,
+    /// Per-provider overrides of `provider_timeout_ms`, keyed by provider name
+    #[serde(default)]
+    pub provider_timeouts_ms: HashMap<String, u64>,
+    /// Average all providers (true), or use only the first that answers in priority order
+    #[serde(default = "default_true")]
+    pub ensemble: bool,
+    /// Provider names, most preferred first (unlisted providers follow in their default order)
+    #[serde(default)]
+    pub provider_priority: Vec<String>,
+    /// Per-city overrides of `provider_priority`
+    #[serde(default)]
+    pub city_provider_priority: HashMap<String, Vec<String>>,
+    /// UTC times each provider publishes new model runs, keyed by provider name
+    #[serde(default)]
+    pub forecast_update_times: HashMap<String, Vec<NaiveTime>>,
+    /// Don't use a provider's forecast within this many minutes of one of its updates (0 = off)
+    #[serde(default)]
+    pub update_avoid_minutes: u32,
+    /// Open-Meteo confidence scales down when fewer hourly values than this are returned
+    #[serde(default = "default_open_meteo_min_hours")]
+    pub open_meteo_min_hours: usize,
+    /// Fit out the daily temperature cycle before taking Open-Meteo's sigma from its hourly values
+    #[serde(default = "default_true")]
+    pub open_meteo_remove_diurnal: bool,
+    /// Skip markets whose mean forecast std dev (°C) exceeds this
+    #[serde(default)]
+    pub max_forecast_sigma: Option<f64>,
+    /// Fast (1.5e-7) or precise (1e-14) erf behind forecast probabilities
+    #[serde(default)]
+    pub erf_precision: ErfPrecision,
+    /// Price entries at the average fill across book depth (when known) instead of the top ask
+    #[serde(default)]
+    pub price_impact: bool,
+    /// Min edge by market liquidity, interpolated between points; never below `min_edge`
+    #[serde(default)]
+    pub min_edge_liquidity_curve: Vec<LiquidityEdgePoint>,
+    /// Where the NO ask comes from when pricing a market
+    #[serde(default)]
+    pub no_price_source: NoPriceSource,
+    /// Also price each city/day's thresholds together and trade the most mispriced one
+    #[serde(default)]
+    pub ladder_trading: bool,
+    /// Extra venues quoting a market (by condition_id); a cheaper ask is logged, edge stays at the book
+    #[serde(default)]
+    pub price_sources: HashMap<String, Vec<VenueQuote>>,
+    /// Quotes in `price_sources` older than this, or without an `as_of`, are ignored
+    #[serde(default = "default_price_source_max_age_secs")]
+    pub price_source_max_age_secs: u64,
+    /// "Exactly N°" markets resolve YES within this many of the question's degrees of N
+    #[serde(default = "default_exactly_half_width")]
+    pub exactly_half_width: f64,
+    /// Re-fetch forecasts up to this many times when they disagree, in case one was stale
+    #[serde(default)]
+    pub disagreement_retries: u32,
+    /// Wait between those re-fetches
+    #[serde(default = "default_disagreement_retry_delay_ms")]
+    pub disagreement_retry_delay_ms: u64,
+}
+
+impl WeatherStrategyConfig {
+    /// Built-in cities plus configured aliases and any city given coordinates or a time zone
+    pub fn city_registry(&self) -> CityRegistry {
+        let mut aliases = self.city_aliases.clone();
+        for city in self.city_coords.keys().chain(self.city_timezones.keys()) {
+            aliases.entry(city.clone()).or_default();
+        }
+        CityRegistry::new(&aliases).with_timezones(&self.city_timezones)
+    }
+    
+    /// Timeout for a named forecast provider
+    pub fn provider_timeout(&self, provider: &str) -> std::time::Duration {
+        let ms = self.provider_timeouts_ms.get(provider).copied().unwrap_or(self.provider_timeout_ms);
+        std::time::Duration::from_millis(ms)
+    }
+    
+    /// Whether `at` falls within `update_avoid_minutes` of one of the provider's scheduled updates
+    pub fn in_update_window(&self, provider: &str, at: DateTime<Utc>) -> bool {
+        if self.update_avoid_minutes == 0 {
+            return false;
+        }
+        let Some((_, times)) = self.forecast_update_times.iter().find(|(name, _)| name.eq_ignore_ascii_case(provider)) else {
+            return false;
+        };
+        
+        let minute_of_day = |t: NaiveTime| (t.num_seconds_from_midnight() / 60) as i64;
+        let now = minute_of_day(at.time());
+        times.iter().any(|&update| {
+            // Distance around the clock, so 23:55 is 10 minutes from 00:05
+            let diff = (now - minute_of_day(update)).rem_euclid(1440);
+            diff.min(1440 - diff) <= self.update_avoid_minutes as i64
+        })
+    }
+    
+    /// Provider preference for a city
+    pub fn provider_priority_for(&self, city: &str) -> &[String] {
+        self.city_provider_priority
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(city))
+            .map_or(&self.provider_priority, |(_, order)| order)
+    }
+    
+    /// Minimum edge for a market with this much resting liquidity (USD)
+    /// Linear between curve points, flat past either end
+    pub fn min_edge_for_liquidity(&self, liquidity: f64) -> f64 {
+        let mut curve = self.min_edge_liquidity_curve.clone();
+        curve.sort_by(|a, b| a.liquidity.total_cmp(&b.liquidity));
+        
+        let scaled = match (curve.first(), curve.last()) {
+            (Some(first), _) if liquidity <= first.liquidity => first.min_edge,
+            (_, Some(last)) if liquidity >= last.liquidity => last.min_edge,
+            (None, _) | (_, None) => self.min_edge,
+            _ => curve
+                .windows(2)
+                .find(|w| liquidity <= w[1].liquidity)
+                .map(|w| {
+                    let t = (liquidity - w[0].liquidity) / (w[1].liquidity - w[0].liquidity);
+                    w[0].min_edge + t * (w[1].min_edge - w[0].min_edge)
+                })
+                .unwrap_or(self.min_edge),
+        };
+        scaled.max(self.min_edge)
+    }
+}
+
+/// A point on the liquidity -> min edge curve
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct LiquidityEdgePoint {
+    /// Resting liquidity (USD)
+    pub liquidity: f64,
+    pub min_edge: f64,
+}
+
+/// What `min_edge` is measured against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeBasis {
+    /// Market mid-price (overstates realizable edge by half the spread)
+    #[default]
+    Mid,
+    /// Ask of the side we'd enter (realizable edge)
+    Ask,
+}
+
+/// Response to every forecast source failing for a market
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourcesFailedAction {
+    /// Skip the market and carry on with the cycle
+    #[default]
+    Skip,
+    /// Skip the market, and trip the ApiErrors breaker once `sources_failed_limit` markets in a row fail
+    Breaker,
+}
+
+/// Order intent for new weather positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryOrder {
+    /// GTC at the entry price (less any price improvement)
+    #[default]
+    Limit,
+    /// FOK at the ask
+    Market,
+}
+
+/// Source of a market's NO ask
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoPriceSource {
+    /// Best ask of the NO token's own book
+    #[default]
+    Book,
+    /// 1 - best YES bid (skips the NO book fetch; ignores NO-side liquidity)
+    Complement,
+}
+
+/// Regex for a market question format the built-in parser doesn't know.
+/// Named captures: `city` and `threshold` (required), `unit` and `comparison` (optional).
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestionTemplate {
+    pub pattern: String,
+    /// Used when the pattern has no `comparison` capture
+    #[serde(default)]
+    pub comparison: Option<Comparison>,
+    /// Used when the pattern has no `unit` capture
+    #[serde(default)]
+    pub unit: Option<TemperatureUnit>,
+}
+
+/// Forecast probabilities outside [floor, ceiling] are clamped to the band
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ProbabilityBounds {
+    #[serde(default)]
+    pub floor: f64,
+    #[serde(default = "default_prob_ceiling")]
+    pub ceiling: f64,
+}
+
+impl Default for ProbabilityBounds {
+    fn default() -> Self {
+        Self { floor: 0.0, ceiling: 1.0 }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -41,6 +437,23 @@ pub struct ArbitrageStrategyConfig {
     pub min_spread: f64,
     pub min_spread_15min_crypto: f64,
     pub execution_timeout_ms: u64,
+    /// What to do when only one leg fills
+    #[serde(default)]
+    pub on_legged: LeggedAction,
+    /// A hedge may pay up to this much over the missing leg's original limit
+    #[serde(default = "default_hedge_price_tolerance")]
+    pub hedge_price_tolerance: f64,
+}
+
+/// Response to a legged arb (one side filled, the other didn't)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LeggedAction {
+    /// Sell the filled leg back
+    #[default]
+    Unwind,
+    /// Cross the spread for the missing leg, unwinding only if that fails too
+    Hedge,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -48,14 +461,50 @@ pub struct RiskConfig {
     pub max_position_size_usd: f64,
     pub max_position_pct: f64,
     pub max_open_positions: usize,
+    /// Open-position caps per strategy name ("weather_edge", "sum_to_one_arb"), within the global cap
+    #[serde(default)]
+    pub max_open_positions_per_strategy: HashMap<String, usize>,
+    /// Resting orders not yet filled, counted separately from positions
+    #[serde(default = "default_max_open_orders")]
+    pub max_open_orders: usize,
     pub max_daily_trades: usize,
     pub max_daily_loss_usd: f64,
     pub max_drawdown_pct: f64,
     pub max_positions_per_city_per_day: usize,
+    #[serde(default = "default_max_positions_per_event")]
+    pub max_positions_per_event: usize,
+    /// Hours after a losing close before that market or event can be traded again (0 = off)
+    #[serde(default)]
+    pub loss_cooldown_hours: u64,
     pub claude_validation_weather: bool,
     pub claude_validation_arb: bool,
     pub min_liquidity_usd: f64,
+    /// Minimum 24h traded volume, independent of resting liquidity
+    #[serde(default = "default_min_volume_24h")]
+    pub min_volume_24h: f64,
     pub max_gas_gwei: u64,
+    /// Execution backends refuse any single order costing more than this fraction of balance
+    #[serde(default = "default_max_order_balance_fraction")]
+    pub max_order_balance_fraction: f64,
+    /// Check each fill's token, size and price against the order that produced it
+    #[serde(default = "default_true")]
+    pub verify_fills: bool,
+    /// Max |executed - intended| price before a fill counts as a discrepancy
+    #[serde(default = "default_fill_price_band")]
+    pub fill_price_band: f64,
+    /// Trip the circuit breaker on any fill discrepancy
+    #[serde(default)]
+    pub halt_on_fill_discrepancy: bool,
+    /// Execution backends refuse orders beyond this many per minute (unset = no limit)
+    #[serde(default)]
+    pub max_orders_per_minute: Option<u32>,
+    /// Trip the circuit breaker instead of opening positions once balance is below this (unset = off)
+    /// Paper trading only: the balance is the simulator's, and nothing reads the venue's yet
+    #[serde(default)]
+    pub min_balance_floor_usd: Option<f64>,
+    /// Trip the circuit breaker once reconciliation finds this many shares unaccounted for
+    #[serde(default = "default_reconcile_halt_shares")]
+    pub reconcile_halt_shares: f64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -69,6 +518,12 @@ pub struct InfrastructureConfig {
     pub websocket_staleness_threshold_secs: u64,
     pub cache_ttl_arb_ms: u64,
     pub cache_ttl_weather_secs: u64,
+    /// Arb refuses cached prices older than this, even before the TTL evicts them
+    #[serde(default = "default_max_age_for_trade_arb_ms")]
+    pub max_age_for_trade_arb_ms: u64,
+    /// HTTP requests in flight at once, across every API client
+    #[serde(default = "default_max_concurrent_http")]
+    pub max_concurrent_http: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -77,6 +532,27 @@ pub struct MonitoringConfig {
     pub csv_log_path: String,
     pub prometheus_enabled: bool,
     pub telegram_enabled: bool,
+    /// Log (and send via Telegram, if enabled) a summary of each UTC day
+    #[serde(default)]
+    pub daily_summary_enabled: bool,
+    /// UTC time the summary of the last complete day goes out ("HH:MM")
+    #[serde(default = "default_daily_summary_time")]
+    pub daily_summary_time: NaiveTime,
+    /// Record declined trades and why, for threshold tuning
+    #[serde(default = "default_true")]
+    pub log_rejections: bool,
+    /// Keep markets whose questions fail to parse (question + error) in the dead_letters table
+    #[serde(default = "default_true")]
+    pub log_dead_letters: bool,
+    /// Fee on winnings assumed when computing open-position EV
+    #[serde(default = "default_ev_fee_rate")]
+    pub ev_fee_rate: f64,
+    /// Re-fetch markets with open positions each cycle and flag amended questions for review
+    #[serde(default = "default_true")]
+    pub check_question_changes: bool,
+    /// Re-forecast open positions each cycle and warn about those whose EV has turned negative
+    #[serde(default = "default_true")]
+    pub check_negative_ev: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -87,13 +563,102 @@ pub struct PaperTradingConfig {
     pub fill_rate: f64,
     #[serde(default = "default_slippage")]
     pub slippage_pct: f64,
+    /// Reject fills whose simulated slippage exceeds this
+    #[serde(default)]
+    pub max_slippage_pct: Option<f64>,
     #[serde(default = "default_balance")]
     pub initial_balance_usd: f64,
+    /// Simulated submission latency range (0 disables)
+    #[serde(default)]
+    pub latency_min_ms: u64,
+    #[serde(default)]
+    pub latency_max_ms: u64,
+    /// RNG seed for reproducible paper runs
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Fill rate multiplier per tick of price improvement
+    #[serde(default = "default_improved_fill_decay")]
+    pub improved_fill_decay: f64,
+    /// Chance an aggressive hedge order (crossing the spread) fills
+    #[serde(default = "default_hedge_fill_rate")]
+    pub hedge_fill_rate: f64,
+    /// JSON file of real order outcomes to replay instead of fill_rate/slippage_pct
+    #[serde(default)]
+    pub recorded_fills_path: Option<String>,
 }
 
+fn default_ev_fee_rate() -> f64 { 0.02 }
+
+fn default_scale_out_fraction() -> f64 { 0.5 }
+
+fn default_true() -> bool { true }
+
+fn default_disagreement_retry_delay_ms() -> u64 { 5000 }
+
+fn default_exactly_half_width() -> f64 { 0.5 }
+
+fn default_db_open_attempts() -> u32 { 5 }
+
+fn default_db_open_backoff_ms() -> u64 { 500 }
+
+fn default_settlement_grace_secs() -> u64 { 7200 }
+
+fn default_backup_interval_secs() -> u64 { 86400 }
+
+fn default_backup_keep() -> usize { 7 }
+
+fn default_kelly_fraction() -> f64 { 0.25 }
+
+fn default_single_source_confidence_factor() -> f64 { 0.5 }
+
+fn default_lead_time_confidence_decay() -> f64 { 1.0 }
+
+fn default_sources_failed_limit() -> u32 { 3 }
+
+fn default_tick_size() -> f64 { 0.01 }
+
+fn default_loss_streak_size_multiplier() -> f64 { 0.5 }
+
+fn default_provider_timeout_ms() -> u64 { 10_000 }
+
+fn default_open_meteo_min_hours() -> usize { 24 }
+
+fn default_polling_backoff_factor() -> f64 { 2.0 }
+
+fn default_polling_backoff_after_empty_cycles() -> u32 { 3 }
+
+fn default_max_markets_per_cycle() -> usize { 50 }
+
+fn default_prob_ceiling() -> f64 { 1.0 }
+
+fn default_max_positions_per_event() -> usize { 1 }
+
+fn default_max_open_orders() -> usize { 5 }
+
+fn default_max_age_for_trade_arb_ms() -> u64 { 300 }
+
+fn default_min_volume_24h() -> f64 { 5000.0 }
+
+fn default_max_order_balance_fraction() -> f64 { 0.25 }
+
+fn default_fill_price_band() -> f64 { 0.02 }
+
+fn default_reconcile_halt_shares() -> f64 { 10.0 }
+
+fn default_daily_summary_time() -> NaiveTime { NaiveTime::MIN }
+
+fn default_price_source_max_age_secs() -> u64 { 900 }
+
+fn default_max_concurrent_http() -> usize { crate::data::http::DEFAULT_MAX_CONCURRENT_HTTP }
+
 fn default_fill_rate() -> f64 { 0.70 }
 fn default_slippage() -> f64 { 0.005 }
 fn default_balance() -> f64 { 2000.0 }
+fn default_improved_fill_decay() -> f64 { 0.75 }
+
+fn default_hedge_fill_rate() -> f64 { 0.95 }
+
+fn default_hedge_price_tolerance() -> f64 { 0.01 }
 
 #[derive(Debug, Clone)]
 pub struct EnvConfig {
@@ -106,6 +671,8 @@ pub struct EnvConfig {
     pub polymarket_gamma_url: String,
     pub polymarket_ws_url: String,
     pub dry_run: bool,
+    pub telegram_bot_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
 }
 
 impl Config {
@@ -113,37 +680,349 @@ impl Config {
         let contents = fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path))?;
         
-        let config: Config = toml::from_str(&contents)
-            .with_context(|| format!("Failed to parse config file: {}", path))?;
+        Self::from_toml_str(&contents)
+            .with_context(|| format!("Failed to parse config file: {}", path))
+    }
+    
+    /// Parse config, filling keys not set explicitly from the selected profile
+    pub fn from_toml_str(contents: &str) -> Result<Self> {
+        let mut value: toml::Value = toml::from_str(contents)?;
+        
+        let profile = value.get("system")
+            .and_then(|system| system.get("profile"))
+            .cloned()
+            .map(|p| p.try_into::<Profile>())
+            .transpose()?;
+        
+        if let Some(profile) = profile {
+            let params = profile.params();
+            let overridden: Vec<String> = [
+                (&["strategies", "weather", "min_edge"][..], params.min_edge),
+                (&["strategies", "weather", "kelly_fraction"][..], params.kelly_fraction),
+                (&["strategies", "weather", "min_confidence"][..], params.min_confidence),
+                (&["risk", "max_position_pct"][..], params.max_position_pct),
+            ]
+            .into_iter()
+            .filter(|(path, default)| set_default(&mut value, path, *default))
+            .map(|(path, default)| format!("{} (profile: {})", path.join("."), default))
+            .collect();
+            
+            // A profile whose every key is set explicitly does nothing; say so
+            if !overridden.is_empty() {
+                tracing::info!("Profile {:?} overridden by explicit keys: {}", profile, overridden.join(", "));
+            }
+        }
         
+        let config: Self = value.try_into()?;
+        for template in &config.strategies.weather.question_templates {
+            CompiledTemplate::compile(template)?;
+        }
         Ok(config)
     }
 }
 
+/// Insert a float at a nested table path unless the key is already present
+/// Returns true if the key was already set
+fn set_default(value: &mut toml::Value, path: &[&str], default: f64) -> bool {
+    let (key, tables) = path.split_last().expect("non-empty path");
+    
+    let mut current = value;
+    for table in tables {
+        let Some(next) = current.as_table_mut().map(|t| {
+            t.entry(table.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+        }) else {
+            return false;
+        };
+        current = next;
+    }
+    
+    let Some(table) = current.as_table_mut() else {
+        return false;
+    };
+    if table.contains_key(*key) {
+        return true;
+    }
+    table.insert(key.to_string(), toml::Value::Float(default));
+    false
+}
+
+/// Only the keys without defaults, so tests don't move when the shipped config.toml is tuned
+#[cfg(test)]
+pub const TEST_CONFIG_TOML: &str = r#"
+[system]
+dry_run = true
+database_path = ":memory:"
+
+[strategies.weather]
+enabled = true
+min_edge = 0.10
+target_cities = ["London", "New York", "Chicago", "Seoul"]
+forecast_lead_time_hours = 24
+polling_interval_secs = 3600
+polling_interval_urgent_secs = 900
+
+[strategies.arbitrage]
+enabled = false
+min_spread = 0.025
+min_spread_15min_crypto = 0.035
+execution_timeout_ms = 500
+
+[risk]
+max_position_size_usd = 50.0
+max_position_pct = 0.10
+max_open_positions = 2
+max_daily_trades = 5
+max_daily_loss_usd = 50.0
+max_drawdown_pct = 0.15
+max_positions_per_city_per_day = 1
+claude_validation_weather = true
+claude_validation_arb = false
+min_liquidity_usd = 5000.0
+max_gas_gwei = 100
+
+[infrastructure]
+primary_rpc = "alchemy"
+secondary_rpc = "quicknode"
+rpc_timeout_secs = 5
+rpc_failover_enabled = true
+websocket_reconnect_backoff_secs = 1
+websocket_max_reconnect_delay_secs = 60
+websocket_staleness_threshold_secs = 2
+cache_ttl_arb_ms = 500
+cache_ttl_weather_secs = 300
+
+[monitoring]
+csv_logging = false
+csv_log_path = "trades.csv"
+prometheus_enabled = false
+telegram_enabled = false
+
+[paper_trading]
+enabled = true
+"#;
+
+#[cfg(test)]
+impl Config {
+    /// Config for tests: `TEST_CONFIG_TOML`, everything else at its default
+    pub fn for_test() -> Self {
+        Self::from_toml_str(TEST_CONFIG_TOML).expect("test config parses")
+    }
+}
+
+/// Optional env vars, reported present/absent at startup (values are never logged)
+const OPTIONAL_ENV_VARS: &[&str] = &[
+    "NOAA_API_KEY",
+    "POLYMARKET_CLOB_URL",
+    "POLYMARKET_GAMMA_URL",
+    "POLYMARKET_WS_URL",
+    "DRY_RUN",
+    "TELEGRAM_BOT_TOKEN",
+    "TELEGRAM_CHAT_ID",
+];
+
 impl EnvConfig {
     pub fn load() -> Result<Self> {
         dotenv::dotenv().ok();
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+    
+    /// Build from a variable lookup, failing fast on anything missing or malformed
+    fn from_lookup(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let required = |key: &str| var(key).with_context(|| format!("{} not set", key));
+        
+        let (present, absent): (Vec<&str>, Vec<&str>) =
+            OPTIONAL_ENV_VARS.iter().partition(|key| var(key).is_some());
+        tracing::info!(
+            "Optional env vars set: [{}]; unset: [{}]",
+            present.join(", "),
+            absent.join(", ")
+        );
         
         Ok(Self {
-            polygon_rpc_primary: std::env::var("POLYGON_RPC_PRIMARY")
-                .context("POLYGON_RPC_PRIMARY not set")?,
-            polygon_rpc_secondary: std::env::var("POLYGON_RPC_SECONDARY")
-                .context("POLYGON_RPC_SECONDARY not set")?,
-            polygon_wallet_private_key: std::env::var("POLYGON_WALLET_PRIVATE_KEY")
-                .context("POLYGON_WALLET_PRIVATE_KEY not set")?,
-            anthropic_api_key: std::env::var("ANTHROPIC_API_KEY")
-                .context("ANTHROPIC_API_KEY not set")?,
-            noaa_api_key: std::env::var("NOAA_API_KEY").ok(),
-            polymarket_clob_url: std::env::var("POLYMARKET_CLOB_URL")
-                .unwrap_or_else(|_| "https://clob.polymarket.com".to_string()),
-            polymarket_gamma_url: std::env::var("POLYMARKET_GAMMA_URL")
-                .unwrap_or_else(|_| "https://gamma-api.polymarket.com".to_string()),
-            polymarket_ws_url: std::env::var("POLYMARKET_WS_URL")
-                .unwrap_or_else(|_| "wss://ws-subscriptions-clob.polymarket.com/ws/".to_string()),
-            dry_run: std::env::var("DRY_RUN")
-                .unwrap_or_else(|_| "true".to_string())
-                .parse()
-                .unwrap_or(true),
+            polygon_rpc_primary: required("POLYGON_RPC_PRIMARY")?,
+            polygon_rpc_secondary: required("POLYGON_RPC_SECONDARY")?,
+            polygon_wallet_private_key: required("POLYGON_WALLET_PRIVATE_KEY")?,
+            anthropic_api_key: required("ANTHROPIC_API_KEY")?,
+            noaa_api_key: var("NOAA_API_KEY"),
+            polymarket_clob_url: var("POLYMARKET_CLOB_URL")
+                .unwrap_or_else(|| "https://clob.polymarket.com".to_string()),
+            polymarket_gamma_url: var("POLYMARKET_GAMMA_URL")
+                .unwrap_or_else(|| "https://gamma-api.polymarket.com".to_string()),
+            polymarket_ws_url: var("POLYMARKET_WS_URL")
+                .unwrap_or_else(|| "wss://ws-subscriptions-clob.polymarket.com/ws/".to_string()),
+            dry_run: parse_dry_run(var("DRY_RUN").as_deref())?,
+            telegram_bot_token: var("TELEGRAM_BOT_TOKEN"),
+            telegram_chat_id: var("TELEGRAM_CHAT_ID"),
         })
     }
 }
+
+/// Refuse to start when config, `DRY_RUN` and the wallet key disagree about whether we're live
+pub fn check_mode_consistency(config: &Config, env: &EnvConfig) -> Result<()> {
+    if config.system.dry_run != env.dry_run {
+        anyhow::bail!(
+            "config dry_run = {} but DRY_RUN = {}; set both to the same mode",
+            config.system.dry_run, env.dry_run
+        );
+    }
+    
+    let key = env.polygon_wallet_private_key.trim();
+    let has_key = !key.is_empty() && key != "0x...";
+    
+    if !config.system.dry_run && !has_key {
+        anyhow::bail!("Live mode (dry_run = false) but POLYGON_WALLET_PRIVATE_KEY is empty");
+    }
+    if config.system.dry_run && has_key {
+        if !config.system.allow_key_in_dry_run {
+            anyhow::bail!("Wallet key loaded in dry-run mode and allow_key_in_dry_run = false");
+        }
+        tracing::warn!("Wallet key loaded while dry-running; no live orders will be sent");
+    }
+    Ok(())
+}
+
+/// `DRY_RUN` defaults to true when unset; a value that isn't clearly a boolean is an error,
+/// since silently staying dry would hide a typo meant to go live (and vice versa)
+fn parse_dry_run(value: Option<&str>) -> Result<bool> {
+    let Some(value) = value else {
+        return Ok(true);
+    };
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" => Ok(true),
+        "false" | "0" | "no" => Ok(false),
+        _ => anyhow::bail!("DRY_RUN must be true or false, got {:?}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn env_lookup(dry_run: Option<&str>) -> impl Fn(&str) -> Option<String> {
+        let dry_run = dry_run.map(str::to_string);
+        move |key| match key {
+            "POLYGON_RPC_PRIMARY" | "POLYGON_RPC_SECONDARY" | "POLYGON_WALLET_PRIVATE_KEY"
+            | "ANTHROPIC_API_KEY" => Some("x".to_string()),
+            "DRY_RUN" => dry_run.clone(),
+            _ => None,
+        }
+    }
+    
+    #[test]
+    fn test_contradictory_modes_are_rejected() {
+        let mut config = Config::for_test();
+        let mut env = EnvConfig::from_lookup(env_lookup(None)).unwrap();
+        env.polygon_wallet_private_key = String::new();
+        
+        // Dry-run everywhere, no key
+        assert!(check_mode_consistency(&config, &env).is_ok());
+        
+        // Config says live, env says dry
+        config.system.dry_run = false;
+        assert!(check_mode_consistency(&config, &env).is_err());
+        
+        // Live in both, but nothing to sign with
+        env.dry_run = false;
+        assert!(check_mode_consistency(&config, &env).unwrap_err().to_string().contains("Live mode"));
+        env.polygon_wallet_private_key = "0x...".to_string();
+        assert!(check_mode_consistency(&config, &env).is_err());
+        env.polygon_wallet_private_key = "0xabc123".to_string();
+        assert!(check_mode_consistency(&config, &env).is_ok());
+        
+        // Live key while dry-running: allowed unless disabled
+        config.system.dry_run = true;
+        env.dry_run = true;
+        assert!(check_mode_consistency(&config, &env).is_ok());
+        config.system.allow_key_in_dry_run = false;
+        assert!(check_mode_consistency(&config, &env).is_err());
+    }
+    
+    #[test]
+    fn test_unparseable_dry_run_errors() {
+        let err = EnvConfig::from_lookup(env_lookup(Some("maybe"))).err().unwrap();
+        assert!(err.to_string().contains("DRY_RUN"));
+        
+        assert!(EnvConfig::from_lookup(env_lookup(None)).unwrap().dry_run);
+        assert!(!EnvConfig::from_lookup(env_lookup(Some("false"))).unwrap().dry_run);
+        assert!(EnvConfig::from_lookup(env_lookup(Some("TRUE"))).unwrap().dry_run);
+    }
+    
+    #[test]
+    fn test_shipped_config_parses() {
+        let config = Config::from_toml_str(include_str!("../config.toml")).unwrap();
+        
+        // The shipped profile supplies the keys left commented out
+        let balanced = Profile::Balanced.params();
+        assert_eq!(config.system.profile, Some(Profile::Balanced));
+        assert_eq!(config.strategies.weather.min_edge, balanced.min_edge);
+        assert_eq!(config.strategies.weather.kelly_fraction, balanced.kelly_fraction);
+        assert_eq!(config.risk.max_position_pct, balanced.max_position_pct);
+    }
+    
+    #[test]
+    fn test_bad_question_template_fails_load() {
+        let with_template = |pattern: &str| {
+            let mut value: toml::Value = toml::from_str(TEST_CONFIG_TOML).unwrap();
+            let mut template = toml::map::Map::new();
+            template.insert("pattern".to_string(), toml::Value::String(pattern.to_string()));
+            value["strategies"]["weather"]
+                .as_table_mut()
+                .unwrap()
+                .insert("question_templates".to_string(), toml::Value::Array(vec![toml::Value::Table(template)]));
+            Config::from_toml_str(&toml::to_string(&value).unwrap())
+        };
+        
+        assert!(with_template(r"(?P<city>\w+) hits (?P<threshold>\d+)").is_ok());
+        assert!(with_template(r"(?P<city>\w+) hits (\d+)").is_err()); // no threshold capture
+        assert!(with_template(r"(?P<city>\w+ (?P<threshold>\d+)").is_err()); // unbalanced
+    }
+    
+    /// Test config with the given weather/risk keys removed and a profile selected
+    fn config_with_profile(profile: &str, remove: &[(&str, &str)]) -> Config {
+        let mut value: toml::Value = toml::from_str(TEST_CONFIG_TOML).unwrap();
+        
+        for (section, key) in remove {
+            let table = match *section {
+                "weather" => &mut value["strategies"]["weather"],
+                other => &mut value[other],
+            };
+            table.as_table_mut().unwrap().remove(*key);
+        }
+        value["system"]
+            .as_table_mut()
+            .unwrap()
+            .insert("profile".to_string(), toml::Value::String(profile.to_string()));
+        
+        Config::from_toml_str(&toml::to_string(&value).unwrap()).unwrap()
+    }
+    
+    #[test]
+    fn test_conservative_profile_applies_bundle() {
+        let config = config_with_profile(
+            "conservative",
+            &[("weather", "min_edge"), ("risk", "max_position_pct")],
+        );
+        
+        let weather = &config.strategies.weather;
+        assert_eq!(config.system.profile, Some(Profile::Conservative));
+        assert_eq!(weather.kelly_fraction, 0.15);
+        assert_eq!(weather.min_edge, 0.15);
+        assert_eq!(weather.min_confidence, 0.90);
+        assert_eq!(config.risk.max_position_pct, 0.05);
+        
+        // Lower Kelly and higher edge bar than the balanced bundle
+        let balanced = Profile::Balanced.params();
+        assert!(weather.kelly_fraction < balanced.kelly_fraction);
+        assert!(weather.min_edge > balanced.min_edge);
+    }
+    
+    #[test]
+    fn test_explicit_keys_override_profile() {
+        // min_edge stays set explicitly (0.10)
+        let config = config_with_profile("conservative", &[]);
+        
+        assert_eq!(config.strategies.weather.min_edge, 0.10);
+        assert_eq!(config.strategies.weather.kelly_fraction, 0.15);
+    }
+}
@@ -1,8 +1,11 @@
 use dashmap::DashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 pub struct PriceCache {
     cache: DashMap<String, CachedPrice>,
+    /// Current time; replaceable so tests can age entries without sleeping
+    clock: Arc<dyn Fn() -> Instant + Send + Sync>,
 }
 
 struct CachedPrice {
@@ -15,9 +18,20 @@ impl PriceCache {
     pub fn new() -> Self {
         Self {
             cache: DashMap::new(),
+            clock: Arc::new(Instant::now),
         }
     }
     
+    /// Read the time from `clock` instead of `Instant::now`
+    pub fn with_clock(mut self, clock: impl Fn() -> Instant + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+    
+    fn age(&self, entry: &CachedPrice) -> Duration {
+        (self.clock)().saturating_duration_since(entry.timestamp)
+    }
+    
     /// Insert price with strategy-aware TTL
     pub fn insert(&self, key: String, price: f64, strategy: &str) {
         let ttl = match strategy {
@@ -28,7 +42,7 @@ impl PriceCache {
         
         self.cache.insert(key, CachedPrice {
             price,
-            timestamp: Instant::now(),
+            timestamp: (self.clock)(),
             ttl,
         });
     }
@@ -37,7 +51,7 @@ impl PriceCache {
     pub fn get(&self, key: &str) -> Option<f64> {
         self.cache.get(key).and_then(|entry| {
             // Check if expired
-            if entry.timestamp.elapsed() > entry.ttl {
+            if self.age(&entry) > entry.ttl {
                 drop(entry); // Drop the read lock
                 self.cache.remove(key); // Evict stale entry
                 None
@@ -47,6 +61,16 @@ impl PriceCache {
         })
     }
     
+    /// Get price only if younger than `max_age` (the trade threshold, tighter than the TTL)
+    /// A near-expiry entry stays cached for `get` but is too old to act on
+    pub fn get_fresh(&self, key: &str, max_age: Duration) -> Option<f64> {
+        let price = self.get(key)?; // evicts if past the TTL
+        self.cache
+            .get(key)
+            .filter(|entry| self.age(entry) <= max_age)
+            .map(|_| price)
+    }
+    
     /// Clear all entries
     pub fn clear(&self) {
         self.cache.clear();
@@ -72,7 +96,14 @@ impl Default for PriceCache {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::thread;
+    use std::sync::Mutex;
+    
+    /// A cache on a clock the test moves forward, and the handle to move it
+    fn cache_with_clock() -> (PriceCache, Arc<Mutex<Instant>>) {
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock = now.clone();
+        (PriceCache::new().with_clock(move || *clock.lock().unwrap()), now)
+    }
     
     #[test]
     fn test_cache_insert_and_get() {
@@ -84,14 +115,14 @@ mod tests {
     
     #[test]
     fn test_cache_ttl_expiration() {
-        let cache = PriceCache::new();
+        let (cache, now) = cache_with_clock();
         cache.insert("ETH".to_string(), 3000.0, "sum_to_one_arb"); // 500ms TTL
         
         // Should exist immediately
         assert_eq!(cache.get("ETH"), Some(3000.0));
         
-        // Wait for expiration
-        thread::sleep(Duration::from_millis(600));
+        // Past expiration
+        *now.lock().unwrap() += Duration::from_millis(600);
         
         // Should be evicted
         assert_eq!(cache.get("ETH"), None);
@@ -99,7 +130,7 @@ mod tests {
     
     #[test]
     fn test_different_ttls() {
-        let cache = PriceCache::new();
+        let (cache, now) = cache_with_clock();
         
         // Arb: 500ms TTL
         cache.insert("ARB".to_string(), 1.0, "sum_to_one_arb");
@@ -107,7 +138,7 @@ mod tests {
         // Weather: 5min TTL
         cache.insert("WEATHER".to_string(), 2.0, "weather_edge");
         
-        thread::sleep(Duration::from_millis(600));
+        *now.lock().unwrap() += Duration::from_millis(600);
         
         // Arb should be expired
         assert_eq!(cache.get("ARB"), None);
@@ -115,4 +146,17 @@ mod tests {
         // Weather should still be valid
         assert_eq!(cache.get("WEATHER"), Some(2.0));
     }
+    
+    #[test]
+    fn test_near_expiry_entry_is_not_fresh_enough_to_trade() {
+        let (cache, now) = cache_with_clock();
+        cache.insert("ARB".to_string(), 0.48, "sum_to_one_arb"); // 500ms TTL
+        assert_eq!(cache.get_fresh("ARB", Duration::from_millis(300)), Some(0.48));
+        
+        *now.lock().unwrap() += Duration::from_millis(450);
+        
+        // Not yet evicted, but too close to stale to trade on
+        assert_eq!(cache.get("ARB"), Some(0.48));
+        assert_eq!(cache.get_fresh("ARB", Duration::from_millis(300)), None);
+    }
 }
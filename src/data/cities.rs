@@ -0,0 +1,152 @@
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+/// Supported cities and the names markets use for them
+#[derive(Debug, Clone)]
+pub struct CityRegistry {
+    /// Canonical name and its lowercased aliases (including the name itself)
+    cities: Vec<(String, Vec<String>)>,
+    /// Local time zone by canonical name
+    timezones: HashMap<String, Tz>,
+}
+
+impl Default for CityRegistry {
+    fn default() -> Self {
+        Self::new(&HashMap::new())
+    }
+}
+
+impl CityRegistry {
+    /// Built-in cities, plus extra aliases keyed by canonical name (new names add a city)
+    pub fn new(extra_aliases: &HashMap<String, Vec<String>>) -> Self {
+        let mut cities: Vec<(String, Vec<String>)> = [
+            ("London", &["london"][..]),
+            ("New York", &["new york", "nyc"][..]),
+            ("Chicago", &["chicago"][..]),
+            ("Seoul", &["seoul"][..]),
+        ]
+        .iter()
+        .map(|(name, aliases)| (name.to_string(), aliases.iter().map(|a| a.to_string()).collect()))
+        .collect();
+        
+        // Sorted so extra cities are registered in a stable order
+        let mut extra: Vec<_> = extra_aliases.iter().collect();
+        extra.sort();
+        for (name, aliases) in extra {
+            let index = match cities.iter().position(|(c, _)| c.eq_ignore_ascii_case(name)) {
+                Some(i) => i,
+                None => {
+                    cities.push((name.clone(), vec![name.to_lowercase()]));
+                    cities.len() - 1
+                }
+            };
+            for alias in aliases {
+                let alias = alias.trim().to_lowercase();
+                if !alias.is_empty() && !cities[index].1.contains(&alias) {
+                    cities[index].1.push(alias);
+                }
+            }
+        }
+        
+        let timezones = [
+            ("London", chrono_tz::Europe::London),
+            ("New York", chrono_tz::America::New_York),
+            ("Chicago", chrono_tz::America::Chicago),
+            ("Seoul", chrono_tz::Asia::Seoul),
+        ]
+        .iter()
+        .map(|(name, tz)| (name.to_string(), *tz))
+        .collect();
+        
+        Self { cities, timezones }
+    }
+    
+    /// Set or override cities' time zones, keyed by canonical name
+    pub fn with_timezones(mut self, timezones: &HashMap<String, Tz>) -> Self {
+        for (name, tz) in timezones {
+            let name = self.canonical(name).unwrap_or(name).to_string();
+            self.timezones.insert(name, *tz);
+        }
+        self
+    }
+    
+    /// Local time zone of a city (canonical name or alias), UTC if none is known
+    pub fn timezone(&self, city: &str) -> Tz {
+        self.canonical(city)
+            .and_then(|name| self.timezones.get(name))
+            .copied()
+            .unwrap_or(Tz::UTC)
+    }
+    
+    /// Canonical name for a city name or alias, any case
+    fn canonical(&self, city: &str) -> Option<&str> {
+        let city_lower = city.to_lowercase();
+        self.cities
+            .iter()
+            .find(|(name, aliases)| name.to_lowercase() == city_lower || aliases.contains(&city_lower))
+            .map(|(name, _)| name.as_str())
+    }
+    
+    /// Canonical name of the first city mentioned in lowercased text
+    pub fn resolve(&self, text_lower: &str) -> Option<&str> {
+        self.cities
+            .iter()
+            .find(|(_, aliases)| aliases.iter().any(|a| contains_word(text_lower, a)))
+            .map(|(name, _)| name.as_str())
+    }
+    
+    /// Whether lowercased text mentions `city` (a canonical name or any of its aliases)
+    pub fn mentions(&self, city: &str, text_lower: &str) -> bool {
+        let city_lower = city.to_lowercase();
+        match self.cities.iter().find(|(name, aliases)| {
+            name.to_lowercase() == city_lower || aliases.contains(&city_lower)
+        }) {
+            Some((_, aliases)) => aliases.iter().any(|a| contains_word(text_lower, a)),
+            None => contains_word(text_lower, &city_lower),
+        }
+    }
+}
+
+/// `needle` appears in `haystack` as whole words ("ny" doesn't match "any")
+fn contains_word(haystack: &str, needle: &str) -> bool {
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::gamma_api::{parse_weather_question, ParseOptions};
+    
+    #[test]
+    fn test_configured_alias_resolves_to_city() {
+        let mut extra = HashMap::new();
+        extra.insert("New York".to_string(), vec!["Manhattan".to_string(), "NY".to_string()]);
+        let registry = CityRegistry::new(&extra);
+        
+        assert_eq!(registry.resolve("will manhattan's high exceed 60°f?"), Some("New York"));
+        assert_eq!(registry.resolve("will ny reach 60°f?"), Some("New York"));
+        assert!(registry.mentions("New York", "manhattan temperature above 60°f"));
+        assert!(registry.mentions("NYC", "manhattan temperature above 60°f"));
+        
+        let options = ParseOptions { cities: registry.clone(), ..ParseOptions::default() };
+        let info = parse_weather_question("Will Manhattan temperature exceed 60°F?", &options).unwrap();
+        assert_eq!(info.city, "New York");
+        
+        // Aliases match whole words only
+        assert_eq!(registry.resolve("will any city exceed 60°f?"), None);
+        assert_eq!(CityRegistry::default().resolve("will manhattan exceed 60°f?"), None);
+        
+        // Aliases share their city's zone; configured cities get theirs, others are UTC
+        assert_eq!(registry.timezone("Manhattan"), chrono_tz::America::New_York);
+        let mut extra = HashMap::new();
+        extra.insert("Miami".to_string(), Vec::new());
+        let zones = HashMap::from([("miami".to_string(), chrono_tz::America::New_York)]);
+        let registry = CityRegistry::new(&extra).with_timezones(&zones);
+        assert_eq!(registry.timezone("Miami"), chrono_tz::America::New_York);
+        assert_eq!(registry.timezone("Lagos"), Tz::UTC);
+    }
+}
@@ -1,15 +1,27 @@
 use anyhow::{Context, Result};
-use reqwest::Client;
+use crate::data::http::HttpClient;
 use serde::Deserialize;
-use chrono::{DateTime, Utc};
-use crate::data::types::Market;
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use crate::config::{OutcomeOverride, QuestionTemplate, RiskConfig, WeatherStrategyConfig};
+use crate::execution::risk::check_market_activity;
+use crate::execution::settlement::Resolution;
+use crate::strategies::types::Side;
+use crate::data::cities::CityRegistry;
+use crate::data::types::{ForecastKind, Market, TemperatureUnit};
+use tracing::info;
 
 pub struct GammaApiClient {
-    client: Client,
+    http: HttpClient,
     base_url: String,
+    cities: CityRegistry,
+    outcome_overrides: HashMap<String, OutcomeOverride>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct GammaMarket {
     #[allow(dead_code)]
     condition_id: String,
@@ -18,7 +30,7 @@ struct GammaMarket {
     end_date_iso: Option<String>,
     #[serde(default)]
     closed: bool,
-    #[allow(dead_code)]
+    #[serde(default)]
     description: Option<String>,
     #[allow(dead_code)]
     market_slug: Option<String>,
@@ -26,8 +38,32 @@ struct GammaMarket {
     volume: Option<String>,
     #[serde(default)]
     liquidity: Option<String>,
+    /// Outcome labels; Gamma sends a JSON-encoded string, e.g. "[\"Yes\", \"No\"]"
+    #[serde(default)]
+    outcomes: Option<serde_json::Value>,
+    /// CLOB token ids in outcome order, encoded like `outcomes`
+    #[serde(default, alias = "clobTokenIds")]
+    clob_token_ids: Option<serde_json::Value>,
+    /// Per-outcome prices, encoded like `outcomes`; a resolved market pays 1 on the winner
+    #[serde(default, alias = "outcomePrices")]
+    outcome_prices: Option<serde_json::Value>,
+    /// When trading closed, e.g. "2026-02-18 14:03:11+00"
+    #[serde(default, alias = "closedTime")]
+    closed_time: Option<String>,
+    /// "proposed", "disputed", "resolved", ... (absent for markets not resolved via UMA)
+    #[serde(default, alias = "umaResolutionStatus")]
+    uma_resolution_status: Option<String>,
+    /// Price increment, e.g. 0.01 (absent for markets without a CLOB book)
+    #[serde(default, alias = "orderPriceMinTickSize")]
+    order_price_min_tick_size: Option<f64>,
+    /// Smallest order the CLOB accepts, in shares
+    #[serde(default, alias = "orderMinSize")]
+    order_min_size: Option<f64>,
 }
 
+/// CLOB order sizes carry two decimals, whatever the market's price tick
+const CLOB_SHARE_INCREMENT: f64 = 0.01;
+
 #[derive(Debug, Deserialize)]
 struct GammaMarketsResponse {
     #[serde(default)]
@@ -37,16 +73,37 @@ struct GammaMarketsResponse {
 impl GammaApiClient {
     pub fn new(base_url: String) -> Self {
         Self {
-            client: Client::new(),
+            http: HttpClient::default(),
             base_url,
+            cities: CityRegistry::default(),
+            outcome_overrides: HashMap::new(),
         }
     }
     
+    /// Share a connection pool and in-flight limit with the other API clients
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+    
+    /// Recognize weather markets by any configured city alias
+    pub fn with_cities(mut self, cities: CityRegistry) -> Self {
+        self.cities = cities;
+        self
+    }
+    
+    /// Map YES/NO tokens by index for these markets instead of reading the outcome labels
+    pub fn with_outcome_overrides(mut self, overrides: HashMap<String, OutcomeOverride>) -> Self {
+        self.outcome_overrides = overrides;
+        self
+    }
+    
     /// Fetch all active markets from Polymarket Gamma API
     pub async fn fetch_markets(&self) -> Result<Vec<Market>> {
         let url = format!("{}/markets", self.base_url);
         
-        let response: GammaMarketsResponse = self.client
+        let _permit = self.http.permit().await;
+        let response: GammaMarketsResponse = self.http
             .get(&url)
             .send()
             .await
@@ -73,6 +130,40 @@ impl GammaApiClient {
             .collect())
     }
     
+    /// Re-fetch a market and update its mutable metadata in place
+    /// Returns whether the market is still tradable
+    pub async fn refresh(&self, market: &mut Market) -> Result<bool> {
+        let gm = self.fetch_gamma_market(&market.id).await?;
+        Ok(apply_refresh(market, gm))
+    }
+    
+    /// Fetch one market by id, as currently listed
+    pub async fn fetch_market(&self, market_id: &str) -> Result<Market> {
+        let gm = self.fetch_gamma_market(market_id).await?;
+        self.convert_gamma_market(gm)
+    }
+    
+    /// A market's outcome once it has closed with a decided payout, None before that
+    pub async fn fetch_resolution(&self, market_id: &str) -> Result<Option<Resolution>> {
+        let gm = self.fetch_gamma_market(market_id).await?;
+        let mapping = self.outcome_overrides.get(market_id).copied().unwrap_or(OutcomeOverride { yes: 0, no: 1 });
+        Ok(resolution_of(&gm, mapping))
+    }
+    
+    async fn fetch_gamma_market(&self, market_id: &str) -> Result<GammaMarket> {
+        let url = format!("{}/markets/{}", self.base_url, market_id);
+        
+        let _permit = self.http.permit().await;
+        self.http
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to refresh market")?
+            .json()
+            .await
+            .context("Failed to parse market response")
+    }
+    
     /// Convert Gamma API market format to our internal Market type
     fn convert_gamma_market(&self, gm: GammaMarket) -> Result<Market> {
         let end_date = gm.end_date_iso
@@ -88,17 +179,35 @@ impl GammaApiClient {
             .and_then(|l| l.parse::<f64>().ok())
             .unwrap_or(0.0);
         
-        Ok(Market {
+        let mut market = Market {
             id: gm.condition_id.clone(),
             question: gm.question,
             end_date,
-            yes_price: 0.5, // Default, will be updated from order book
+            // Placeholders until the order book is applied (see `priced`)
+            yes_price: 0.5,
             yes_ask: 0.5,
             no_ask: 0.5,
             volume_24h,
             yes_liquidity: liquidity / 2.0,
             no_liquidity: liquidity / 2.0,
-        })
+            closed: gm.closed,
+            share_granularity: gm.order_price_min_tick_size.map(|_| CLOB_SHARE_INCREMENT),
+            tick_size: gm.order_price_min_tick_size,
+            min_order_size: gm.order_min_size,
+            quoted_yes_ask: None,
+            quoted_no_ask: None,
+            priced: false,
+            outcomes: gm.outcomes.as_ref().map(parse_string_list).unwrap_or_default(),
+            yes_asks: Vec::new(),
+            no_asks: Vec::new(),
+            token_ids: gm.clob_token_ids.as_ref().map(parse_string_list).unwrap_or_default(),
+            description: gm.description,
+        };
+        
+        if let Some(mapping) = self.outcome_overrides.get(&market.id) {
+            apply_outcome_override(&mut market, mapping)?;
+        }
+        Ok(market)
     }
     
     /// Check if market is a weather market
@@ -115,17 +224,161 @@ impl GammaApiClient {
             || question_lower.contains("rain")
             || question_lower.contains("snow");
         
-        // Target cities
-        let target_cities = ["london", "new york", "nyc", "chicago", "seoul"];
-        let has_target_city = target_cities.iter()
-            .any(|city| question_lower.contains(city));
+        // Known cities
+        let has_target_city = self.cities.resolve(&question_lower).is_some();
         
         has_weather_keyword && has_target_city
     }
 }
 
+/// Update end date, closed status, and volume/liquidity from fresh metadata
+fn apply_refresh(market: &mut Market, gm: GammaMarket) -> bool {
+    if let Some(end_date) = gm.end_date_iso
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+    {
+        market.end_date = end_date.with_timezone(&Utc);
+    }
+    
+    if let Some(volume) = gm.volume.and_then(|v| v.parse::<f64>().ok()) {
+        market.volume_24h = volume;
+    }
+    
+    if let Some(liquidity) = gm.liquidity.and_then(|l| l.parse::<f64>().ok()) {
+        market.yes_liquidity = liquidity / 2.0;
+        market.no_liquidity = liquidity / 2.0;
+    }
+    
+    // Amended wording shows up as a changed `criteria_hash`
+    if !gm.question.is_empty() {
+        market.question = gm.question;
+    }
+    if gm.description.is_some() {
+        market.description = gm.description;
+    }
+    
+    // The CLOB widens the tick as a price nears 0 or 1
+    if gm.order_price_min_tick_size.is_some() {
+        market.tick_size = gm.order_price_min_tick_size;
+    }
+    if gm.order_min_size.is_some() {
+        market.min_order_size = gm.order_min_size;
+    }
+    
+    market.closed = gm.closed;
+    market.is_tradable()
+}
+
+/// Winner and resolution time of a closed market whose payout is settled
+/// Disputed or still-proposed outcomes, and splits, aren't a resolution yet
+fn resolution_of(gm: &GammaMarket, mapping: OutcomeOverride) -> Option<Resolution> {
+    if !gm.closed || gm.uma_resolution_status.as_deref().is_some_and(|s| s != "resolved") {
+        return None;
+    }
+    
+    let prices: Vec<f64> = gm.outcome_prices
+        .as_ref()
+        .map(parse_string_list)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|p| p.parse().ok())
+        .collect();
+    let winner = match (prices.get(mapping.yes), prices.get(mapping.no)) {
+        (Some(&yes), Some(&no)) if yes == 1.0 && no == 0.0 => Side::Yes,
+        (Some(&yes), Some(&no)) if yes == 0.0 && no == 1.0 => Side::No,
+        _ => return None,
+    };
+    
+    let resolved_at = gm.closed_time
+        .as_deref()
+        .or(gm.end_date_iso.as_deref())
+        .and_then(parse_gamma_time)?;
+    
+    Some(Resolution {
+        market_id: gm.condition_id.clone(),
+        winner,
+        resolved_at,
+    })
+}
+
+/// Gamma timestamps come as RFC 3339 or as "2026-02-18 14:03:11+00"
+fn parse_gamma_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .or_else(|_| DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%#z"))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Strings (outcome labels, token ids) from either a JSON array or a JSON-encoded string of one
+fn parse_string_list(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::String(s) => serde_json::from_str(s).unwrap_or_default(),
+        serde_json::Value::Array(labels) => labels
+            .iter()
+            .filter_map(|l| l.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Reorder a market's tokens so the configured YES token comes first. The labels become
+/// plain Yes/No, since the operator's mapping replaces the automatic labeling.
+pub fn apply_outcome_override(market: &mut Market, mapping: &OutcomeOverride) -> Result<()> {
+    let (Some(yes_token), Some(no_token)) = (market.token_ids.get(mapping.yes), market.token_ids.get(mapping.no)) else {
+        anyhow::bail!(
+            "Outcome override for {} points at token {}/{} but the market has {} tokens",
+            market.id, mapping.yes, mapping.no, market.token_ids.len()
+        );
+    };
+    
+    let label = |i: usize| market.outcomes.get(i).map(String::as_str).unwrap_or("?");
+    info!(
+        "Outcome override for {}: YES = token {} (\"{}\"), NO = token {} (\"{}\")",
+        market.id, mapping.yes, label(mapping.yes), mapping.no, label(mapping.no)
+    );
+    
+    market.token_ids = vec![yes_token.clone(), no_token.clone()];
+    market.outcomes = vec!["Yes".to_string(), "No".to_string()];
+    Ok(())
+}
+
+/// Check that the YES token pays out on the parsed comparison
+/// Yes/No labels follow the question; directional labels ("Above"/"Below") must match it.
+/// Unknown or missing labels pass, since there's nothing to contradict.
+pub fn check_outcome_alignment(outcomes: &[String], comparison: &Comparison) -> Result<(), String> {
+    let Some(yes_label) = outcomes.first() else {
+        return Ok(());
+    };
+    let label = yes_label.trim().to_lowercase();
+    
+    let yes_means = if label == "yes" {
+        return Ok(());
+    } else if label == "no" {
+        return Err(format!("first outcome is \"{}\", expected the YES token", yes_label));
+    } else if ["above", "over", "higher", "more"].iter().any(|w| label.contains(w)) {
+        Comparison::Above
+    } else if ["below", "under", "lower", "less"].iter().any(|w| label.contains(w)) {
+        Comparison::Below
+    } else {
+        return Ok(());
+    };
+    
+    if &yes_means == comparison {
+        Ok(())
+    } else {
+        Err(format!(
+            "YES token \"{}\" pays on {:?} but the question asks {:?}",
+            yes_label, yes_means, comparison
+        ))
+    }
+}
+
 /// Check if we should trade this weather market
-pub fn should_trade_weather_market(market: &Market, config_cities: &[String]) -> bool {
+pub fn should_trade_weather_market(
+    market: &Market,
+    config_cities: &[String],
+    cities: &CityRegistry,
+    risk: &RiskConfig,
+) -> bool {
     let question_lower = market.question.to_lowercase();
     
     // Must be temperature market (highest accuracy)
@@ -138,9 +391,16 @@ pub fn should_trade_weather_market(market: &Market, config_cities: &[String]) ->
         return false;
     }
     
-    // Must be in target cities
+    // Snowfall / degree-day markets often mention °F too; the model only prices temperature
+    let index = classify_market_index(&question_lower);
+    if index != MarketIndex::Temperature {
+        info!("Skipping {}: resolves on {:?}, not temperature", market.id, index);
+        return false;
+    }
+    
+    // Must be in target cities (under any alias)
     let in_target_city = config_cities.iter()
-        .any(|city| question_lower.contains(&city.to_lowercase()));
+        .any(|city| cities.mentions(city, &question_lower));
     
     if !in_target_city {
         return false;
@@ -152,8 +412,9 @@ pub fn should_trade_weather_market(market: &Market, config_cities: &[String]) ->
         return false; // Max 3 days (forecast degrades)
     }
     
-    // Minimum liquidity
-    if market.volume_24h < 5000.0 {
+    // Minimum volume and liquidity (independent gates)
+    if let Err(e) = check_market_activity(market.volume_24h, market.liquidity(), risk) {
+        info!("Skipping {}: {}", market.id, e);
         return false;
     }
     
@@ -171,98 +432,738 @@ pub fn should_trade_weather_market(market: &Market, config_cities: &[String]) ->
     true
 }
 
+/// Options controlling market question parsing
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Unit for bare "degrees" values that aren't obviously Fahrenheit
+    pub default_unit: TemperatureUnit,
+    /// Fall back to spelled-out numbers ("sixty degrees") when no digits match
+    pub number_words: bool,
+    /// User-supplied formats, tried in order before the built-in heuristics
+    pub templates: Vec<CompiledTemplate>,
+    /// City names and aliases
+    pub cities: CityRegistry,
+}
+
+/// A `QuestionTemplate` with its regex compiled
+#[derive(Debug, Clone)]
+pub struct CompiledTemplate {
+    regex: regex::Regex,
+    comparison: Option<Comparison>,
+    unit: Option<TemperatureUnit>,
+}
+
+impl CompiledTemplate {
+    pub fn compile(template: &QuestionTemplate) -> Result<Self> {
+        let regex = regex::Regex::new(&template.pattern)
+            .with_context(|| format!("Invalid question template: {}", template.pattern))?;
+        
+        for name in ["city", "threshold"] {
+            if !regex.capture_names().any(|n| n == Some(name)) {
+                anyhow::bail!("Question template {} has no `{}` capture", template.pattern, name);
+            }
+        }
+        
+        Ok(Self {
+            regex,
+            comparison: template.comparison.clone(),
+            unit: template.unit,
+        })
+    }
+    
+    /// Parse a question, or None if the template doesn't apply
+    fn parse(&self, question: &str, options: &ParseOptions) -> Option<WeatherMarketInfo> {
+        let cap = self.regex.captures(question)?;
+        
+        let raw_city = cap.name("city")?.as_str().trim();
+        let city = options.cities.resolve(&raw_city.to_lowercase()).unwrap_or(raw_city);
+        
+        let temp = cap.name("threshold")?.as_str().parse::<f64>().ok()?;
+        let (threshold, unit) = match cap.name("unit") {
+            Some(unit) => to_celsius(temp, Some(unit.as_str()), options.default_unit),
+            None => match self.unit {
+                Some(TemperatureUnit::Fahrenheit) => to_celsius(temp, Some("F"), options.default_unit),
+                Some(TemperatureUnit::Celsius) => (temp, TemperatureUnit::Celsius),
+                None => to_celsius(temp, None, options.default_unit),
+            },
+        };
+        
+        let comparison = match cap.name("comparison") {
+            Some(word) => match word.as_str().to_lowercase().as_str() {
+                "over" | "higher" | "more" | "at least" => Some(Comparison::Above),
+                "under" | "lower" | "less" | "at most" => Some(Comparison::Below),
+                other => match_comparison(other),
+            }?,
+            None => self.comparison.clone()?,
+        };
+        
+        let timezone = options.cities.timezone(city);
+        Some(WeatherMarketInfo {
+            city: city.to_string(),
+            threshold,
+            unit,
+            comparison,
+            inclusive: is_inclusive(&question.to_lowercase()),
+            target_date: extract_target_date(question, timezone, Utc::now()),
+            timezone,
+            kind: ForecastKind::Temperature,
+        })
+    }
+}
+
+impl ParseOptions {
+    /// Errors on the first question template that doesn't compile
+    pub fn from_config(config: &WeatherStrategyConfig) -> Result<Self> {
+        let templates = config
+            .question_templates
+            .iter()
+            .map(CompiledTemplate::compile)
+            .collect::<Result<_>>()?;
+        
+        Ok(Self {
+            default_unit: config.degrees_default_unit,
+            number_words: config.parse_number_words,
+            templates,
+            cities: config.city_registry(),
+        })
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            default_unit: TemperatureUnit::Celsius,
+            number_words: true,
+            templates: Vec::new(),
+            cities: CityRegistry::default(),
+        }
+    }
+}
+
 /// Parse market question to extract city, date, threshold, and comparison
-pub fn parse_weather_question(question: &str) -> Result<WeatherMarketInfo> {
+pub fn parse_weather_question(question: &str, options: &ParseOptions) -> Result<WeatherMarketInfo> {
     // Example: "Will NYC temperature exceed 60°F on 2026-02-17?"
     
+    // Checked before templates too: any of them would read "4 inches ... below 32°F" as a temperature
+    let index = classify_market_index(&question.to_lowercase());
+    if index != MarketIndex::Temperature {
+        return Err(UnsupportedIndex(index).into());
+    }
+    
+    for template in &options.templates {
+        if let Some(info) = template.parse(question, options) {
+            return Ok(info);
+        }
+    }
+    
+    // Localized questions need a template; the English keyword matching below would mis-parse them
+    if !is_english_question(question) {
+        return Err(UnsupportedLanguage.into());
+    }
+    
     let question_lower = question.to_lowercase();
     
     // Extract city
-    let city = if question_lower.contains("london") {
-        "London"
-    } else if question_lower.contains("new york") || question_lower.contains("nyc") {
-        "New York"
-    } else if question_lower.contains("chicago") {
-        "Chicago"
-    } else if question_lower.contains("seoul") {
-        "Seoul"
-    } else {
+    let Some(city) = options.cities.resolve(&question_lower) else {
         anyhow::bail!("Could not identify city in question")
     };
     
     // Extract threshold
-    let threshold = extract_temperature(question)?;
+    let (threshold, unit) = extract_temperature(question, options)?;
     
     // Extract comparison type
-    let comparison = if question_lower.contains("exceed")
-        || question_lower.contains("above")
-        || question_lower.contains(">") {
-        Comparison::Above
-    } else if question_lower.contains("below")
-        || question_lower.contains("<") {
-        Comparison::Below
-    } else {
+    let Some(comparison) = match_comparison(&question_lower) else {
         anyhow::bail!("Could not identify comparison type")
     };
     
+    let timezone = options.cities.timezone(city);
     Ok(WeatherMarketInfo {
         city: city.to_string(),
         threshold,
+        unit,
         comparison,
+        inclusive: is_inclusive(&question_lower),
+        target_date: extract_target_date(question, timezone, Utc::now()),
+        timezone,
+        kind: ForecastKind::Temperature,
     })
 }
 
-fn extract_temperature(question: &str) -> Result<f64> {
-    // Look for patterns like "60°F", "15°C", "60 degrees"
-    let re = regex::Regex::new(r"(\d+(?:\.\d+)?)\s*(?:°[FC]|degrees?)")?;
+/// What a weather market resolves on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketIndex {
+    Temperature,
+    /// Snow accumulation, usually in inches
+    Snowfall,
+    /// Rain / total precipitation
+    Precipitation,
+    /// Heating or cooling degree days
+    DegreeDays,
+}
+
+/// Index a lowercased question resolves on; temperature unless another index is named
+pub fn classify_market_index(question_lower: &str) -> MarketIndex {
+    // Called for every market on every scan, so compiled once
+    static DEGREE_DAY_ABBREV: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(?:hdd|cdd)\b").expect("valid regex"));
+    static RAIN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\brain(?:fall)?\b").expect("valid regex"));
+    
+    if question_lower.contains("degree day") || question_lower.contains("degree-day") || DEGREE_DAY_ABBREV.is_match(question_lower) {
+        MarketIndex::DegreeDays
+    } else if question_lower.contains("snow") {
+        MarketIndex::Snowfall
+    } else if RAIN.is_match(question_lower) || question_lower.contains("precipitation") {
+        MarketIndex::Precipitation
+    } else {
+        MarketIndex::Temperature
+    }
+}
+
+/// Question resolves on an index the temperature model can't price
+#[derive(Debug, thiserror::Error)]
+#[error("unsupported index: {0:?}")]
+pub struct UnsupportedIndex(pub MarketIndex);
+
+/// Question isn't in English and no template matched it
+#[derive(Debug, thiserror::Error)]
+#[error("unsupported language")]
+pub struct UnsupportedLanguage;
+
+/// Mostly Latin script and at least one English word the parser relies on
+fn is_english_question(question: &str) -> bool {
+    let letters: Vec<char> = question.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.is_empty() {
+        return false;
+    }
+    let ascii = letters.iter().filter(|c| c.is_ascii()).count();
+    if (ascii as f64) < letters.len() as f64 * 0.8 {
+        return false;
+    }
+    
+    let lower = question.to_lowercase();
+    ["will", "temperature", "temp", "high", "low", "degrees", "exceed", "above", "below", "reach"]
+        .iter()
+        .any(|word| lower.contains(word))
+}
+
+/// Day the question is about: an explicit YYYY-MM-DD, or "today" / "tomorrow" /
+/// "this weekend" resolved against `now` in the city's local time
+pub fn extract_target_date(question: &str, timezone: Tz, now: DateTime<Utc>) -> Option<NaiveDate> {
+    // Called for every market on every scan, so compiled once
+    static ABSOLUTE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(\d{4}-\d{2}-\d{2})\b").expect("valid regex"));
+    static RELATIVE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\b(today|tonight|tomorrow|this weekend)\b").expect("valid regex"));
+    
+    if let Some(cap) = ABSOLUTE.captures(question) {
+        if let Ok(date) = NaiveDate::parse_from_str(&cap[1], "%Y-%m-%d") {
+            return Some(date);
+        }
+    }
+    
+    let question_lower = question.to_lowercase();
+    let has_word = |word: &str| RELATIVE.captures_iter(&question_lower).any(|cap| &cap[1] == word);
+    
+    let local_today = now.with_timezone(&timezone).date_naive();
+    
+    if has_word("today") || has_word("tonight") {
+        Some(local_today)
+    } else if has_word("tomorrow") {
+        local_today.succ_opt()
+    } else if has_word("this weekend") {
+        // Saturday, unless the weekend has already started
+        let days_to_saturday = match local_today.weekday() {
+            Weekday::Sat | Weekday::Sun => 0,
+            day => 5 - day.num_days_from_monday() as i64,
+        };
+        Some(local_today + chrono::Duration::days(days_to_saturday))
+    } else {
+        None
+    }
+}
+
+/// Local hour a day's high is typically reached
+const DAILY_HIGH_LOCAL_HOUR: u32 = 15;
+
+/// Local hour a day's low is typically reached (around sunrise)
+const DAILY_LOW_LOCAL_HOUR: u32 = 6;
+
+/// Comparison implied by lowercased text
+fn match_comparison(text_lower: &str) -> Option<Comparison> {
+    if text_lower.contains("exactly") {
+        Some(Comparison::Exactly)
+    } else if text_lower.contains("exceed")
+        || text_lower.contains("above")
+        || text_lower.contains("reach")
+        || text_lower.contains("at least")
+        || text_lower.contains(">")
+        || text_lower.contains("≥") {
+        Some(Comparison::Above)
+    } else if text_lower.contains("below")
+        || text_lower.contains("at most")
+        || text_lower.contains("<")
+        || text_lower.contains("≤") {
+        Some(Comparison::Below)
+    } else {
+        None
+    }
+}
+
+/// Whether landing exactly on the threshold counts as YES ("reach 60°F", "15°C or higher")
+fn is_inclusive(text_lower: &str) -> bool {
+    [
+        "reach", "at least", "at most", "or higher", "or above", "or more",
+        "or lower", "or below", "or less", ">=", "<=", "≥", "≤",
+    ]
+    .iter()
+    .any(|phrase| text_lower.contains(phrase))
+}
+
+/// Bare "degrees" above this value is almost certainly Fahrenheit
+/// (no target city realistically reaches 45°C)
+const AMBIGUOUS_FAHRENHEIT_CUTOFF: f64 = 45.0;
+
+/// Threshold in °C, and the unit the question states it in
+fn extract_temperature(question: &str, options: &ParseOptions) -> Result<(f64, TemperatureUnit)> {
+    // Look for patterns like "60°F", "15°C", "60 degrees", "60 degrees F"
+    let re = regex::Regex::new(
+        r"(?i)(\d+(?:\.\d+)?)\s*(?:°\s*([FC])|degrees?(?:\s+(fahrenheit|celsius|[FC])\b)?)",
+    )?;
     
     if let Some(cap) = re.captures(question) {
         let temp = cap[1].parse::<f64>()?;
+        let explicit_unit = cap.get(2).or_else(|| cap.get(3)).map(|m| m.as_str());
+        return Ok(to_celsius(temp, explicit_unit, options.default_unit));
+    }
+    
+    // Fallback: spelled-out numbers like "sixty degrees F" or "fifteen C"
+    if options.number_words {
+        let re = regex::Regex::new(&format!(
+            r"(?i)\b({})\s*(?:°\s*([FC])|degrees?(?:\s+(fahrenheit|celsius|[FC])\b)?|(fahrenheit|celsius|[FC])\b)",
+            NUMBER_WORD_PATTERN
+        ))?;
         
-        // Convert to Celsius if Fahrenheit
-        let temp_celsius = if question.contains("°F") || question.contains("degrees F") {
-            (temp - 32.0) * 5.0 / 9.0
-        } else {
-            temp
-        };
-        
-        Ok(temp_celsius)
+        if let Some(cap) = re.captures(question) {
+            if let Some(temp) = parse_number_words(&cap[1]) {
+                let explicit_unit = cap.get(2)
+                    .or_else(|| cap.get(3))
+                    .or_else(|| cap.get(4))
+                    .map(|m| m.as_str());
+                return Ok(to_celsius(temp, explicit_unit, options.default_unit));
+            }
+        }
+    }
+    
+    anyhow::bail!("Could not extract temperature from question")
+}
+
+/// Convert a parsed value to Celsius given its (possibly missing) unit marker
+fn to_celsius(temp: f64, explicit_unit: Option<&str>, default_unit: TemperatureUnit) -> (f64, TemperatureUnit) {
+    let unit = match explicit_unit {
+        Some(u) if u.to_lowercase().starts_with('f') => TemperatureUnit::Fahrenheit,
+        Some(_) => TemperatureUnit::Celsius,
+        None => infer_ambiguous_unit(temp, default_unit),
+    };
+    
+    match unit {
+        TemperatureUnit::Fahrenheit => ((temp - 32.0) * 5.0 / 9.0, unit),
+        TemperatureUnit::Celsius => (temp, unit),
+    }
+}
+
+/// Resolve the unit for a bare "degrees" value
+fn infer_ambiguous_unit(temp: f64, default_unit: TemperatureUnit) -> TemperatureUnit {
+    if temp > AMBIGUOUS_FAHRENHEIT_CUTOFF {
+        info!(
+            "Inferred Fahrenheit for bare \"{} degrees\" (>{}, implausible as Celsius)",
+            temp, AMBIGUOUS_FAHRENHEIT_CUTOFF
+        );
+        TemperatureUnit::Fahrenheit
     } else {
-        anyhow::bail!("Could not extract temperature from question")
+        info!("Assuming {:?} for bare \"{} degrees\" (configured default)", default_unit, temp);
+        default_unit
     }
 }
 
+const UNIT_WORDS: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen",
+    "seventeen", "eighteen", "nineteen",
+];
+
+const TENS_WORDS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// Tens with an optional unit ("sixty-five"), or 0-19
+const NUMBER_WORD_PATTERN: &str = concat!(
+    r"(?:twenty|thirty|forty|fifty|sixty|seventy|eighty|ninety)",
+    r"(?:[\s-](?:one|two|three|four|five|six|seven|eight|nine)\b)?",
+    r"|nineteen|eighteen|seventeen|sixteen|fifteen|fourteen|thirteen|twelve|eleven|ten",
+    r"|zero|one|two|three|four|five|six|seven|eight|nine",
+);
+
+/// Parse spelled-out numbers in the 0-99 range used by temperature questions
+fn parse_number_words(words: &str) -> Option<f64> {
+    let mut total = 0;
+    
+    for word in words.to_lowercase().split(|c: char| c.is_whitespace() || c == '-') {
+        if word.is_empty() {
+            continue;
+        }
+        if let Some(i) = TENS_WORDS.iter().position(|w| *w == word) {
+            total += (i as u32 + 2) * 10;
+        } else if let Some(i) = UNIT_WORDS.iter().position(|w| *w == word) {
+            total += i as u32;
+        } else {
+            return None;
+        }
+    }
+    
+    Some(total as f64)
+}
+
 #[derive(Debug, Clone)]
 pub struct WeatherMarketInfo {
     pub city: String,
+    /// Always °C, whatever the question uses
     pub threshold: f64,
+    /// Unit the question states its threshold in
+    pub unit: TemperatureUnit,
     pub comparison: Comparison,
+    /// The threshold itself resolves YES (≥ / ≤ rather than > / <)
+    pub inclusive: bool,
+    /// Local day the question refers to, if stated (absolutely or relatively)
+    pub target_date: Option<NaiveDate>,
+    /// The city's time zone, which `target_date` and the forecast hour are local to
+    pub timezone: Tz,
+    /// What the market resolves on; only temperature questions parse
+    pub kind: ForecastKind,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl WeatherMarketInfo {
+    /// Key grouping markets on the same underlying (city + day + kind),
+    /// e.g. every threshold on the same NYC daily high
+    pub fn event_key(&self, resolution_date: NaiveDate) -> String {
+        format!("{}:{}:{}", self.city.to_lowercase(), resolution_date, self.kind.as_str())
+    }
+    
+    /// Hour to forecast on the question's day, else the market's end: the early-morning low for
+    /// "below" questions, the afternoon high otherwise
+    pub fn resolution_time(&self, market_end: DateTime<Utc>) -> DateTime<Utc> {
+        let hour = match self.comparison {
+            Comparison::Below => DAILY_LOW_LOCAL_HOUR,
+            Comparison::Above | Comparison::Exactly => DAILY_HIGH_LOCAL_HOUR,
+        };
+        self.target_date
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .and_then(|local| self.timezone.from_local_datetime(&local).earliest())
+            .map_or(market_end, |local| local.with_timezone(&Utc))
+    }
+    
+    /// °C range an "exactly N" question resolves YES on: readings within `half_width`
+    /// of the question's own degrees (0.5 when it settles on whole degrees)
+    pub fn exact_band(&self, half_width: f64) -> (f64, f64) {
+        let degree_c = match self.unit {
+            TemperatureUnit::Fahrenheit => 5.0 / 9.0,
+            TemperatureUnit::Celsius => 1.0,
+        };
+        (self.threshold - half_width * degree_c, self.threshold + half_width * degree_c)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Comparison {
     Above,
     Below,
+    /// Lands on the threshold itself (at the question's resolution)
+    Exactly,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     
+    fn gamma_market(closed: bool, end_date_iso: &str) -> GammaMarket {
+        GammaMarket {
+            condition_id: "0xabc".to_string(),
+            question: "Will NYC temperature exceed 60°F on 2026-02-17?".to_string(),
+            end_date_iso: Some(end_date_iso.to_string()),
+            closed,
+            description: None,
+            market_slug: None,
+            volume: Some("12000".to_string()),
+            liquidity: Some("8000".to_string()),
+            outcomes: Some(serde_json::Value::String("[\"Yes\", \"No\"]".to_string())),
+            clob_token_ids: Some(serde_json::Value::String("[\"111\", \"222\"]".to_string())),
+            outcome_prices: None,
+            closed_time: None,
+            uma_resolution_status: None,
+            order_price_min_tick_size: None,
+            order_min_size: None,
+        }
+    }
+    
+    #[test]
+    fn test_venue_limits_parsed_from_gamma_payload() {
+        let gm: GammaMarket = serde_json::from_value(serde_json::json!({
+            "condition_id": "0xabc",
+            "question": "Will NYC temperature exceed 60°F on 2026-02-17?",
+            "orderPriceMinTickSize": 0.001,
+            "orderMinSize": 5,
+        }))
+        .unwrap();
+        
+        let client = GammaApiClient::new("https://example.invalid".to_string());
+        let mut market = client.convert_gamma_market(gm).unwrap();
+        assert_eq!(market.tick_size, Some(0.001));
+        assert_eq!(market.min_order_size, Some(5.0));
+        assert_eq!(market.share_granularity, Some(CLOB_SHARE_INCREMENT));
+        
+        // A refresh picks up a widened tick
+        let mut gm = gamma_market(false, "2026-02-17T00:00:00Z");
+        gm.order_price_min_tick_size = Some(0.01);
+        apply_refresh(&mut market, gm);
+        assert_eq!(market.tick_size, Some(0.01));
+        assert_eq!(market.min_order_size, Some(5.0));
+    }
+    
+    #[test]
+    fn test_outcome_override_flips_token_mapping() {
+        let end_date = (Utc::now() + chrono::Duration::days(2)).to_rfc3339();
+        let mut gm = gamma_market(false, &end_date);
+        gm.outcomes = Some(serde_json::Value::String("[\"Below 60°F\", \"Above 60°F\"]".to_string()));
+        
+        // Inferred: first token is YES, and its label contradicts the question
+        let client = GammaApiClient::new("https://example.invalid".to_string());
+        let market = client.convert_gamma_market(gm.clone()).unwrap();
+        assert_eq!(market.token_ids, vec!["111", "222"]);
+        assert!(check_outcome_alignment(&market.outcomes, &Comparison::Above).is_err());
+        
+        let overrides = HashMap::from([("0xabc".to_string(), OutcomeOverride { yes: 1, no: 0 })]);
+        let client = client.with_outcome_overrides(overrides);
+        let market = client.convert_gamma_market(gm.clone()).unwrap();
+        assert_eq!(market.token_ids, vec!["222", "111"]);
+        assert!(check_outcome_alignment(&market.outcomes, &Comparison::Above).is_ok());
+        
+        // An override pointing past the tokens drops the market rather than guessing
+        let overrides = HashMap::from([("0xabc".to_string(), OutcomeOverride { yes: 2, no: 0 })]);
+        let client = client.with_outcome_overrides(overrides);
+        assert!(client.convert_gamma_market(gm).is_err());
+    }
+    
+    #[test]
+    fn test_refresh_marks_closed_market_untradable() {
+        let client = GammaApiClient::new("https://example.invalid".to_string());
+        let end_date = (Utc::now() + chrono::Duration::days(2)).to_rfc3339();
+        let mut market = client.convert_gamma_market(gamma_market(false, &end_date)).unwrap();
+        assert!(market.is_tradable());
+        assert_eq!(market.token_ids, vec!["111", "222"]);
+        
+        // Market closed early and its end date was amended
+        let amended = Utc::now() + chrono::Duration::days(1);
+        let tradable = apply_refresh(&mut market, gamma_market(true, &amended.to_rfc3339()));
+        
+        assert!(!tradable);
+        assert!(market.closed);
+        assert_eq!(market.end_date, amended);
+    }
+    
+    #[test]
+    fn test_screen_applies_volume_and_liquidity_gates() {
+        let risk = crate::config::Config::for_test().risk;
+        let cities = vec!["NYC".to_string()];
+        let end_date = (Utc::now() + chrono::Duration::hours(48)).to_rfc3339();
+        let client = GammaApiClient::new("https://example.invalid".to_string());
+        let mut market = client.convert_gamma_market(gamma_market(false, &end_date)).unwrap();
+        
+        // High volume, thin book
+        market.volume_24h = 50_000.0;
+        market.yes_liquidity = 500.0;
+        market.no_liquidity = 500.0;
+        assert!(!should_trade_weather_market(&market, &cities, &CityRegistry::default(), &risk));
+        assert!(matches!(
+            check_market_activity(market.volume_24h, market.liquidity(), &risk),
+            Err(crate::execution::risk::ValidationError::LiquidityTooLow(..))
+        ));
+        
+        // Deep book, no trading
+        market.volume_24h = 100.0;
+        market.yes_liquidity = 20_000.0;
+        market.no_liquidity = 20_000.0;
+        assert!(!should_trade_weather_market(&market, &cities, &CityRegistry::default(), &risk));
+        assert!(matches!(
+            check_market_activity(market.volume_24h, market.liquidity(), &risk),
+            Err(crate::execution::risk::ValidationError::VolumeTooLow(..))
+        ));
+        
+        market.volume_24h = 50_000.0;
+        assert!(should_trade_weather_market(&market, &cities, &CityRegistry::default(), &risk));
+    }
+    
+    #[test]
+    fn test_question_template_parses_unknown_format() {
+        let question = "Daily high in Chicago: 75 or higher on March 3?";
+        assert!(parse_weather_question(question, &ParseOptions::default()).is_err());
+        
+        let template = QuestionTemplate {
+            pattern: r"(?i)daily high in (?P<city>[a-z ]+): (?P<threshold>\d+) or higher".to_string(),
+            comparison: Some(Comparison::Above),
+            unit: Some(TemperatureUnit::Fahrenheit),
+        };
+        let options = ParseOptions {
+            templates: vec![CompiledTemplate::compile(&template).unwrap()],
+            ..ParseOptions::default()
+        };
+        
+        let info = parse_weather_question(question, &options).unwrap();
+        assert_eq!(info.city, "Chicago");
+        assert!((info.threshold - 23.89).abs() < 0.01); // 75°F
+        assert_eq!(info.comparison, Comparison::Above);
+        assert!(info.inclusive); // "or higher"
+    }
+    
+    #[test]
+    fn test_question_template_requires_named_captures() {
+        let template = QuestionTemplate {
+            pattern: r"(?P<city>\w+) (\d+)".to_string(),
+            comparison: None,
+            unit: None,
+        };
+        assert!(CompiledTemplate::compile(&template).is_err());
+    }
+    
+    #[test]
+    fn test_relative_dates_resolve_in_city_local_time() {
+        // 2026-02-17 02:00 UTC: already the 17th in London, still the 16th in New York
+        let now = DateTime::parse_from_rfc3339("2026-02-17T02:00:00Z").unwrap().with_timezone(&Utc);
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        
+        let london_today = "Will London temperature exceed 10°C today?";
+        let london_tomorrow = "Will London temperature exceed 10°C tomorrow?";
+        assert_eq!(extract_target_date(london_today, chrono_tz::Europe::London, now), Some(date("2026-02-17")));
+        assert_eq!(extract_target_date(london_tomorrow, chrono_tz::Europe::London, now), Some(date("2026-02-18")));
+        
+        let nyc_today = "Will NYC temperature exceed 40°F today?";
+        let nyc_tomorrow = "Will NYC temperature exceed 40°F tomorrow?";
+        assert_eq!(extract_target_date(nyc_today, chrono_tz::America::New_York, now), Some(date("2026-02-16")));
+        assert_eq!(extract_target_date(nyc_tomorrow, chrono_tz::America::New_York, now), Some(date("2026-02-17")));
+        
+        // Seoul (UTC+9) is already on the 17th at 11:00
+        let seoul_tomorrow = "Will Seoul temperature exceed 5°C tomorrow?";
+        assert_eq!(extract_target_date(seoul_tomorrow, chrono_tz::Asia::Seoul, now), Some(date("2026-02-18")));
+        
+        // 2026-02-17 is a Tuesday -> Saturday the 21st
+        let weekend = "Will London temperature exceed 10°C this weekend?";
+        assert_eq!(extract_target_date(weekend, chrono_tz::Europe::London, now), Some(date("2026-02-21")));
+        
+        // Explicit dates win; no date at all is None
+        let explicit = "Will NYC temperature exceed 60°F on 2026-03-01?";
+        assert_eq!(extract_target_date(explicit, chrono_tz::America::New_York, now), Some(date("2026-03-01")));
+        assert_eq!(extract_target_date("Will NYC exceed 60°F?", chrono_tz::America::New_York, now), None);
+    }
+    
     #[test]
     fn test_parse_weather_question() {
         let question = "Will NYC temperature exceed 60°F on 2026-02-17?";
-        let info = parse_weather_question(question).unwrap();
+        let info = parse_weather_question(question, &ParseOptions::default()).unwrap();
         
         assert_eq!(info.city, "New York");
         assert!((info.threshold - 15.56).abs() < 0.1); // 60°F ≈ 15.56°C
         assert_eq!(info.comparison, Comparison::Above);
+        assert!(!info.inclusive);
+        
+        let info = parse_weather_question("Will NYC reach 60°F on 2026-02-17?", &ParseOptions::default()).unwrap();
+        assert_eq!(info.comparison, Comparison::Above);
+        assert!(info.inclusive);
+    }
+    
+    #[test]
+    fn test_snowfall_market_is_classified_not_parsed_as_temperature() {
+        // The °F clause alone would parse as "New York below 0°C"
+        let question = "Will NYC get more than 4 inches of snow on 2026-02-17 with temps below 32°F?";
+        let err = parse_weather_question(question, &ParseOptions::default()).unwrap_err();
+        assert!(matches!(err.downcast_ref::<UnsupportedIndex>(), Some(UnsupportedIndex(MarketIndex::Snowfall))));
+        
+        assert_eq!(classify_market_index("will chicago heating degree days exceed 30 this week?"), MarketIndex::DegreeDays);
+        assert_eq!(classify_market_index("will london see more than 1 inch of rain on 2026-02-17?"), MarketIndex::Precipitation);
+        assert_eq!(classify_market_index("will nyc temperature exceed 60°f on 2026-02-17?"), MarketIndex::Temperature);
+    }
+    
+    #[test]
+    fn test_non_english_question_is_skipped_not_misparsed() {
+        // Has a city, a temperature and a ">" the English parser would happily read as "exceed"
+        let question = "Seoul 최고기온이 2026-02-17에 > 15°C 를 기록할까요?";
+        let err = parse_weather_question(question, &ParseOptions::default()).unwrap_err();
+        assert!(err.downcast_ref::<UnsupportedLanguage>().is_some());
+        
+        // English still parses
+        assert!(parse_weather_question("Will Seoul temperature exceed 15°C on 2026-02-17?", &ParseOptions::default()).is_ok());
     }
     
     #[test]
     fn test_extract_temperature() {
-        assert!((extract_temperature("60°F").unwrap() - 15.56).abs() < 0.1);
-        assert!((extract_temperature("15°C").unwrap() - 15.0).abs() < 0.1);
-        assert!((extract_temperature("20.5 degrees C").unwrap() - 20.5).abs() < 0.1);
+        let c = &ParseOptions::default();
+        assert!((extract_temperature("60°F", c).unwrap().0 - 15.56).abs() < 0.1);
+        assert!((extract_temperature("15°C", c).unwrap().0 - 15.0).abs() < 0.1);
+        assert!((extract_temperature("20.5 degrees C", c).unwrap().0 - 20.5).abs() < 0.1);
+        assert!((extract_temperature("60 degrees Fahrenheit", c).unwrap().0 - 15.56).abs() < 0.1);
+    }
+    
+    #[test]
+    fn test_extract_temperature_infers_fahrenheit_for_large_bare_degrees() {
+        // 90 degrees can't be Celsius, so it's treated as °F regardless of default
+        let temp = extract_temperature("Will Chicago hit 90 degrees?", &ParseOptions::default()).unwrap().0;
+        assert!((temp - 32.22).abs() < 0.1);
+    }
+    
+    #[test]
+    fn test_extract_temperature_bare_degrees_uses_configured_default() {
+        let q = "Will London exceed 20 degrees?";
+        
+        let temp = extract_temperature(q, &ParseOptions::default()).unwrap().0;
+        assert!((temp - 20.0).abs() < 0.1);
+        
+        // 20°F ≈ -6.67°C
+        let fahrenheit_default = ParseOptions {
+            default_unit: TemperatureUnit::Fahrenheit,
+            ..ParseOptions::default()
+        };
+        let temp = extract_temperature(q, &fahrenheit_default).unwrap().0;
+        assert!((temp + 6.67).abs() < 0.1);
+    }
+    
+    #[test]
+    fn test_extract_temperature_from_number_words() {
+        let options = ParseOptions::default();
+        
+        let temp = extract_temperature("Will NYC exceed sixty degrees F?", &options).unwrap().0;
+        assert!((temp - 15.56).abs() < 0.1);
+        
+        let temp = extract_temperature("Will London fall below fifteen C?", &options).unwrap().0;
+        assert!((temp - 15.0).abs() < 0.1);
+        
+        let temp = extract_temperature("Will Chicago top sixty-five degrees F?", &options).unwrap().0;
+        assert!((temp - 18.33).abs() < 0.1);
+        
+        // Disabled: spelled-out numbers are not parsed
+        let digits_only = ParseOptions { number_words: false, ..ParseOptions::default() };
+        assert!(extract_temperature("Will NYC exceed sixty degrees F?", &digits_only).is_err());
+    }
+    
+    #[test]
+    fn test_outcome_labels_are_checked_against_comparison() {
+        let client = GammaApiClient::new("https://example.invalid".to_string());
+        let end_date = (Utc::now() + chrono::Duration::days(2)).to_rfc3339();
+        let market = client.convert_gamma_market(gamma_market(false, &end_date)).unwrap();
+        assert_eq!(market.outcomes, vec!["Yes", "No"]);
+        
+        let labels = |a: &str, b: &str| vec![a.to_string(), b.to_string()];
+        assert!(check_outcome_alignment(&market.outcomes, &Comparison::Below).is_ok());
+        assert!(check_outcome_alignment(&[], &Comparison::Above).is_ok());
+        assert!(check_outcome_alignment(&labels("Above 60°F", "60°F or below"), &Comparison::Above).is_ok());
+        
+        // Token order flipped, or YES pays on the opposite side of the threshold
+        assert!(check_outcome_alignment(&labels("No", "Yes"), &Comparison::Above).is_err());
+        assert!(check_outcome_alignment(&labels("Below", "Above"), &Comparison::Above).is_err());
     }
 }
@@ -0,0 +1,97 @@
+use reqwest::{Client, IntoUrl, RequestBuilder};
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Requests in flight when no limit is configured
+pub const DEFAULT_MAX_CONCURRENT_HTTP: usize = 16;
+
+/// One connection pool and in-flight cap shared by every API client
+/// Cloning shares both; hold a `permit()` from before `send` until the body is read
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: Client,
+    in_flight: Arc<Semaphore>,
+}
+
+impl HttpClient {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            client: Client::new(),
+            in_flight: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+    
+    /// Wait for a free request slot
+    pub async fn permit(&self) -> OwnedSemaphorePermit {
+        self.in_flight
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("HTTP semaphore is never closed")
+    }
+    
+    pub fn get<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.get(url)
+    }
+    
+    pub fn post<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.post(url)
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_HTTP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    
+    #[tokio::test]
+    async fn test_requests_in_flight_never_exceed_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        
+        // Server counts requests being handled; the count drops before replying, so it never overstates
+        let (server_active, server_peak) = (active.clone(), peak.clone());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let (active, peak) = (server_active.clone(), server_peak.clone());
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = socket.read(&mut buf).await;
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(40)).await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    let _ = socket
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                        .await;
+                });
+            }
+        });
+        
+        let http = HttpClient::new(3);
+        let requests = (0..12).map(|_| {
+            let (http, url) = (http.clone(), url.clone());
+            tokio::spawn(async move {
+                let _permit = http.permit().await;
+                http.get(&url).send().await.unwrap().text().await.unwrap()
+            })
+        });
+        for body in futures::future::join_all(requests).await {
+            assert_eq!(body.unwrap(), "ok");
+        }
+        
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+}
@@ -3,3 +3,6 @@ pub mod websocket;
 pub mod gamma_api;
 pub mod weather;
 pub mod cache;
+pub mod cities;
+pub mod order_book;
+pub mod http;
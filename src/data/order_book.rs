@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use crate::data::http::HttpClient;
+use serde::Deserialize;
+use crate::config::NoPriceSource;
+use crate::data::types::{BookLevel, Market};
+use crate::strategies::types::Side;
+use tracing::info;
+
+/// Fetches per-token order books from the CLOB
+pub struct OrderBookClient {
+    http: HttpClient,
+    base_url: String,
+}
+
+/// One token's book, as the CLOB sends it (prices and sizes are strings)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OrderBook {
+    #[serde(default)]
+    bids: Vec<RawLevel>,
+    #[serde(default)]
+    asks: Vec<RawLevel>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawLevel {
+    price: String,
+    size: String,
+}
+
+impl RawLevel {
+    fn parse(&self) -> Option<BookLevel> {
+        Some(BookLevel {
+            price: self.price.parse().ok()?,
+            size: self.size.parse().ok()?,
+        })
+    }
+}
+
+impl OrderBook {
+    /// Ask levels, best (lowest) first
+    pub fn asks(&self) -> Vec<BookLevel> {
+        let mut levels: Vec<BookLevel> = self.asks.iter().filter_map(RawLevel::parse).collect();
+        levels.sort_by(|a, b| a.price.total_cmp(&b.price));
+        levels
+    }
+    
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks().first().map(|l| l.price)
+    }
+    
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids
+            .iter()
+            .filter_map(RawLevel::parse)
+            .map(|l| l.price)
+            .max_by(|a, b| a.total_cmp(b))
+    }
+}
+
+/// Price a market from its YES and NO books. Returns false (market left unpriced)
+/// if a needed side of either book is empty.
+pub fn apply_books(market: &mut Market, yes: &OrderBook, no: &OrderBook, source: NoPriceSource) -> bool {
+    let (Some(yes_bid), Some(yes_ask)) = (yes.best_bid(), yes.best_ask()) else {
+        return false;
+    };
+    let no_ask = match source {
+        NoPriceSource::Book => match no.best_ask() {
+            Some(ask) => ask,
+            None => return false,
+        },
+        // Selling YES at the bid is the synthetic NO ask
+        NoPriceSource::Complement => 1.0 - yes_bid,
+    };
+    
+    market.apply_prices((yes_bid + yes_ask) / 2.0, yes_ask, no_ask);
+    market.apply_depth(yes.asks(), no.asks());
+    true
+}
+
+impl OrderBookClient {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            http: HttpClient::default(),
+            base_url,
+        }
+    }
+    
+    /// Share a connection pool and in-flight limit with the other API clients
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+    
+    /// Fetch one token's book
+    pub async fn fetch(&self, token_id: &str) -> Result<OrderBook> {
+        let _permit = self.http.permit().await;
+        self.http
+            .get(format!("{}/book", self.base_url))
+            .query(&[("token_id", token_id)])
+            .send()
+            .await
+            .context("Failed to fetch order book")?
+            .json()
+            .await
+            .context("Failed to parse order book")
+    }
+    
+    /// Best bid for one side of a market: what held shares sell for right now
+    /// None if the market has no token ids or nobody is bidding
+    pub async fn best_bid(&self, market: &Market, side: &Side) -> Result<Option<f64>> {
+        let [yes_token, no_token] = market.token_ids.as_slice() else {
+            return Ok(None);
+        };
+        let token = match side {
+            Side::Yes => yes_token,
+            Side::No => no_token,
+        };
+        Ok(self.fetch(token).await?.best_bid())
+    }
+    
+    /// Fetch both books and price the market; false if it has no token ids or thin books
+    pub async fn price_market(&self, market: &mut Market, source: NoPriceSource) -> Result<bool> {
+        let [yes_token, no_token] = market.token_ids.as_slice() else {
+            return Ok(false);
+        };
+        let yes = self.fetch(yes_token).await?;
+        let no = match source {
+            NoPriceSource::Book => self.fetch(no_token).await?,
+            NoPriceSource::Complement => OrderBook::default(),
+        };
+        
+        let priced = apply_books(market, &yes, &no, source);
+        if !priced {
+            info!("Order book for {} too thin to price", market.id);
+        }
+        Ok(priced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EdgeBasis;
+    use crate::strategies::types::Side;
+    use crate::strategies::weather_edge::calculate_edge;
+    
+    fn book(json: &str) -> OrderBook {
+        serde_json::from_str(json).unwrap()
+    }
+    
+    #[test]
+    fn test_no_side_edge_uses_the_real_no_ask() {
+        let mut market: Market = serde_json::from_value(serde_json::json!({
+            "id": "0xbook",
+            "question": "Will NYC temperature exceed 60°F on 2026-02-17?",
+            "end_date": "2026-02-17T00:00:00Z",
+            "yes_price": 0.5, "yes_ask": 0.5, "no_ask": 0.5,
+            "volume_24h": 10000.0, "yes_liquidity": 5000.0, "no_liquidity": 5000.0,
+            "closed": false, "share_granularity": null,
+            "token_ids": ["yes-token", "no-token"]
+        }))
+        .unwrap();
+        let yes = book(r#"{"bids": [{"price": "0.38", "size": "200"}], "asks": [{"price": "0.42", "size": "150"}, {"price": "0.40", "size": "100"}]}"#);
+        let no = book(r#"{"bids": [{"price": "0.55", "size": "80"}], "asks": [{"price": "0.64", "size": "90"}]}"#);
+        
+        assert!(apply_books(&mut market, &yes, &no, NoPriceSource::Book));
+        assert_eq!(market.yes_ask, 0.40);
+        assert_eq!(market.no_ask, 0.64);
+        assert!(market.yes_ask + market.no_ask != 1.0);
+        assert_eq!(market.no_asks[0].price, 0.64);
+        
+        // NO is worth 0.75: 11% edge at the real 0.64 ask, not 15% at 1 - yes_ask
+        let (side, edge) = calculate_edge(0.25, &market, EdgeBasis::Ask);
+        assert_eq!(side, Side::No);
+        assert!((edge - 0.11).abs() < 1e-9);
+        
+        // Complement mode prices NO off the YES bid instead
+        assert!(apply_books(&mut market, &yes, &OrderBook::default(), NoPriceSource::Complement));
+        assert!((market.no_ask - 0.62).abs() < 1e-9);
+        assert!(!apply_books(&mut market, &yes, &OrderBook::default(), NoPriceSource::Book));
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use crate::math::format::fmt_price;
+use tracing::{info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Market {
@@ -12,6 +14,156 @@ pub struct Market {
     pub volume_24h: f64,
     pub yes_liquidity: f64,
     pub no_liquidity: f64,
+    pub closed: bool,
+    /// Smallest share increment accepted (None = fractional shares)
+    pub share_granularity: Option<f64>,
+    /// Price increment the venue accepts (None = the configured `tick_size`)
+    #[serde(default)]
+    pub tick_size: Option<f64>,
+    /// Smallest order the venue accepts, in shares (None = no minimum)
+    #[serde(default)]
+    pub min_order_size: Option<f64>,
+    /// Cheaper asks quoted on another venue (`price_sources`); reported only, since
+    /// orders go to the book and edge is taken at `yes_ask`/`no_ask`
+    #[serde(default)]
+    pub quoted_yes_ask: Option<f64>,
+    #[serde(default)]
+    pub quoted_no_ask: Option<f64>,
+    /// False until real prices are applied; the price fields are placeholders until then
+    #[serde(default)]
+    pub priced: bool,
+    /// Outcome labels in token order (YES token first), empty if unknown
+    #[serde(default)]
+    pub outcomes: Vec<String>,
+    /// Ask-side depth per token, best price first (empty until a book is fetched)
+    #[serde(default)]
+    pub yes_asks: Vec<BookLevel>,
+    #[serde(default)]
+    pub no_asks: Vec<BookLevel>,
+    /// CLOB token ids in outcome order (YES first), empty if unknown
+    #[serde(default)]
+    pub token_ids: Vec<String>,
+    /// Resolution criteria text, if the venue provides it
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Executable asks for a market on a venue other than its CLOB book (e.g. an AMM pool)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VenueQuote {
+    /// Label for logs
+    pub source: String,
+    #[serde(default)]
+    pub yes_ask: Option<f64>,
+    #[serde(default)]
+    pub no_ask: Option<f64>,
+    /// When the quote was taken; older than `price_source_max_age_secs` and it's ignored
+    #[serde(default)]
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+/// One price level of an order book
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: f64,
+    /// Shares resting at this price
+    pub size: f64,
+}
+
+impl Market {
+    /// Still open for new positions
+    pub fn is_tradable(&self) -> bool {
+        !self.closed && self.end_date > Utc::now()
+    }
+    
+    /// Total resting liquidity across both outcomes
+    pub fn liquidity(&self) -> f64 {
+        self.yes_liquidity + self.no_liquidity
+    }
+    
+    /// Set prices from the order book and mark the market priced
+    pub fn apply_prices(&mut self, yes_price: f64, yes_ask: f64, no_ask: f64) {
+        self.yes_price = yes_price;
+        self.yes_ask = yes_ask;
+        self.no_ask = no_ask;
+        self.priced = true;
+    }
+    
+    /// Record the cheapest ask per side quoted on another venue, where it beats the book
+    /// Only applies once the book has priced the market; quotes older than `max_age`, or
+    /// with no `as_of` to tell, are skipped
+    pub fn apply_best_quotes(&mut self, quotes: &[VenueQuote], max_age: Duration, now: DateTime<Utc>) {
+        if !self.priced {
+            return;
+        }
+        for quote in quotes {
+            match quote.as_of {
+                Some(as_of) if now - as_of <= max_age => {}
+                Some(as_of) => {
+                    warn!("{}: {} quote from {} is stale, ignoring it", self.id, quote.source, as_of);
+                    continue;
+                }
+                None => {
+                    warn!("{}: {} quote has no as_of, ignoring it", self.id, quote.source);
+                    continue;
+                }
+            }
+            let (best_yes, best_no) = self.best_asks();
+            if let Some(ask) = quote.yes_ask.filter(|ask| *ask < best_yes) {
+                info!("{}: YES ask ${} on {} beats the book's ${}", self.id, fmt_price(ask), quote.source, fmt_price(self.yes_ask));
+                self.quoted_yes_ask = Some(ask);
+            }
+            if let Some(ask) = quote.no_ask.filter(|ask| *ask < best_no) {
+                info!("{}: NO ask ${} on {} beats the book's ${}", self.id, fmt_price(ask), quote.source, fmt_price(self.no_ask));
+                self.quoted_no_ask = Some(ask);
+            }
+        }
+    }
+    
+    /// (YES, NO) cheapest asks across venues: the book's, or a cheaper quote from another venue
+    pub fn best_asks(&self) -> (f64, f64) {
+        (
+            self.quoted_yes_ask.map_or(self.yes_ask, |ask| ask.min(self.yes_ask)),
+            self.quoted_no_ask.map_or(self.no_ask, |ask| ask.min(self.no_ask)),
+        )
+    }
+    
+    /// Stable fingerprint of the question and resolution criteria
+    /// Case and whitespace are ignored so cosmetic edits don't count as amendments
+    pub fn criteria_hash(&self) -> String {
+        let text = format!("{}\n{}", self.question, self.description.as_deref().unwrap_or(""));
+        let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        
+        // FNV-1a: unlike DefaultHasher, stable across builds, so stored hashes stay comparable
+        let hash = normalized.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        format!("{:016x}", hash)
+    }
+    
+    /// Set ask-side depth from the order book (levels are sorted best price first)
+    pub fn apply_depth(&mut self, mut yes_asks: Vec<BookLevel>, mut no_asks: Vec<BookLevel>) {
+        yes_asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+        no_asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+        self.yes_asks = yes_asks;
+        self.no_asks = no_asks;
+    }
+}
+
+/// Quantity a forecast provider is asked to predict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForecastKind {
+    /// P(temperature > threshold °C)
+    Temperature,
+}
+
+impl ForecastKind {
+    /// Name used in event keys
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForecastKind::Temperature => "temperature",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -31,3 +183,24 @@ pub struct OrderBookUpdate {
     pub no_ask: f64,
     pub timestamp: DateTime<Utc>,
 }
+
+/// Unit assumed for bare "degrees" in market questions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+/// Distribution used to turn a forecast mean/sigma into P(temp > threshold)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProbabilityModel {
+    #[default]
+    Normal,
+    /// Mean/sigma-preserving skew-normal; negative alpha fattens the cold tail
+    SkewNormal { alpha: f64 },
+    /// Historical forecast errors in °C (actual - forecast)
+    Empirical { errors: Vec<f64> },
+}
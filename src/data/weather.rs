@@ -1,12 +1,120 @@
 use anyhow::{Context, Result};
-use reqwest::Client;
+use crate::data::http::HttpClient;
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Timelike, Utc};
 use std::collections::HashMap;
-use crate::data::types::ProbabilisticForecast;
+use futures::future::BoxFuture;
+use crate::data::cities::CityRegistry;
+use crate::data::types::{ForecastKind, ProbabilisticForecast, ProbabilityModel};
+use crate::math::distributions::{normal_cdf_with, skew_normal_cdf, ErfPrecision};
 
+/// A source of probabilistic forecasts
+/// Object-safe (boxed future) so the strategy can hold a mixed `Vec<Box<dyn ForecastProvider>>`;
+/// a new source (ECMWF, Tomorrow.io, ...) is one more impl passed to `WeatherEdgeStrategy::with_providers`
+pub trait ForecastProvider: Send + Sync {
+    /// Short name for logs
+    fn name(&self) -> &str;
+    
+    /// Forecast for the hour of `resolution_time`, where the source resolves hours
+    fn forecast<'a>(
+        &'a self,
+        city: &'a str,
+        threshold: f64,
+        resolution_time: DateTime<Utc>,
+        kind: ForecastKind,
+    ) -> BoxFuture<'a, Result<ProbabilisticForecast>>;
+}
+
+/// P(temp > threshold) for a forecast of `mean_temp` ± `std_dev` under `model`
+/// This is THE CORE ALGORITHM - converts weather forecasts to tradable probabilities
+pub fn probability_above(
+    model: &ProbabilityModel,
+    precision: ErfPrecision,
+    mean_temp: f64,
+    threshold: f64,
+    std_dev: f64,
+) -> f64 {
+    match model {
+        // Model temperature as normal distribution: N(mean, σ²)
+        // P(temp > threshold) = 1 - CDF(threshold | N(mean, σ²))
+        ProbabilityModel::Normal => {
+            let z_score = (threshold - mean_temp) / std_dev;
+            1.0 - normal_cdf_with(z_score, precision)
+        }
+        ProbabilityModel::SkewNormal { alpha } => {
+            1.0 - skew_normal_cdf(threshold, mean_temp, std_dev, *alpha)
+        }
+        ProbabilityModel::Empirical { errors } if !errors.is_empty() => {
+            let exceed = errors.iter().filter(|e| mean_temp + *e > threshold).count();
+            exceed as f64 / errors.len() as f64
+        }
+        ProbabilityModel::Empirical { .. } => {
+            let z_score = (threshold - mean_temp) / std_dev;
+            1.0 - normal_cdf_with(z_score, precision)
+        }
+    }
+}
+
+/// NOAA National Blend of Models
+pub struct NoaaProvider(pub WeatherClient);
+
+impl ForecastProvider for NoaaProvider {
+    fn name(&self) -> &str {
+        "NOAA"
+    }
+    
+    fn forecast<'a>(
+        &'a self,
+        city: &'a str,
+        threshold: f64,
+        resolution_time: DateTime<Utc>,
+        kind: ForecastKind,
+    ) -> BoxFuture<'a, Result<ProbabilisticForecast>> {
+        Box::pin(async move {
+            match kind {
+                ForecastKind::Temperature => self.0.fetch_probabilistic_forecast(city, threshold, resolution_time).await,
+            }
+        })
+    }
+}
+
+/// Open-Meteo hourly forecast (the 24h around the resolution time as a whole, not a single hour)
+pub struct OpenMeteoProvider(pub WeatherClient);
+
+impl ForecastProvider for OpenMeteoProvider {
+    fn name(&self) -> &str {
+        "Open-Meteo"
+    }
+    
+    fn forecast<'a>(
+        &'a self,
+        city: &'a str,
+        threshold: f64,
+        resolution_time: DateTime<Utc>,
+        kind: ForecastKind,
+    ) -> BoxFuture<'a, Result<ProbabilisticForecast>> {
+        Box::pin(async move {
+            match kind {
+                ForecastKind::Temperature => self.0.fetch_open_meteo(city, threshold, resolution_time).await,
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
 pub struct WeatherClient {
-    client: Client,
+    http: HttpClient,
     noaa_api_key: Option<String>,
+    model: ProbabilityModel,
+    /// Open-Meteo hours needed for full confidence in the 24h average
+    min_open_meteo_hours: usize,
+    erf_precision: ErfPrecision,
+    /// Take Open-Meteo's sigma from the hourly temps net of the daily cycle, not their raw spread
+    remove_diurnal: bool,
+    /// Keyed by lowercased city name
+    city_coords: HashMap<String, Coordinates>,
+    /// Resolves aliases ("NYC") to the canonical names coordinates are keyed by
+    cities: CityRegistry,
 }
 
 #[derive(Debug, Deserialize)]
@@ -23,7 +131,11 @@ struct NoaaProperties {
 #[allow(non_snake_case)]
 struct NoaaPeriod {
     temperature: f64,
-    temperatureUnit: String,
+    #[serde(default)]
+    temperatureUnit: Option<String>,
+    /// Start of the period (RFC 3339, in the grid point's local offset)
+    #[serde(default)]
+    startTime: Option<String>,
     shortForecast: Option<String>,
     detailedForecast: Option<String>,
 }
@@ -35,24 +147,80 @@ struct OpenMeteoResponse {
 
 #[derive(Debug, Deserialize)]
 struct OpenMeteoHourly {
+    /// UTC hour starts ("2026-02-17T20:00"), as requested with `timezone=GMT`
     time: Vec<String>,
     temperature_2m: Vec<f64>,
 }
 
 impl WeatherClient {
-    pub fn new(api_key: Option<String>) -> Self {
+    /// `city_coords` (lat, lon) add to or replace the built-in cities
+    pub fn new(
+        api_key: Option<String>,
+        model: ProbabilityModel,
+        city_coords: Option<HashMap<String, (f64, f64)>>,
+    ) -> Self {
+        let mut coords: HashMap<String, Coordinates> = [
+            ("london", Coordinates { lat: 51.5074, lon: -0.1278 }),
+            ("new york", Coordinates { lat: 40.7128, lon: -74.0060 }),
+            ("chicago", Coordinates { lat: 41.8781, lon: -87.6298 }),
+            ("seoul", Coordinates { lat: 37.5665, lon: 126.9780 }),
+        ]
+        .into_iter()
+        .map(|(city, c)| (city.to_string(), c))
+        .collect();
+        for (city, (lat, lon)) in city_coords.unwrap_or_default() {
+            coords.insert(city.trim().to_lowercase(), Coordinates { lat, lon });
+        }
+        
         Self {
-            client: Client::new(),
+            http: HttpClient::default(),
             noaa_api_key: api_key,
+            model,
+            min_open_meteo_hours: 24,
+            erf_precision: ErfPrecision::Fast,
+            remove_diurnal: true,
+            city_coords: coords,
+            cities: CityRegistry::default(),
         }
     }
     
-    /// Fetch probabilistic forecast from NOAA
+    /// Share a connection pool and in-flight limit with the other API clients
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+    
+    /// Trade normal-CDF speed for accuracy (see `ErfPrecision`)
+    pub fn with_erf_precision(mut self, precision: ErfPrecision) -> Self {
+        self.erf_precision = precision;
+        self
+    }
+    
+    /// Scale down Open-Meteo confidence when fewer hours than this come back
+    pub fn with_min_open_meteo_hours(mut self, hours: usize) -> Self {
+        self.min_open_meteo_hours = hours;
+        self
+    }
+    
+    /// Resolve city names through these aliases before looking up coordinates
+    pub fn with_cities(mut self, cities: CityRegistry) -> Self {
+        self.cities = cities;
+        self
+    }
+    
+    /// false = legacy sigma from the raw 24h variance (diurnal swing counted as uncertainty)
+    pub fn with_diurnal_removal(mut self, remove: bool) -> Self {
+        self.remove_diurnal = remove;
+        self
+    }
+    
+    /// Fetch probabilistic forecast from NOAA for the hour of `resolution_time`
     /// Uses National Blend of Models (NBM) for probabilistic temperature
     pub async fn fetch_probabilistic_forecast(
         &self,
         city: &str,
         threshold: f64,
+        resolution_time: DateTime<Utc>,
     ) -> Result<ProbabilisticForecast> {
         let coords = self.city_to_coords(city)?;
         
@@ -62,44 +230,40 @@ impl WeatherClient {
             coords.lat, coords.lon
         );
         
-        let grid_response: serde_json::Value = self.client
-            .get(&grid_url)
-            .header("User-Agent", "PolymarketBot/1.0")
-            .send()
-            .await?
-            .json()
-            .await?;
+        // Release the slot before the second request so one fetch never holds two
+        let grid_response: serde_json::Value = {
+            let _permit = self.http.permit().await;
+            self.http
+                .get(&grid_url)
+                .header("User-Agent", "PolymarketBot/1.0")
+                .send()
+                .await?
+                .json()
+                .await?
+        };
         
         let forecast_hourly_url = grid_response["properties"]["forecastHourly"]
             .as_str()
             .context("Missing forecast URL")?;
         
         // Fetch hourly forecast
-        let forecast_response: NoaaResponse = self.client
-            .get(forecast_hourly_url)
-            .header("User-Agent", "PolymarketBot/1.0")
-            .send()
-            .await?
-            .json()
-            .await?;
+        let forecast_response: NoaaResponse = {
+            let _permit = self.http.permit().await;
+            self.http
+                .get(forecast_hourly_url)
+                .header("User-Agent", "PolymarketBot/1.0")
+                .send()
+                .await?
+                .json()
+                .await?
+        };
         
-        // Get first period (next few hours)
-        let period = forecast_response
-            .properties
-            .periods
-            .first()
-            .context("No forecast periods")?;
+        let period = period_at(&forecast_response.properties.periods, resolution_time)?;
         
-        // Convert Fahrenheit to Celsius if needed
-        let mean_temp = if period.temperatureUnit == "F" {
-            (period.temperature - 32.0) * 5.0 / 9.0
-        } else {
-            period.temperature
-        };
+        let mean_temp = period_temp_celsius(period.temperature, period.temperatureUnit.as_deref())?;
         
-        // NOAA doesn't directly provide uncertainty, use historical average
-        // Research shows NOAA 24h forecast error ~2.5°C typical
-        let std_dev = 2.5;
+        // The hourly forecast carries no uncertainty; use NOAA's typical error
+        let std_dev = NOAA_STD_DEV;
         
         // Calculate probability using normal CDF
         let probability = self.forecast_to_probability(mean_temp, threshold, std_dev);
@@ -113,128 +277,193 @@ impl WeatherClient {
         })
     }
     
-    /// Fetch Open-Meteo forecast for cross-validation
+    /// Fetch Open-Meteo forecast for cross-validation, over the 24 hours around `resolution_time`
     pub async fn fetch_open_meteo(
         &self,
         city: &str,
         threshold: f64,
+        resolution_time: DateTime<Utc>,
     ) -> Result<ProbabilisticForecast> {
         let coords = self.city_to_coords(city)?;
         
         let url = format!(
-            "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&hourly=temperature_2m&forecast_days=3",
+            "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&hourly=temperature_2m&timezone=GMT&forecast_days=7",
             coords.lat, coords.lon
         );
         
-        let response: OpenMeteoResponse = self.client
+        let _permit = self.http.permit().await;
+        let response: OpenMeteoResponse = self.http
             .get(&url)
             .send()
             .await?
             .json()
             .await?;
         
-        // Get average of next 24 hours
-        let temps: Vec<f64> = response.hourly.temperature_2m
-            .iter()
-            .take(24)
-            .copied()
-            .collect();
+        self.open_meteo_forecast(&open_meteo_window(&response.hourly, resolution_time), threshold)
+    }
+    
+    /// Forecast from Open-Meteo hourly temperatures (averaging up to 24 hours)
+    fn open_meteo_forecast(&self, hourly: &[f64], threshold: f64) -> Result<ProbabilisticForecast> {
+        let temps: Vec<f64> = hourly.iter().take(24).copied().collect();
+        if temps.is_empty() {
+            anyhow::bail!("Open-Meteo returned no hourly temperatures");
+        }
         
         let mean_temp: f64 = temps.iter().sum::<f64>() / temps.len() as f64;
         
-        // Calculate standard deviation
-        let variance: f64 = temps.iter()
-            .map(|t| (t - mean_temp).powi(2))
-            .sum::<f64>() / temps.len() as f64;
-        let std_dev = variance.sqrt().max(2.0); // Minimum 2°C
+        // A warm afternoon and cool night are expected, not uncertain: only what the daily cycle
+        // doesn't explain counts toward sigma
+        let spread = if self.remove_diurnal {
+            diurnal_residual_std(&temps)
+        } else {
+            let variance: f64 = temps.iter()
+                .map(|t| (t - mean_temp).powi(2))
+                .sum::<f64>() / temps.len() as f64;
+            variance.sqrt()
+        };
+        let std_dev = spread.max(2.0); // Minimum 2°C
         
         let probability = self.forecast_to_probability(mean_temp, threshold, std_dev);
         
+        // Near the end of the forecast range the window is short and its variance unstable
+        let mut confidence = 0.90;
+        if temps.len() < self.min_open_meteo_hours {
+            tracing::warn!(
+                "Open-Meteo returned {} of {} hours needed, lowering confidence",
+                temps.len(), self.min_open_meteo_hours
+            );
+            confidence *= temps.len() as f64 / self.min_open_meteo_hours as f64;
+        }
+        
         Ok(ProbabilisticForecast {
             probability,
-            confidence: 0.90,
+            confidence,
             mean_temp,
             std_dev,
             model: "Open-Meteo".to_string(),
         })
     }
     
-    /// Convert point forecast to probability distribution using normal CDF
-    /// This is THE CORE ALGORITHM - converts weather forecasts to tradable probabilities
+    /// Convert point forecast to probability distribution using the configured model
     fn forecast_to_probability(
         &self,
         mean_temp: f64,
         threshold: f64,
         std_dev: f64,
     ) -> f64 {
-        // Model temperature as normal distribution: N(mean, σ²)
-        // P(temp > threshold) = 1 - CDF(threshold | N(mean, σ²))
-        
-        let z_score = (threshold - mean_temp) / std_dev;
-        1.0 - Self::normal_cdf(z_score)
-    }
-    
-    /// Standard normal cumulative distribution function
-    fn normal_cdf(z: f64) -> f64 {
-        0.5 * (1.0 + Self::erf(z / f64::sqrt(2.0)))
-    }
-    
-    /// Error function approximation (Abramowitz & Stegun)
-    fn erf(x: f64) -> f64 {
-        let a1 =  0.254829592;
-        let a2 = -0.284496736;
-        let a3 =  1.421413741;
-        let a4 = -1.453152027;
-        let a5 =  1.061405429;
-        let p  =  0.3275911;
-        
-        let sign = if x < 0.0 { -1.0 } else { 1.0 };
-        let x = x.abs();
-        
-        let t = 1.0 / (1.0 + p * x);
-        let y = 1.0 - (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * (-x * x).exp();
-        
-        sign * y
+        probability_above(&self.model, self.erf_precision, mean_temp, threshold, std_dev)
     }
     
-    /// Map city names to coordinates
+    /// Map city names (any case, or an alias) to coordinates
     fn city_to_coords(&self, city: &str) -> Result<Coordinates> {
-        let coords_map: HashMap<&str, Coordinates> = [
-            ("London", Coordinates { lat: 51.5074, lon: -0.1278 }),
-            ("New York", Coordinates { lat: 40.7128, lon: -74.0060 }),
-            ("NYC", Coordinates { lat: 40.7128, lon: -74.0060 }),
-            ("Chicago", Coordinates { lat: 41.8781, lon: -87.6298 }),
-            ("Seoul", Coordinates { lat: 37.5665, lon: 126.9780 }),
-        ].into_iter().collect();
-        
-        coords_map
-            .get(city)
+        let name = city.trim().to_lowercase();
+        let canonical = self.cities.resolve(&name).map(str::to_lowercase);
+        
+        canonical
+            .and_then(|c| self.city_coords.get(&c))
+            .or_else(|| self.city_coords.get(&name))
             .copied()
             .context(format!("Unknown city: {}", city))
     }
 }
 
+/// Temperatures of the hours within 12h either side of `at`; short near the end of the forecast
+fn open_meteo_window(hourly: &OpenMeteoHourly, at: DateTime<Utc>) -> Vec<f64> {
+    let half = chrono::Duration::hours(12);
+    hourly.time
+        .iter()
+        .zip(&hourly.temperature_2m)
+        .filter_map(|(time, temp)| {
+            let start = chrono::NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").ok()?.and_utc();
+            (start >= at - half && start < at + half).then_some(*temp)
+        })
+        .collect()
+}
+
+/// The hourly period containing `at`, matched on the local date and hour of each period
+/// Errors rather than falling back to another hour when `at` is outside the forecast
+fn period_at(periods: &[NoaaPeriod], at: DateTime<Utc>) -> Result<&NoaaPeriod> {
+    let mut last_start = None;
+    for period in periods {
+        let Some(start) = period.startTime.as_deref().and_then(|t| DateTime::parse_from_rfc3339(t).ok()) else {
+            continue;
+        };
+        let local = at.with_timezone(start.offset());
+        if local.date_naive() == start.date_naive() && local.hour() == start.hour() {
+            return Ok(period);
+        }
+        last_start = Some(start);
+    }
+    
+    match last_start {
+        Some(last) if at > last => anyhow::bail!(
+            "Resolution time {} is beyond the NOAA forecast horizon (last period starts {})",
+            at, last
+        ),
+        Some(_) => anyhow::bail!("No NOAA forecast period covers {}", at),
+        None => anyhow::bail!("No forecast periods"),
+    }
+}
+
+/// Research shows NOAA 24h forecast error ~2.5°C typical
+const NOAA_STD_DEV: f64 = 2.5;
+
+/// NOAA period temperature in °C
+/// Anything but an explicit "F" or "C" is an error: guessing a unit would misprice the market
+fn period_temp_celsius(temperature: f64, unit: Option<&str>) -> Result<f64> {
+    match unit.map(str::trim) {
+        Some("F") | Some("f") => Ok((temperature - 32.0) * 5.0 / 9.0),
+        Some("C") | Some("c") => Ok(temperature),
+        Some(other) => anyhow::bail!("Unrecognized NOAA temperature unit {:?}", other),
+        None => anyhow::bail!("NOAA forecast period has no temperatureUnit"),
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct Coordinates {
     lat: f64,
     lon: f64,
 }
 
+/// Std dev of hourly temps around a fitted 24h sinusoid (least squares, with intercept)
+/// Falls back to the raw std dev when the window is too short to fit a cycle
+fn diurnal_residual_std(temps: &[f64]) -> f64 {
+    let n = temps.len() as f64;
+    let phase = |i: usize| 2.0 * std::f64::consts::PI * i as f64 / 24.0;
+    let cos: Vec<f64> = (0..temps.len()).map(|i| phase(i).cos()).collect();
+    let sin: Vec<f64> = (0..temps.len()).map(|i| phase(i).sin()).collect();
+    let center = |xs: &[f64]| {
+        let mean = xs.iter().sum::<f64>() / n;
+        xs.iter().map(|x| x - mean).collect::<Vec<f64>>()
+    };
+    let (t, c, s) = (center(temps), center(&cos), center(&sin));
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+    
+    let (cc, ss, cs) = (dot(&c, &c), dot(&s, &s), dot(&c, &s));
+    let det = cc * ss - cs * cs;
+    if det < 1e-9 {
+        return (dot(&t, &t) / n).sqrt();
+    }
+    let (ct, st) = (dot(&c, &t), dot(&s, &t));
+    let a = (ct * ss - st * cs) / det;
+    let b = (st * cc - ct * cs) / det;
+    
+    let residual: f64 = (0..temps.len())
+        .map(|i| (t[i] - a * c[i] - b * s[i]).powi(2))
+        .sum();
+    (residual / n).sqrt()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
-    #[test]
-    fn test_normal_cdf() {
-        // Test known values
-        assert!((WeatherClient::normal_cdf(0.0) - 0.5).abs() < 0.001);
-        assert!((WeatherClient::normal_cdf(1.0) - 0.8413).abs() < 0.01);
-        assert!((WeatherClient::normal_cdf(-1.0) - 0.1587).abs() < 0.01);
-    }
+    use chrono::NaiveDate;
+    use crate::data::gamma_api::{Comparison, WeatherMarketInfo};
+    use crate::data::types::TemperatureUnit;
     
     #[test]
     fn test_forecast_to_probability() {
-        let client = WeatherClient::new(None);
+        let client = WeatherClient::new(None, ProbabilityModel::Normal, None);
         
         // If mean = 16°C, threshold = 15°C, std_dev = 2.5°C
         // z = (15 - 16) / 2.5 = -0.4
@@ -250,4 +479,179 @@ mod tests {
         let prob = client.forecast_to_probability(10.0, 15.0, 2.5);
         assert!(prob < 0.05);
     }
+    
+    #[test]
+    fn test_short_open_meteo_window_lowers_confidence() {
+        let client = WeatherClient::new(None, ProbabilityModel::Normal, None);
+        
+        let full = client.open_meteo_forecast(&[20.0; 48], 15.0).unwrap();
+        assert!((full.confidence - 0.90).abs() < 1e-9);
+        
+        let short = client.open_meteo_forecast(&[20.0; 8], 15.0).unwrap();
+        assert!((short.confidence - 0.30).abs() < 1e-9);
+        
+        // A lower minimum accepts the short window as-is
+        let lenient = WeatherClient::new(None, ProbabilityModel::Normal, None).with_min_open_meteo_hours(6);
+        assert!((lenient.open_meteo_forecast(&[20.0; 8], 15.0).unwrap().confidence - 0.90).abs() < 1e-9);
+        
+        assert!(client.open_meteo_forecast(&[], 15.0).is_err());
+    }
+    
+    #[test]
+    fn test_open_meteo_window_centers_on_resolution_time() {
+        // Three days of hours, each hour's temperature its index
+        let start = "2026-02-16T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let hourly = OpenMeteoHourly {
+            time: (0..72).map(|h| (start + chrono::Duration::hours(h)).format("%Y-%m-%dT%H:%M").to_string()).collect(),
+            temperature_2m: (0..72).map(|h| h as f64).collect(),
+        };
+        
+        // 20:00 UTC on the 17th is hour 44: the window is hours 32..56
+        let at = "2026-02-17T20:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let window = open_meteo_window(&hourly, at);
+        assert_eq!(window, (32..56).map(|h| h as f64).collect::<Vec<_>>());
+        
+        // Near the end of the forecast only the hours still covered remain
+        assert_eq!(open_meteo_window(&hourly, at + chrono::Duration::hours(36)).len(), 4);
+        assert!(open_meteo_window(&hourly, at + chrono::Duration::days(3)).is_empty());
+    }
+    
+    #[test]
+    fn test_open_meteo_sigma_excludes_diurnal_swing() {
+        // Clear-sky day: 12°C at dawn to 28°C mid-afternoon, plus a little hour-to-hour noise
+        let temps: Vec<f64> = (0..24)
+            .map(|h| {
+                let cycle = -8.0 * (2.0 * std::f64::consts::PI * (h as f64 - 3.0) / 24.0).cos();
+                let noise = if h % 2 == 0 { 2.5 } else { -2.5 };
+                20.0 + cycle + noise
+            })
+            .collect();
+        
+        // Raw variance reads the swing as ~6.2°C of uncertainty
+        let raw = WeatherClient::new(None, ProbabilityModel::Normal, None).with_diurnal_removal(false);
+        let raw_sigma = raw.open_meteo_forecast(&temps, 25.0).unwrap().std_dev;
+        assert!((raw_sigma - (32.0f64 + 6.25).sqrt()).abs() < 0.01);
+        
+        // Net of the daily cycle only the 2.5°C wobble is left
+        let client = WeatherClient::new(None, ProbabilityModel::Normal, None);
+        let forecast = client.open_meteo_forecast(&temps, 25.0).unwrap();
+        assert!((forecast.std_dev - 2.5).abs() < 0.01);
+        assert!(forecast.probability < raw.open_meteo_forecast(&temps, 25.0).unwrap().probability);
+        
+        // A flat day has nothing to remove
+        assert_eq!(client.open_meteo_forecast(&[20.0; 24], 15.0).unwrap().std_dev, 2.0);
+    }
+    
+    #[test]
+    fn test_configured_city_coords_and_aliases() {
+        // Built-ins, any case, with NYC resolved through the alias table
+        let client = WeatherClient::new(None, ProbabilityModel::Normal, None);
+        assert_eq!(client.city_to_coords("London").unwrap().lat, 51.5074);
+        assert_eq!(client.city_to_coords("NYC").unwrap().lon, -74.0060);
+        assert_eq!(client.city_to_coords("new york").unwrap().lat, 40.7128);
+        assert!(client.city_to_coords("Miami").is_err());
+        
+        let coords = HashMap::from([("Miami".to_string(), (25.7617, -80.1918))]);
+        let aliases = HashMap::from([("Miami".to_string(), vec!["MIA".to_string()])]);
+        let client = WeatherClient::new(None, ProbabilityModel::Normal, Some(coords))
+            .with_cities(CityRegistry::new(&aliases));
+        assert_eq!(client.city_to_coords("MIAMI").unwrap().lat, 25.7617);
+        assert_eq!(client.city_to_coords("mia").unwrap().lon, -80.1918);
+        assert_eq!(client.city_to_coords("Seoul").unwrap().lon, 126.9780);
+    }
+    
+    #[test]
+    fn test_noaa_period_selected_for_resolution_hour() {
+        // New York hourly periods in local time (UTC-5), 14:00-16:00 on Feb 17
+        let periods: Vec<NoaaPeriod> = serde_json::from_value(serde_json::json!([
+            {"temperature": 40.0, "temperatureUnit": "F", "startTime": "2026-02-17T14:00:00-05:00"},
+            {"temperature": 44.0, "temperatureUnit": "F", "startTime": "2026-02-17T15:00:00-05:00"},
+            {"temperature": 42.0, "temperatureUnit": "F", "startTime": "2026-02-17T16:00:00-05:00"}
+        ]))
+        .unwrap();
+        
+        // A "highest temperature in NYC on Feb 17" market resolves on 15:00 local, 20:00 UTC
+        let info = WeatherMarketInfo {
+            city: "New York".to_string(),
+            threshold: 5.0,
+            unit: TemperatureUnit::Fahrenheit,
+            comparison: Comparison::Above,
+            inclusive: false,
+            target_date: NaiveDate::from_ymd_opt(2026, 2, 17),
+            timezone: chrono_tz::America::New_York,
+            kind: ForecastKind::Temperature,
+        };
+        let end = "2026-02-18T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let resolution_time = info.resolution_time(end);
+        assert_eq!(resolution_time, "2026-02-17T20:00:00Z".parse::<DateTime<Utc>>().unwrap());
+        assert_eq!(period_at(&periods, resolution_time).unwrap().temperature, 44.0);
+        
+        // Mid-hour still picks that hour's period
+        let half_past = resolution_time + chrono::Duration::minutes(30);
+        assert_eq!(period_at(&periods, half_past).unwrap().temperature, 44.0);
+        
+        // Past the last period: an error, not some other day's forecast
+        let err = period_at(&periods, resolution_time + chrono::Duration::days(1)).unwrap_err();
+        assert!(err.to_string().contains("beyond the NOAA forecast horizon"));
+        
+        // A "below" question is about the day's low, around sunrise: 06:00 local, 11:00 UTC
+        let low = WeatherMarketInfo { comparison: Comparison::Below, ..info.clone() };
+        assert_eq!(low.resolution_time(end), "2026-02-17T11:00:00Z".parse::<DateTime<Utc>>().unwrap());
+        
+        // In July New York is on daylight time: 15:00 EDT is 19:00 UTC
+        let summer = WeatherMarketInfo { target_date: NaiveDate::from_ymd_opt(2026, 7, 17), ..info.clone() };
+        assert_eq!(summer.resolution_time(end), "2026-07-17T19:00:00Z".parse::<DateTime<Utc>>().unwrap());
+        
+        // No stated day: the market's end
+        let undated = WeatherMarketInfo { target_date: None, ..info };
+        assert_eq!(undated.resolution_time(end), end);
+    }
+    
+    #[test]
+    fn test_skew_normal_vs_normal() {
+        let normal = WeatherClient::new(None, ProbabilityModel::Normal, None);
+        let symmetric = WeatherClient::new(None, ProbabilityModel::SkewNormal { alpha: 0.0 }, None);
+        let cold_tail = WeatherClient::new(None, ProbabilityModel::SkewNormal { alpha: -4.0 }, None);
+        
+        // alpha = 0 reduces to the normal model
+        let p_normal = normal.forecast_to_probability(16.0, 15.0, 2.5);
+        let p_symmetric = symmetric.forecast_to_probability(16.0, 15.0, 2.5);
+        assert!((p_normal - p_symmetric).abs() < 1e-6);
+        
+        // Same mean and sigma, but a fat cold tail puts the median above the mean,
+        // so exceeding a threshold at the mean is more likely than 50%
+        let p_normal = normal.forecast_to_probability(15.0, 15.0, 2.5);
+        let p_skewed = cold_tail.forecast_to_probability(15.0, 15.0, 2.5);
+        assert!((p_normal - 0.5).abs() < 1e-3);
+        assert!(p_skewed > 0.55);
+        
+        // ...and a deep cold threshold is less certain to be exceeded
+        let p_normal = normal.forecast_to_probability(15.0, 10.0, 2.5);
+        let p_skewed = cold_tail.forecast_to_probability(15.0, 10.0, 2.5);
+        assert!(p_skewed < p_normal);
+    }
+    
+    #[test]
+    fn test_empirical_model_counts_historical_errors() {
+        let errors = vec![-3.0, -1.0, 0.5, 1.5];
+        let client = WeatherClient::new(None, ProbabilityModel::Empirical { errors }, None);
+        
+        // mean 15 + errors -> [12, 14, 15.5, 16.5]; two exceed 15
+        assert!((client.forecast_to_probability(15.0, 15.0, 2.5) - 0.5).abs() < 1e-9);
+        assert!((client.forecast_to_probability(15.0, 16.0, 2.5) - 0.25).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_noaa_unit_must_be_explicit() {
+        assert!((period_temp_celsius(59.0, Some("F")).unwrap() - 15.0).abs() < 1e-9);
+        assert_eq!(period_temp_celsius(15.0, Some("C")).unwrap(), 15.0);
+        
+        // Kelvin or a missing unit is skipped, not read as 288°C / assumed Celsius
+        let err = period_temp_celsius(288.0, Some("K")).unwrap_err();
+        assert!(err.to_string().contains("Unrecognized NOAA temperature unit"));
+        assert!(period_temp_celsius(59.0, None).is_err());
+        
+        let period: NoaaPeriod = serde_json::from_str(r#"{"temperature": 59}"#).unwrap();
+        assert!(period_temp_celsius(period.temperature, period.temperatureUnit.as_deref()).is_err());
+    }
 }
@@ -0,0 +1,1556 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::config::{Config, EnvConfig, WeatherStrategyConfig};
+use crate::data::cache::PriceCache;
+use crate::data::cities::CityRegistry;
+use crate::data::http::HttpClient;
+use crate::data::gamma_api::{should_trade_weather_market, GammaApiClient};
+use crate::data::order_book::OrderBookClient;
+use crate::data::types::Market;
+use crate::data::weather::WeatherClient;
+use crate::execution::order_manager::{build_order, OrderOptions, OrderPlan};
+use crate::execution::persistence::PositionDatabase;
+use crate::execution::reconcile::check_positions;
+use crate::execution::risk::{CircuitBreaker, CircuitBreakerReason, RiskManager};
+use crate::execution::settlement::settle_resolved_positions;
+use crate::execution::simulator::{load_recorded_fills, PaperTradingSimulator};
+use crate::execution::store::{HttpPositionStore, PositionStore, TeeStore};
+use crate::execution::types::{
+    submission_outcome_unknown, DeadLetter, Fill, FillOutcome, Order, OrderType, Position, Rejection, Token,
+};
+use crate::execution::verify::verify_fill;
+use crate::monitoring::amendments::{flag_if_amended, QUESTION_CHANGED_TAG};
+use crate::monitoring::ev::negative_ev_positions;
+use crate::math::format::fmt_usd;
+use crate::monitoring::logger::CsvLogger;
+use crate::monitoring::scale_out::{apply_scale_out, scale_out_candidates, SCALED_OUT_TAG};
+use crate::strategies::sum_to_one::{cache_asks, execute_arb, fresh_arb_asks, ArbOutcome};
+use crate::strategies::types::{OrderIntent, Side, Signal, Strategy};
+use crate::strategies::weather_edge::WeatherEdgeStrategy;
+use tracing::{info, warn};
+
+/// DB state key for the persisted pause flag
+const PAUSED_STATE_KEY: &str = "paused";
+
+/// DB state key for the adaptive poller's saved `PollingState`
+const POLLING_STATE_KEY: &str = "polling_state";
+
+/// Global pause: stops new orders while monitoring keeps running
+#[derive(Debug, Clone, Default)]
+pub struct PauseSwitch(Arc<AtomicBool>);
+
+impl PauseSwitch {
+    pub fn new(paused: bool) -> Self {
+        Self(Arc::new(AtomicBool::new(paused)))
+    }
+    
+    pub fn is_paused(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+    
+    pub fn set(&self, paused: bool) {
+        if self.0.swap(paused, Ordering::SeqCst) != paused {
+            log_pause_change(paused);
+        }
+    }
+    
+    /// Flip the pause state atomically, returning the new state
+    pub fn toggle(&self) -> bool {
+        let paused = !self.0.fetch_xor(true, Ordering::SeqCst);
+        log_pause_change(paused);
+        paused
+    }
+}
+
+fn log_pause_change(paused: bool) {
+    if paused {
+        warn!("⏸️  Bot paused: no new orders will be placed");
+    } else {
+        info!("▶️  Bot resumed");
+    }
+}
+
+/// Flip the pause for an admin signal, writing it to `db` (when persisting) straight away
+/// so a restart right after the signal keeps it
+pub fn toggle_pause(pause: &PauseSwitch, db: Option<&PositionDatabase>) -> Result<bool> {
+    let paused = pause.toggle();
+    if let Some(db) = db {
+        db.set_state(PAUSED_STATE_KEY, &paused.to_string())?;
+    }
+    Ok(paused)
+}
+
+/// Polling interval that stretches through quiet periods
+/// After `empty_cycles_before_backoff` cycles with no qualifying markets the interval
+/// is multiplied by `factor` each cycle up to `max`; any activity snaps it back to `base`.
+#[derive(Debug, Clone)]
+pub struct AdaptivePoller {
+    base: Duration,
+    max: Duration,
+    factor: f64,
+    empty_cycles_before_backoff: u32,
+    current: Duration,
+    empty_streak: u32,
+}
+
+impl AdaptivePoller {
+    pub fn new(base: Duration, max: Duration, factor: f64, empty_cycles_before_backoff: u32) -> Self {
+        Self {
+            base,
+            max: max.max(base),
+            factor: factor.max(1.0),
+            empty_cycles_before_backoff,
+            current: base,
+            empty_streak: 0,
+        }
+    }
+    
+    pub fn from_config(config: &WeatherStrategyConfig) -> Self {
+        let base = Duration::from_secs(config.polling_interval_secs);
+        let max = config.polling_interval_max_secs.map_or(base, Duration::from_secs);
+        Self::new(base, max, config.polling_backoff_factor, config.polling_backoff_after_empty_cycles)
+    }
+    
+    /// Interval to wait before the next cycle
+    pub fn interval(&self) -> Duration {
+        self.current
+    }
+    
+    /// Update after a cycle that found `qualifying` markets, returning the next interval
+    pub fn record_cycle(&mut self, qualifying: usize) -> Duration {
+        if qualifying > 0 {
+            if self.current != self.base {
+                info!("Markets active again, polling every {:?}", self.base);
+            }
+            self.empty_streak = 0;
+            self.current = self.base;
+        } else {
+            self.empty_streak += 1;
+            if self.empty_streak >= self.empty_cycles_before_backoff && self.current < self.max {
+                self.current = self.current.mul_f64(self.factor).min(self.max);
+                info!(
+                    "{} quiet cycles, backing off polling to {:?}",
+                    self.empty_streak, self.current
+                );
+            }
+        }
+        self.current
+    }
+    
+    /// Snapshot after a cycle that ran at `last_cycle_at`
+    pub fn state(&self, last_cycle_at: DateTime<Utc>) -> PollingState {
+        PollingState {
+            interval_secs: self.current.as_secs(),
+            empty_streak: self.empty_streak,
+            last_cycle_at,
+        }
+    }
+    
+    /// Resume a saved cadence, kept within the currently configured bounds
+    pub fn restore(&mut self, state: &PollingState) {
+        self.current = Duration::from_secs(state.interval_secs).clamp(self.base, self.max);
+        self.empty_streak = state.empty_streak;
+    }
+}
+
+/// Poller state kept across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollingState {
+    pub interval_secs: u64,
+    pub empty_streak: u32,
+    /// When the last cycle ran
+    pub last_cycle_at: DateTime<Utc>,
+}
+
+impl PollingState {
+    /// How much of the saved interval is still left at `now`
+    pub fn remaining(&self, now: DateTime<Utc>) -> Duration {
+        let due = self.last_cycle_at + chrono::Duration::seconds(self.interval_secs as i64);
+        (due - now).to_std().unwrap_or(Duration::ZERO)
+    }
+}
+
+/// What one engine cycle saw and did
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CycleReport {
+    pub candidates: usize,
+    pub analyzed: usize,
+    pub opened: usize,
+}
+
+/// Order candidates soonest-resolving first (highest volume breaking ties) and keep the top `max`
+/// Returns the markets to analyze this cycle and how many were deferred
+pub fn prioritize_candidates(mut candidates: Vec<Market>, max: usize) -> (Vec<Market>, usize) {
+    candidates.sort_by(|a, b| {
+        a.end_date
+            .cmp(&b.end_date)
+            .then(b.volume_24h.total_cmp(&a.volume_24h))
+    });
+    
+    let deferred = candidates.len().saturating_sub(max);
+    candidates.truncate(max);
+    (candidates, deferred)
+}
+
+/// One position holding both legs of a filled arb basket
+fn arb_position(market: &Market, yes: &Fill, no: &Fill) -> Position {
+    Position {
+        id: None,
+        market_id: market.id.clone(),
+        strategy: Strategy::SumToOneArb.name().to_string(),
+        side: None,
+        yes_shares: yes.size,
+        no_shares: no.size,
+        entry_price: yes.price + no.price,
+        cost: yes.cost + no.cost,
+        opened_at: yes.timestamp.max(no.timestamp),
+        closed_at: None,
+        pnl: None,
+        status: "open".to_string(),
+        event_key: None,
+        tags: Vec::new(),
+        notes: None,
+        question_hash: Some(market.criteria_hash()),
+    }
+}
+
+/// Weather polling loop: screen -> analyze -> validate -> execute
+pub struct WeatherEngine {
+    config: Config,
+    gamma: GammaApiClient,
+    books: OrderBookClient,
+    cities: CityRegistry,
+    strategy: WeatherEdgeStrategy,
+    risk: RiskManager,
+    breaker: CircuitBreaker,
+    simulator: Arc<PaperTradingSimulator>,
+    db: PositionDatabase,
+    /// Secondary copy of position writes, if configured
+    mirror: Option<Box<dyn PositionStore + Send>>,
+    logger: Option<CsvLogger>,
+    pause: PauseSwitch,
+    persisted_pause: Option<bool>,
+    kill_switch_engaged: bool,
+    last_reconciled_at: Option<Instant>,
+    /// Asks of markets priced this cycle, for the sum-to-one arb
+    arb_quotes: PriceCache,
+    /// Latest YES mid per priced market, for marking holdings into equity
+    marks: HashMap<String, f64>,
+}
+
+impl WeatherEngine {
+    /// `http` is shared by every API client the engine creates
+    pub fn new(config: Config, env_config: &EnvConfig, db: PositionDatabase, http: HttpClient) -> Result<Self> {
+        let weather_client = WeatherClient::new(
+            env_config.noaa_api_key.clone(),
+            config.strategies.weather.probability_model.clone(),
+            Some(config.strategies.weather.city_coords.clone()),
+        )
+        .with_cities(config.strategies.weather.city_registry())
+        .with_min_open_meteo_hours(config.strategies.weather.open_meteo_min_hours)
+        .with_diurnal_removal(config.strategies.weather.open_meteo_remove_diurnal)
+        .with_erf_precision(config.strategies.weather.erf_precision)
+        .with_http(http.clone());
+        let strategy = WeatherEdgeStrategy::new(config.strategies.weather.clone(), weather_client)?;
+        let cities = config.strategies.weather.city_registry();
+        
+        let mut simulator = PaperTradingSimulator::new(
+            config.paper_trading.clone(),
+            config.risk.max_order_balance_fraction,
+        );
+        if let Some(path) = &config.paper_trading.recorded_fills_path {
+            simulator = simulator.with_recorded_fills(load_recorded_fills(path)?);
+        }
+        if let Some(per_minute) = config.risk.max_orders_per_minute {
+            simulator = simulator.with_rate_limit(per_minute);
+        }
+        // Positions from an earlier run are still held, so reconciliation doesn't flag them
+        simulator = simulator.with_open_positions(&db.get_open_positions()?);
+        // ...and the cash left after them is where the last run's ledger ended
+        if let Some(balance) = db.last_ledger_balance()? {
+            simulator = simulator.with_restored_balance(balance);
+        }
+        
+        // Orders aren't signed yet and resting orders aren't tracked, so nothing goes to the CLOB
+        if !config.system.dry_run && !config.paper_trading.enabled {
+            warn!("Live order submission isn't available yet; logging order plans as in dry run");
+        }
+        // Only the simulator reports holdings, so outside paper trading there's nothing to check against
+        if config.system.reconcile_interval_secs.is_some() && !config.paper_trading.enabled {
+            warn!("Position reconciliation is off: venue holdings are only known when paper trading");
+        }
+        // Same for cash: the floor would otherwise be checked against the simulator's balance
+        if config.risk.min_balance_floor_usd.is_some() && !config.paper_trading.enabled {
+            warn!("Balance floor is off: the venue balance is only known when paper trading");
+        }
+        
+        let logger = if config.monitoring.csv_logging {
+            Some(CsvLogger::new(config.monitoring.csv_log_path.clone())?)
+        } else {
+            None
+        };
+        
+        // Restore a pause that was in effect before restart
+        let persisted_pause = if config.system.persist_pause {
+            db.get_state(PAUSED_STATE_KEY)?.map(|v| v == "true")
+        } else {
+            None
+        };
+        let pause = PauseSwitch::new(persisted_pause.unwrap_or(false));
+        if pause.is_paused() {
+            warn!("Restored paused state from database");
+        }
+        
+        Ok(Self {
+            gamma: GammaApiClient::new(env_config.polymarket_gamma_url.clone())
+                .with_cities(cities.clone())
+                .with_outcome_overrides(config.system.outcome_overrides.clone())
+                .with_http(http.clone()),
+            books: OrderBookClient::new(env_config.polymarket_clob_url.clone()).with_http(http.clone()),
+            cities,
+            strategy,
+            risk: RiskManager::new(config.risk.clone()),
+            breaker: CircuitBreaker::new(),
+            simulator: Arc::new(simulator),
+            db,
+            mirror: config.system.mirror_url.clone().map(|url| {
+                Box::new(HttpPositionStore::new(url).with_http(http.clone())) as Box<dyn PositionStore + Send>
+            }),
+            logger,
+            pause,
+            persisted_pause,
+            kill_switch_engaged: false,
+            last_reconciled_at: None,
+            arb_quotes: PriceCache::new(),
+            marks: HashMap::new(),
+            config,
+        })
+    }
+    
+    /// Handle for pausing/resuming from other tasks (admin signal, control channel)
+    pub fn pause_switch(&self) -> PauseSwitch {
+        self.pause.clone()
+    }
+    
+    /// Poll weather markets until the task is cancelled
+    pub async fn run(&mut self) -> Result<()> {
+        let mut poller = AdaptivePoller::from_config(&self.config.strategies.weather);
+        if let Some(state) = self.load_polling_state() {
+            poller.restore(&state);
+            let wait = state.remaining(Utc::now()).min(poller.interval());
+            info!("Resuming polling every {:?} (first cycle in {:?})", poller.interval(), wait);
+            tokio::time::sleep(wait).await;
+        }
+        
+        loop {
+            if let Err(e) = self.sync_pause_state() {
+                warn!("Failed to persist pause state: {}", e);
+            }
+            self.reconcile_if_due();
+            match self.breaker.sync_reset(&self.db) {
+                // Otherwise the old streak would trip it again on the next failure
+                Ok(true) => self.strategy.clear_sources_failed_streak(),
+                Ok(false) => {}
+                Err(e) => warn!("Failed to check for a circuit breaker reset: {}", e),
+            }
+            
+            if self.check_kill_switch() {
+                info!("Kill switch engaged, skipping weather cycle");
+            } else if self.pause.is_paused() {
+                info!("Paused, skipping weather cycle");
+            } else {
+                match self.run_cycle().await {
+                    Ok(report) => {
+                        info!(
+                            "Weather cycle complete: {} of {} candidates analyzed, {} positions opened",
+                            report.analyzed, report.candidates, report.opened
+                        );
+                        poller.record_cycle(report.candidates);
+                        self.save_polling_state(&poller);
+                    }
+                    Err(e) => warn!("Weather cycle failed: {}", e),
+                }
+            }
+            
+            tokio::time::sleep(poller.interval()).await;
+        }
+    }
+    
+    /// Run one screen/analyze/execute pass
+    pub async fn run_cycle(&mut self) -> Result<CycleReport> {
+        self.settle_resolved_markets().await?;
+        if self.config.monitoring.check_question_changes {
+            self.check_question_changes().await?;
+        }
+        if let Some(min_gain_pct) = self.config.strategies.weather.scale_out_gain_pct {
+            self.scale_out_winners(min_gain_pct).await?;
+        }
+        if self.config.monitoring.check_negative_ev {
+            self.check_negative_ev().await?;
+        }
+        
+        let markets = if self.config.system.whitelist.is_empty() {
+            self.gamma.fetch_weather_markets().await?
+        } else {
+            self.fetch_whitelisted_markets().await
+        };
+        let candidates = self.screen_markets(markets);
+        
+        info!("{} candidate weather markets", candidates.len());
+        let candidate_count = candidates.len();
+        
+        let (candidates, deferred) =
+            prioritize_candidates(candidates, self.config.strategies.weather.max_markets_per_cycle);
+        if deferred > 0 {
+            info!("Analyzing {} markets this cycle, {} deferred", candidates.len(), deferred);
+        }
+        let analyzed = candidates.len();
+        self.strategy.set_loss_streak(self.db.consecutive_losses()?);
+        
+        // Arb legs are simulated, so arbs only run while paper trading
+        let trade_arbs = self.config.strategies.arbitrage.enabled && self.config.paper_trading.enabled;
+        let mut opened = 0;
+        let mut priced = Vec::new();
+        let mut arb_markets = Vec::new();
+        for mut market in candidates {
+            if let Err(e) = self.books.price_market(&mut market, self.config.strategies.weather.no_price_source).await {
+                warn!("Order book fetch failed for {}: {}", market.id, e);
+            }
+            if market.priced {
+                self.marks.insert(market.id.clone(), market.yes_price);
+            }
+            if let Some(quotes) = self.config.strategies.weather.price_sources.get(&market.id) {
+                let max_age = chrono::Duration::seconds(self.config.strategies.weather.price_source_max_age_secs as i64);
+                market.apply_best_quotes(quotes, max_age, Utc::now());
+            }
+            if self.config.strategies.weather.ladder_trading && market.priced {
+                priced.push(market.clone());
+            }
+            if trade_arbs && market.priced {
+                cache_asks(&self.arb_quotes, &market);
+                arb_markets.push(market.clone());
+            }
+            
+            let capital = self.simulator.balance();
+            let analysis = self.strategy
+                .analyze_weather_market(&market, capital, self.config.risk.max_position_pct)
+                .await;
+            
+            for rejection in self.strategy.take_rejections() {
+                self.record_rejection(&rejection);
+            }
+            for letter in self.strategy.take_dead_letters() {
+                self.record_dead_letter(&letter);
+            }
+            self.escalate_sources_failed()?;
+            
+            let signal = match analysis {
+                Ok(Some(signal)) => signal,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Analysis failed for {}: {}", market.id, e);
+                    continue;
+                }
+            };
+            
+            if self.open_position(&mut market, signal).await? {
+                opened += 1;
+            }
+        }
+        
+        opened += self.trade_ladders(&priced).await?;
+        opened += self.trade_arbs(&arb_markets).await?;
+        self.escalate_sources_failed()?;
+        
+        Ok(CycleReport {
+            candidates: candidate_count,
+            analyzed,
+            opened,
+        })
+    }
+    
+    /// Trip the ApiErrors breaker if forecast sources have been down for too many markets in a row
+    fn escalate_sources_failed(&self) -> Result<()> {
+        if let Some(failures) = self.strategy.sources_failed_escalation() {
+            self.breaker.trigger(CircuitBreakerReason::ApiErrors(failures as usize), &self.db)?;
+        }
+        Ok(())
+    }
+    
+    /// Fetch each whitelisted market by id, since the listing only returns its first page
+    /// A market that can't be fetched is skipped for this cycle
+    async fn fetch_whitelisted_markets(&self) -> Vec<Market> {
+        let mut markets = Vec::new();
+        for market_id in &self.config.system.whitelist {
+            match self.gamma.fetch_market(market_id).await {
+                Ok(market) => markets.push(market),
+                Err(e) => warn!("Failed to fetch whitelisted market {}: {:#}", market_id, e),
+            }
+        }
+        markets
+    }
+    
+    /// Keep the markets worth analyzing: the whitelist if one is set, else the weather screen
+    fn screen_markets(&self, markets: Vec<Market>) -> Vec<Market> {
+        let whitelist = &self.config.system.whitelist;
+        if !whitelist.is_empty() {
+            return markets.into_iter().filter(|m| whitelist.contains(&m.id)).collect();
+        }
+        
+        markets
+            .into_iter()
+            .filter(|m| {
+                should_trade_weather_market(
+                    m,
+                    &self.config.strategies.weather.target_cities,
+                    &self.cities,
+                    &self.config.risk,
+                )
+            })
+            .collect()
+    }
+    
+    /// Trade the most mispriced threshold of each city/day ladder (two or more thresholds)
+    async fn trade_ladders(&mut self, priced: &[Market]) -> Result<usize> {
+        let mut opened = 0;
+        for (event_key, ladder) in self.strategy.group_by_event(priced) {
+            if ladder.len() < 2 {
+                continue;
+            }
+            let held: Vec<String> = self.db
+                .get_open_positions()?
+                .into_iter()
+                .filter(|p| p.event_key.as_deref() == Some(event_key.as_str()))
+                .map(|p| p.market_id)
+                .collect();
+            
+            let capital = self.simulator.balance();
+            let Some(signal) = self.strategy
+                .analyze_event_ladder(&ladder, &held, capital, self.config.risk.max_position_pct)
+                .await
+            else {
+                continue;
+            };
+            
+            let mut market = ladder
+                .into_iter()
+                .find(|m| m.id == signal.market_id)
+                .expect("ladder signal is for one of its markets");
+            if self.open_position(&mut market, signal).await? {
+                opened += 1;
+            }
+        }
+        Ok(opened)
+    }
+    
+    /// Buy both sides of each market whose asks sum under `1 - min_spread`, quoted within
+    /// `max_age_for_trade_arb_ms`; a filled or hedged basket becomes one position holding both sides
+    async fn trade_arbs(&mut self, markets: &[Market]) -> Result<usize> {
+        let config = self.config.strategies.arbitrage.clone();
+        let max_age = Duration::from_millis(self.config.infrastructure.max_age_for_trade_arb_ms);
+        let mut opened = 0;
+        for market in markets {
+            if self.check_kill_switch() || self.breaker.is_triggered() || self.pause.is_paused() {
+                break;
+            }
+            let Some((yes_ask, no_ask)) = fresh_arb_asks(&self.arb_quotes, &market.id, max_age) else {
+                info!("Quotes for {} too old to arb", market.id);
+                continue;
+            };
+            let basket = yes_ask + no_ask;
+            if basket > 1.0 - config.min_spread {
+                continue;
+            }
+            
+            let shares = (self.config.risk.max_position_size_usd / basket).floor();
+            let signal = Signal {
+                market_id: market.id.clone(),
+                strategy: Strategy::SumToOneArb,
+                side: None,
+                entry_price: basket,
+                // Each leg is a FOK at its ask, so the basket never costs more than this
+                intent: OrderIntent::FillOrKill,
+                size: shares * basket,
+                edge: Some(1.0 - basket),
+                confidence: 1.0,
+                event_key: None,
+                market_volume_24h: Some(market.volume_24h),
+                market_liquidity: Some(market.liquidity()),
+            };
+            if let Err(e) = self.risk.validate_trade(&signal, &self.db, self.simulator.balance(), self.equity()).await {
+                info!("Arb rejected for {}: {}", market.id, e);
+                self.record_rejection(&Rejection::from_signal(&signal, e.code(), e.to_string()));
+                continue;
+            }
+            
+            let leg = |side: Side, token: Token, price: f64| Order {
+                market_id: market.id.clone(),
+                side,
+                token,
+                price,
+                size: shares,
+                order_type: OrderType::FOK,
+                improvement_ticks: 0,
+            };
+            let yes_order = leg(Side::Yes, Token::Yes, yes_ask);
+            let no_order = leg(Side::No, Token::No, no_ask);
+            let outcome = execute_arb(&self.simulator, &self.breaker, &self.db, &yes_order, &no_order, &config).await;
+            self.persist_ledger();
+            
+            let (yes, no) = match outcome? {
+                ArbOutcome::Filled { yes, no } | ArbOutcome::Hedged { yes, no } => (yes, no),
+                ArbOutcome::Unwound { filled, .. } => {
+                    self.db.record_fill(&filled)?;
+                    continue;
+                }
+                ArbOutcome::NotFilled => continue,
+            };
+            // Both legs are recorded either way; a redelivered one means the pair is already booked
+            let yes_new = self.db.record_fill(&yes)?;
+            let no_new = self.db.record_fill(&no)?;
+            if !(yes_new && no_new) {
+                continue;
+            }
+            
+            let mut position = arb_position(market, &yes, &no);
+            position.id = Some(self.insert_position(&position)?);
+            if let Some(logger) = &self.logger {
+                logger.log_position(&position)?;
+            }
+            opened += 1;
+        }
+        Ok(opened)
+    }
+    
+    /// Saved poller state, if persistence is on and one was saved
+    fn load_polling_state(&self) -> Option<PollingState> {
+        if !self.config.strategies.weather.persist_polling_state {
+            return None;
+        }
+        match self.db.get_state(POLLING_STATE_KEY) {
+            Ok(Some(json)) => serde_json::from_str(&json)
+                .map_err(|e| warn!("Ignoring unreadable polling state: {}", e))
+                .ok(),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Failed to load polling state: {}", e);
+                None
+            }
+        }
+    }
+    
+    fn save_polling_state(&self, poller: &AdaptivePoller) {
+        if !self.config.strategies.weather.persist_polling_state {
+            return;
+        }
+        let json = serde_json::to_string(&poller.state(Utc::now())).expect("polling state serializes");
+        if let Err(e) = self.db.set_state(POLLING_STATE_KEY, &json) {
+            warn!("Failed to persist polling state: {}", e);
+        }
+    }
+    
+    /// Close open positions whose markets resolved more than `settlement_grace_secs` ago
+    async fn settle_resolved_markets(&self) -> Result<()> {
+        let mut resolutions = Vec::new();
+        for market_id in self.open_market_ids()? {
+            match self.gamma.fetch_resolution(&market_id).await {
+                Ok(Some(resolution)) => resolutions.push(resolution),
+                Ok(None) => {}
+                Err(e) => warn!("Couldn't check resolution of {}: {}", market_id, e),
+            }
+        }
+        if resolutions.is_empty() {
+            return Ok(());
+        }
+        
+        let grace = chrono::Duration::seconds(self.config.system.settlement_grace_secs as i64);
+        settle_resolved_positions(&self.db, self.store().as_ref(), &self.simulator, &resolutions, grace, Utc::now())?;
+        self.persist_ledger();
+        Ok(())
+    }
+    
+    /// Re-forecast markets with open positions; those whose EV has turned negative are logged and returned
+    async fn check_negative_ev(&self) -> Result<Vec<(Position, f64)>> {
+        let mut forecasts = HashMap::new();
+        for market_id in self.open_market_ids()? {
+            let market = match self.gamma.fetch_market(&market_id).await {
+                Ok(market) => market,
+                Err(e) => {
+                    warn!("Couldn't re-forecast {} for EV: {}", market_id, e);
+                    continue;
+                }
+            };
+            if let Some(forecast) = self.strategy.current_forecast(&market).await {
+                forecasts.insert(market_id, forecast);
+            }
+        }
+        negative_ev_positions(&self.db, &forecasts, self.config.monitoring.ev_fee_rate)
+    }
+    
+    /// Distinct markets with open positions
+    fn open_market_ids(&self) -> Result<Vec<String>> {
+        let mut market_ids: Vec<String> = self.db.get_open_positions()?.into_iter().map(|p| p.market_id).collect();
+        market_ids.sort();
+        market_ids.dedup();
+        Ok(market_ids)
+    }
+    
+    /// Re-fetch markets with open positions and flag any whose question/criteria were amended
+    async fn check_question_changes(&self) -> Result<()> {
+        for position in self.db.get_open_positions()? {
+            if position.question_hash.is_none() || position.tags.iter().any(|t| t == QUESTION_CHANGED_TAG) {
+                continue;
+            }
+            match self.gamma.fetch_market(&position.market_id).await {
+                Ok(market) => {
+                    flag_if_amended(self.store().as_ref(), &position, &market)?;
+                }
+                Err(e) => warn!("Couldn't re-check question for {}: {}", position.market_id, e),
+            }
+        }
+        Ok(())
+    }
+    
+    /// Mark open positions from their books and take partial profit on those up `min_gain_pct`
+    async fn scale_out_winners(&self, min_gain_pct: f64) -> Result<()> {
+        // Marked at the held side's bid: what the shares would actually sell for
+        let mut marks: Vec<(String, Side, f64)> = Vec::new();
+        for position in self.db.get_open_positions()? {
+            if position.tags.iter().any(|t| t == SCALED_OUT_TAG) {
+                continue;
+            }
+            let market = match self.gamma.fetch_market(&position.market_id).await {
+                Ok(market) => market,
+                Err(e) => {
+                    warn!("Couldn't mark {} for scale-out: {}", position.market_id, e);
+                    continue;
+                }
+            };
+            let side = position.side.clone().unwrap_or(Side::Yes);
+            match self.books.best_bid(&market, &side).await {
+                Ok(Some(bid)) => marks.push((position.market_id.clone(), side, bid)),
+                Ok(None) => {}
+                Err(e) => warn!("Couldn't mark {} for scale-out: {}", position.market_id, e),
+            }
+        }
+        
+        let prices = |market_id: &str, side: &Side| {
+            marks.iter().find(|(m, s, _)| m == market_id && s == side).map(|(_, _, bid)| *bid)
+        };
+        let fraction = self.config.strategies.weather.scale_out_fraction;
+        let scale_outs = scale_out_candidates(&self.db, &prices, min_gain_pct, fraction)?;
+        for scale_out in &scale_outs {
+            apply_scale_out(self.store().as_ref(), &self.simulator, scale_out)?;
+        }
+        if !scale_outs.is_empty() {
+            self.persist_ledger();
+        }
+        Ok(())
+    }
+    
+    /// Persist a declined trade (if enabled); failures are logged, not fatal
+    fn record_rejection(&self, rejection: &Rejection) {
+        if !self.config.monitoring.log_rejections {
+            return;
+        }
+        if let Err(e) = self.db.log_rejection(rejection) {
+            warn!("Failed to record rejection for {}: {}", rejection.market_id, e);
+        }
+    }
+    
+    fn record_dead_letter(&self, letter: &DeadLetter) {
+        if !self.config.monitoring.log_dead_letters {
+            return;
+        }
+        if let Err(e) = self.db.record_dead_letter(letter) {
+            warn!("Failed to record dead letter for {}: {}", letter.market_id, e);
+        }
+    }
+    
+    /// Where position writes go: SQLite, and the mirror too if there is one
+    fn store(&self) -> Box<dyn PositionStore + '_> {
+        match &self.mirror {
+            Some(mirror) => Box::new(TeeStore::new(&self.db, mirror.as_ref())),
+            None => Box::new(&self.db),
+        }
+    }
+    
+    fn insert_position(&self, position: &Position) -> Result<i64> {
+        self.store().insert_position(position)
+    }
+    
+    /// Move new paper-trading balance changes into the database; failures are logged, not fatal
+    fn persist_ledger(&self) {
+        for entry in self.simulator.take_ledger() {
+            if let Err(e) = self.db.record_ledger_entry(&entry) {
+                warn!("Failed to record ledger entry {:?}: {}", entry.cause, e);
+            }
+        }
+    }
+    
+    /// Compare positions against the venue once `reconcile_interval_secs` has passed
+    /// Runs between cycles, never alongside `execute_signal`, so a fill whose position isn't
+    /// written yet can't read as a mismatch. Paper trading only: the simulator is the venue.
+    fn reconcile_if_due(&mut self) {
+        let Some(secs) = self.config.system.reconcile_interval_secs else {
+            return;
+        };
+        if !self.config.paper_trading.enabled {
+            return;
+        }
+        if self.last_reconciled_at.is_some_and(|at| at.elapsed() < Duration::from_secs(secs)) {
+            return;
+        }
+        
+        self.last_reconciled_at = Some(Instant::now());
+        if let Err(e) = check_positions(&self.db, self.simulator.as_ref(), &self.breaker, self.config.risk.reconcile_halt_shares) {
+            warn!("Reconciliation failed: {:#}", e);
+        }
+    }
+    
+    /// Whether the kill-switch file exists, logging when that changes
+    fn check_kill_switch(&mut self) -> bool {
+        let Some(path) = &self.config.system.kill_switch_path else {
+            return false;
+        };
+        
+        let engaged = std::path::Path::new(path).exists();
+        if engaged != self.kill_switch_engaged {
+            if engaged {
+                warn!("🛑 Kill switch {} present: halting new orders", path);
+            } else {
+                info!("Kill switch {} removed: resuming", path);
+            }
+            self.kill_switch_engaged = engaged;
+        }
+        engaged
+    }
+    
+    /// Write the pause flag to the database when it changes (if enabled)
+    fn sync_pause_state(&mut self) -> Result<()> {
+        if !self.config.system.persist_pause {
+            return Ok(());
+        }
+        
+        let paused = self.pause.is_paused();
+        if self.persisted_pause != Some(paused) {
+            self.db.set_state(PAUSED_STATE_KEY, &paused.to_string())?;
+            self.persisted_pause = Some(paused);
+        }
+        Ok(())
+    }
+    
+    /// Refresh the market, then execute the signal if it's still tradable
+    async fn open_position(&mut self, market: &mut Market, signal: Signal) -> Result<bool> {
+        // Metadata may have changed since the market list was fetched
+        match self.gamma.refresh(market).await {
+            Ok(true) => {}
+            Ok(false) => {
+                info!("Market {} closed or expired since fetch, skipping", market.id);
+                return Ok(false);
+            }
+            Err(e) => {
+                warn!("Couldn't refresh {} before ordering, skipping: {}", market.id, e);
+                return Ok(false);
+            }
+        }
+        
+        self.execute_signal(&signal, Some(market)).await
+    }
+    
+    /// Cash plus holdings marked at their market's last mid (entry price if not priced yet)
+    fn equity(&self) -> f64 {
+        let prices = |market_id: &str, side: &Side| {
+            self.marks.get(market_id).map(|yes| match side {
+                Side::Yes => *yes,
+                Side::No => 1.0 - yes,
+            })
+        };
+        self.simulator.equity(&prices)
+    }
+    
+    /// Validate and execute a signal, returning whether a position was opened
+    /// `market` (when known) supplies share granularity and the question hash kept with the position
+    async fn execute_signal(&mut self, signal: &Signal, market: Option<&Market>) -> Result<bool> {
+        if self.check_kill_switch() {
+            info!("Kill switch engaged, not placing order for {}", signal.market_id);
+            return Ok(false);
+        }
+        
+        if self.breaker.is_triggered() {
+            info!("Circuit breaker tripped, not placing order for {}", signal.market_id);
+            return Ok(false);
+        }
+        
+        // Pause may have been set mid-cycle
+        if self.pause.is_paused() {
+            info!("Paused, not placing order for {}", signal.market_id);
+            return Ok(false);
+        }
+        
+        let balance = self.simulator.balance();
+        let floor = self.config.risk.min_balance_floor_usd.filter(|_| self.config.paper_trading.enabled);
+        if let Some(floor) = floor.filter(|floor| balance < *floor) {
+            warn!("Balance ${} below floor ${}, halting new positions", fmt_usd(balance), fmt_usd(floor));
+            self.breaker.trigger(CircuitBreakerReason::BalanceFloor(balance), &self.db)?;
+            return Ok(false);
+        }
+        
+        if let Err(e) = self.risk.validate_trade(signal, &self.db, balance, self.equity()).await {
+            info!("Trade rejected for {}: {}", signal.market_id, e);
+            self.record_rejection(&Rejection::from_signal(signal, e.code(), e.to_string()));
+            return Ok(false);
+        }
+        
+        let order_options = OrderOptions::from_config(&self.config.strategies.weather)
+            .with_share_granularity(market.and_then(|m| m.share_granularity))
+            .with_tick_size(market.and_then(|m| m.tick_size))
+            .with_min_order_size(market.and_then(|m| m.min_order_size))
+            .with_book_depth(market);
+        let Some(order) = build_order(signal, &order_options) else {
+            return Ok(false);
+        };
+        
+        if !self.config.paper_trading.enabled {
+            if let Some(plan) = OrderPlan::from_signal(signal, &order_options, None) {
+                info!("Dry run order plan:\n{}", plan);
+            }
+            return Ok(false);
+        }
+        
+        // Write-ahead so a crash mid-submission is visible to recovery
+        let intent_key = format!(
+            "{}:{:?}:{}",
+            order.market_id,
+            order.token,
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        );
+        self.db.record_intent(&intent_key, &order)?;
+        // Pending until the outcome is known, counted against max_open_orders
+        let order_id = self.db.insert_order(&order, None)?;
+        
+        let outcome = self.simulator.execute_order(&order).await;
+        self.persist_ledger();
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            Err(e) if submission_outcome_unknown(&e) => {
+                // May have been placed: leave the order pending and the intent for recovery to flag
+                warn!("Order submission for {} has unknown outcome: {}", signal.market_id, e);
+                self.db.resolve_intent(&intent_key, "unknown")?;
+                self.record_rejection(&Rejection::from_signal(signal, "submit_unknown", e.to_string()));
+                return Ok(false);
+            }
+            Err(e) => {
+                // One failed submission shouldn't abandon the rest of the cycle
+                warn!("Order submission failed for {}: {}", signal.market_id, e);
+                self.db.resolve_intent(&intent_key, "failed")?;
+                self.db.mark_order_rejected(order_id)?;
+                self.record_rejection(&Rejection::from_signal(signal, "submit_failed", e.to_string()));
+                return Ok(false);
+            }
+        };
+        
+        let fill = match outcome {
+            FillOutcome::Filled(fill) => fill,
+            FillOutcome::Rejected(reason) => {
+                self.db.resolve_intent(&intent_key, "rejected")?;
+                self.db.mark_order_rejected(order_id)?;
+                info!("Order for {} not filled: {:?}", signal.market_id, reason);
+                return Ok(false);
+            }
+        };
+        if !self.db.record_fill(&fill)? {
+            // Already booked under an earlier submission; a second position would double count it
+            self.db.mark_order_filled(order_id, None)?;
+            self.db.resolve_intent(&intent_key, "duplicate")?;
+            return Ok(false);
+        }
+        verify_fill(&order, &fill, &self.config.risk, &self.db, &self.breaker)?;
+        
+        let mut position = self.simulator.create_position_from_fill(
+            &fill,
+            order.side.clone(),
+            "weather_edge",
+            signal.event_key.clone(),
+        );
+        position.question_hash = market.map(Market::criteria_hash);
+        position.id = Some(self.insert_position(&position)?);
+        self.db.mark_order_filled(order_id, position.id)?;
+        self.db.resolve_intent(&intent_key, "filled")?;
+        
+        if let Some(logger) = &self.logger {
+            logger.log_position(&position)?;
+        }
+        
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    
+    fn test_engine() -> WeatherEngine {
+        test_engine_with_gamma("https://example.invalid")
+    }
+    
+    fn test_engine_with_gamma(gamma_url: &str) -> WeatherEngine {
+        test_engine_on(PositionDatabase::new(":memory:").unwrap(), gamma_url)
+    }
+    
+    /// Paper engine over `db`, e.g. a file shared by an engine before and after a restart
+    fn test_engine_on(db: PositionDatabase, gamma_url: &str) -> WeatherEngine {
+        WeatherEngine::new(test_config(), &test_env(gamma_url), db, HttpClient::default()).unwrap()
+    }
+    
+    fn test_config() -> Config {
+        let mut config = Config::for_test();
+        config.monitoring.csv_logging = false;
+        config.paper_trading.enabled = true;
+        config.paper_trading.fill_rate = 1.0;
+        config.paper_trading.seed = Some(7);
+        config
+    }
+    
+    fn test_env(gamma_url: &str) -> EnvConfig {
+        EnvConfig {
+            polygon_rpc_primary: String::new(),
+            polygon_rpc_secondary: String::new(),
+            polygon_wallet_private_key: String::new(),
+            anthropic_api_key: String::new(),
+            noaa_api_key: None,
+            polymarket_clob_url: "https://example.invalid".to_string(),
+            polymarket_gamma_url: gamma_url.to_string(),
+            polymarket_ws_url: "wss://example.invalid".to_string(),
+            dry_run: true,
+            telegram_bot_token: None,
+            telegram_chat_id: None,
+        }
+    }
+    
+    /// Local Gamma stand-in: `/markets/{id}` returns that entry, anything else an empty list
+    async fn serve_gamma(markets: Vec<serde_json::Value>) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+        
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let markets = Arc::new(markets);
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let markets = markets.clone();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap();
+                    let request = String::from_utf8_lossy(&buf[..n]);
+                    let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+                    let body = markets
+                        .iter()
+                        .find(|m| path == format!("/markets/{}", m["condition_id"].as_str().unwrap()))
+                        .map_or_else(|| r#"{"data":[]}"#.to_string(), |m| m.to_string());
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        url
+    }
+    
+    fn test_signal(market_id: &str) -> Signal {
+        Signal {
+            market_id: market_id.to_string(),
+            strategy: Strategy::WeatherEdge,
+            side: Some(Side::Yes),
+            entry_price: 0.50,
+            intent: OrderIntent::Limit,
+            size: 40.0,
+            edge: Some(0.12),
+            confidence: 0.9,
+            event_key: None,
+            market_volume_24h: None,
+            market_liquidity: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_pause_stops_execution_while_monitoring_continues() {
+        let mut engine = test_engine();
+        let pause = engine.pause_switch();
+        
+        // Monitoring task keeps ticking regardless of pause
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let monitor_ticks = Arc::clone(&ticks);
+        let monitor = tokio::spawn(async move {
+            for _ in 0..5 {
+                monitor_ticks.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        });
+        
+        pause.set(true);
+        assert!(!engine.execute_signal(&test_signal("paused-market"), None).await.unwrap());
+        assert_eq!(engine.db.count_open_positions().unwrap(), 0);
+        
+        monitor.await.unwrap();
+        assert_eq!(ticks.load(Ordering::SeqCst), 5);
+        
+        pause.set(false);
+        assert!(engine.execute_signal(&test_signal("resumed-market"), None).await.unwrap());
+        assert_eq!(engine.db.count_open_positions().unwrap(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_trading_halts_once_balance_falls_under_floor() {
+        let mut engine = test_engine();
+        engine.config.risk.min_balance_floor_usd = Some(1970.0);
+        
+        // $2000 -> $1960: the trade itself may cross the floor
+        assert!(engine.execute_signal(&test_signal("first-market"), None).await.unwrap());
+        assert!(engine.simulator.balance() < 1970.0);
+        
+        // The next one halts trading instead
+        assert!(!engine.execute_signal(&test_signal("second-market"), None).await.unwrap());
+        assert_eq!(engine.db.count_open_positions().unwrap(), 1);
+        assert!(matches!(engine.breaker.reason(), Some(CircuitBreakerReason::BalanceFloor(_))));
+        
+        // Outside paper trading the simulator's balance says nothing about the venue's
+        let mut engine = test_engine();
+        engine.config.risk.min_balance_floor_usd = Some(1_000_000.0);
+        engine.config.paper_trading.enabled = false;
+        engine.config.system.dry_run = true;
+        engine.execute_signal(&test_signal("live-market"), None).await.unwrap();
+        assert!(!engine.breaker.is_triggered());
+    }
+    
+    #[tokio::test]
+    async fn test_failed_submission_is_recorded_and_cycle_continues() {
+        // A 1% per-order cap makes the simulator refuse the $40 order outright
+        let mut config = test_config();
+        config.risk.max_order_balance_fraction = 0.01;
+        config.monitoring.log_rejections = true;
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let mut engine = WeatherEngine::new(config, &test_env("https://example.invalid"), db, HttpClient::default()).unwrap();
+        
+        assert!(!engine.execute_signal(&test_signal("capped-market"), None).await.unwrap());
+        let rejections = engine.db.rejection_summary(Utc::now() - chrono::Duration::hours(1)).unwrap();
+        assert_eq!(rejections, vec![("submit_failed".to_string(), 1)]);
+        assert_eq!(engine.db.count_open_positions().unwrap(), 0);
+    }
+    
+    #[test]
+    fn test_candidates_capped_per_cycle() {
+        let now = Utc::now();
+        let candidates: Vec<Market> = (0..20)
+            .map(|i| Market {
+                id: format!("m{}", i),
+                question: "Will NYC temperature exceed 60°F?".to_string(),
+                end_date: now + chrono::Duration::hours(48 - i),
+                yes_price: 0.5,
+                yes_ask: 0.5,
+                no_ask: 0.5,
+                volume_24h: 10_000.0,
+                yes_liquidity: 5_000.0,
+                no_liquidity: 5_000.0,
+                closed: false,
+                share_granularity: None,
+                tick_size: None,
+                min_order_size: None,
+                quoted_yes_ask: None,
+                quoted_no_ask: None,
+                priced: false,
+                outcomes: Vec::new(),
+                yes_asks: Vec::new(),
+                no_asks: Vec::new(),
+                token_ids: Vec::new(),
+                description: None,
+            })
+            .collect();
+        
+        let (analyzed, deferred) = prioritize_candidates(candidates, 5);
+        assert_eq!(analyzed.len(), 5);
+        assert_eq!(deferred, 15);
+        
+        // Soonest to resolve go first
+        let ids: Vec<&str> = analyzed.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["m19", "m18", "m17", "m16", "m15"]);
+    }
+    
+    #[test]
+    fn test_whitelist_limits_analysis_to_listed_markets() {
+        let mut engine = test_engine();
+        let market = |id: &str, question: &str| -> Market {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "question": question,
+                "end_date": (Utc::now() + chrono::Duration::hours(48)).to_rfc3339(),
+                "yes_price": 0.5, "yes_ask": 0.5, "no_ask": 0.5,
+                "volume_24h": 10000.0, "yes_liquidity": 5000.0, "no_liquidity": 5000.0,
+                "closed": false, "share_granularity": null
+            }))
+            .unwrap()
+        };
+        let markets = vec![
+            market("0xnyc", "Will NYC temperature exceed 60°F?"),
+            market("0xlondon", "Will London temperature exceed 15°C?"),
+            market("0xelection", "Will the incumbent win the election?"),
+        ];
+        
+        // No whitelist: the weather screen drops the non-weather market
+        let screened: Vec<String> = engine.screen_markets(markets.clone()).into_iter().map(|m| m.id).collect();
+        assert_eq!(screened, vec!["0xnyc", "0xlondon"]);
+        
+        // Whitelist: only listed ids, screens bypassed
+        engine.config.system.whitelist = vec!["0xelection".to_string(), "0xnyc".to_string()];
+        let screened: Vec<String> = engine.screen_markets(markets).into_iter().map(|m| m.id).collect();
+        assert_eq!(screened, vec!["0xnyc", "0xelection"]);
+    }
+    
+    #[test]
+    fn test_quiet_cycles_lengthen_polling_up_to_bound() {
+        let base = Duration::from_secs(60);
+        let max = Duration::from_secs(300);
+        let mut poller = AdaptivePoller::new(base, max, 2.0, 2);
+        
+        // First empty cycle is within tolerance
+        assert_eq!(poller.record_cycle(0), base);
+        assert_eq!(poller.record_cycle(0), Duration::from_secs(120));
+        assert_eq!(poller.record_cycle(0), Duration::from_secs(240));
+        assert_eq!(poller.record_cycle(0), max);
+        assert_eq!(poller.record_cycle(0), max);
+        
+        // Activity resumes: straight back to the base interval
+        assert_eq!(poller.record_cycle(3), base);
+        assert_eq!(poller.record_cycle(0), base);
+    }
+    
+    #[test]
+    fn test_restart_resumes_backed_off_polling_interval() {
+        let mut engine = test_engine();
+        engine.config.strategies.weather.persist_polling_state = true;
+        engine.config.strategies.weather.polling_interval_max_secs = Some(14_400);
+        
+        let mut poller = AdaptivePoller::from_config(&engine.config.strategies.weather);
+        for _ in 0..5 {
+            poller.record_cycle(0);
+        }
+        assert_eq!(poller.interval(), Duration::from_secs(14_400));
+        engine.save_polling_state(&poller);
+        
+        // After a restart the poller starts at the base interval until the saved state is applied
+        let mut restarted = AdaptivePoller::from_config(&engine.config.strategies.weather);
+        assert_eq!(restarted.interval(), Duration::from_secs(3600));
+        let state = engine.load_polling_state().unwrap();
+        restarted.restore(&state);
+        assert_eq!(restarted.interval(), Duration::from_secs(14_400));
+        // The cycle just ran, so nearly the whole interval is still to wait
+        assert!(state.remaining(Utc::now()) > Duration::from_secs(14_000));
+        
+        // Still backed off: one more quiet cycle keeps it at the bound, activity resets it
+        assert_eq!(restarted.record_cycle(0), Duration::from_secs(14_400));
+        assert_eq!(restarted.record_cycle(2), Duration::from_secs(3600));
+    }
+    
+    #[tokio::test]
+    async fn test_kill_switch_file_halts_and_resumes_orders() {
+        let mut engine = test_engine();
+        let path = std::env::temp_dir().join(format!("celsius-kill-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        engine.config.system.kill_switch_path = Some(path.to_string_lossy().into_owned());
+        
+        std::fs::write(&path, "").unwrap();
+        assert!(!engine.execute_signal(&test_signal("halted-market"), None).await.unwrap());
+        assert_eq!(engine.db.count_open_positions().unwrap(), 0);
+        
+        std::fs::remove_file(&path).unwrap();
+        assert!(engine.execute_signal(&test_signal("resumed-market"), None).await.unwrap());
+        assert_eq!(engine.db.count_open_positions().unwrap(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_rejected_signal_is_recorded_with_reason() {
+        let mut engine = test_engine();
+        let since = Utc::now() - chrono::Duration::seconds(1);
+        
+        let mut signal = test_signal("oversized-market");
+        signal.size = engine.config.risk.max_position_size_usd + 10.0;
+        
+        assert!(!engine.execute_signal(&signal, None).await.unwrap());
+        assert_eq!(
+            engine.db.rejection_summary(since).unwrap(),
+            vec![("position_too_large".to_string(), 1)]
+        );
+    }
+    
+    #[test]
+    fn test_pause_state_is_persisted_and_restored() {
+        let path = std::env::temp_dir().join(format!("celsius-pause-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let path = path.to_string_lossy().into_owned();
+        let mut config = test_config();
+        config.system.persist_pause = true;
+        let start = || {
+            let db = PositionDatabase::new(&path).unwrap();
+            WeatherEngine::new(config.clone(), &test_env("https://example.invalid"), db, HttpClient::default()).unwrap()
+        };
+        
+        // SIGUSR1 arrives and the bot restarts before another engine loop runs
+        let engine = start();
+        let signal_db = PositionDatabase::new(&path).unwrap();
+        assert!(toggle_pause(&engine.pause_switch(), Some(&signal_db)).unwrap());
+        drop(engine);
+        
+        let engine = start();
+        assert!(engine.pause_switch().is_paused());
+        
+        // Toggling again resumes, and that survives a restart too
+        assert!(!toggle_pause(&engine.pause_switch(), Some(&signal_db)).unwrap());
+        drop(engine);
+        assert!(!start().pause_switch().is_paused());
+        
+        drop(signal_db);
+        let _ = std::fs::remove_file(&path);
+    }
+    
+    #[tokio::test]
+    async fn test_cycle_settles_markets_resolved_past_grace_period() {
+        let resolved = |id: &str, prices: &str, closed_ago: chrono::Duration| {
+            serde_json::json!({
+                "condition_id": id,
+                "question": "Will NYC temperature exceed 60°F on 2026-02-17?",
+                "closed": true,
+                "outcomePrices": prices,
+                "closedTime": (Utc::now() - closed_ago).format("%Y-%m-%d %H:%M:%S+00").to_string(),
+                "umaResolutionStatus": "resolved",
+            })
+        };
+        let url = serve_gamma(vec![
+            resolved("0xwon", r#"["1", "0"]"#, chrono::Duration::hours(3)),
+            resolved("0xlost", r#"["0", "1"]"#, chrono::Duration::hours(3)),
+            resolved("0xfresh", r#"["1", "0"]"#, chrono::Duration::minutes(10)),
+        ])
+        .await;
+        
+        let mut engine = test_engine_with_gamma(&url);
+        for market_id in ["0xwon", "0xlost", "0xfresh"] {
+            engine.db.insert_position(&Position::fixture(market_id)).unwrap();
+        }
+        let balance = engine.simulator.balance();
+        
+        engine.run_cycle().await.unwrap();
+        
+        // 100 YES shares bought for $40: +$60 on a YES win, -$40 on a NO win
+        let closed = engine.db.get_closed_positions().unwrap();
+        let pnl = |id: &str| closed.iter().find(|p| p.market_id == id).and_then(|p| p.pnl);
+        assert_eq!(pnl("0xwon"), Some(60.0));
+        assert_eq!(pnl("0xlost"), Some(-40.0));
+        // Resolved 10 minutes ago: still inside the 2h grace period
+        assert_eq!(engine.db.get_open_positions().unwrap()[0].market_id, "0xfresh");
+        assert!((engine.simulator.balance() - (balance + 100.0)).abs() < 1e-9);
+    }
+    
+    #[tokio::test]
+    async fn test_orders_in_flight_count_against_max_open_orders() {
+        let mut engine = test_engine();
+        engine.config.risk.max_open_orders = 1;
+        engine.risk = RiskManager::new(engine.config.risk.clone());
+        
+        // A filled order leaves nothing pending
+        assert!(engine.execute_signal(&test_signal("filled-market"), None).await.unwrap());
+        assert_eq!(engine.db.count_pending_orders().unwrap(), 0);
+        
+        // An order still awaiting its outcome (here: the submission is abandoned mid-flight)
+        let mut paper = engine.config.paper_trading.clone();
+        paper.latency_min_ms = 5_000;
+        paper.latency_max_ms = 5_000;
+        engine.simulator = Arc::new(PaperTradingSimulator::new(paper, 1.0));
+        let slow = test_signal("slow-market");
+        let submit = engine.execute_signal(&slow, None);
+        assert!(tokio::time::timeout(Duration::from_millis(50), submit).await.is_err());
+        assert_eq!(engine.db.count_pending_orders().unwrap(), 1);
+        
+        // ...holds the only order slot
+        let since = Utc::now() - chrono::Duration::seconds(1);
+        assert!(!engine.execute_signal(&test_signal("blocked-market"), None).await.unwrap());
+        assert!(engine.db.rejection_summary(since).unwrap().contains(&("max_open_orders".to_string(), 1)));
+    }
+    
+    #[tokio::test]
+    async fn test_reconciliation_runs_between_orders_on_its_interval() {
+        let mut engine = test_engine();
+        engine.config.system.reconcile_interval_secs = Some(3600);
+        
+        // Fill and position are both recorded by the time reconciliation runs
+        assert!(engine.execute_signal(&test_signal("0xbooked"), None).await.unwrap());
+        engine.reconcile_if_due();
+        assert!(!engine.breaker.is_triggered());
+        
+        // A position the venue doesn't hold waits for the next interval...
+        engine.db.insert_position(&Position::fixture("0xunheld")).unwrap();
+        engine.reconcile_if_due();
+        assert!(!engine.breaker.is_triggered());
+        
+        // Outside paper trading there are no venue holdings to compare against
+        engine.config.paper_trading.enabled = false;
+        engine.last_reconciled_at = None;
+        engine.reconcile_if_due();
+        assert!(!engine.breaker.is_triggered());
+        engine.config.paper_trading.enabled = true;
+        
+        // ...and then trips the breaker
+        engine.last_reconciled_at = None;
+        engine.reconcile_if_due();
+        assert!(matches!(engine.breaker.reason(), Some(CircuitBreakerReason::PositionMismatch(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_arb_basket_becomes_a_position_priced_off_fresh_quotes() {
+        let mut engine = test_engine();
+        engine.config.paper_trading.slippage_pct = 0.0;
+        engine.simulator = Arc::new(PaperTradingSimulator::new(engine.config.paper_trading.clone(), 1.0));
+        let market = |id: &str| -> Market {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "question": "", "end_date": (Utc::now() + chrono::Duration::hours(48)).to_rfc3339(),
+                "yes_price": 0.45, "yes_ask": 0.45, "no_ask": 0.50,
+                "volume_24h": 10000.0, "yes_liquidity": 5000.0, "no_liquidity": 5000.0,
+                "closed": false, "share_granularity": null, "priced": true
+            }))
+            .unwrap()
+        };
+        
+        // Never quoted this cycle: no arb
+        assert_eq!(engine.trade_arbs(&[market("0xstale")]).await.unwrap(), 0);
+        
+        // Asks sum to 0.95: buy both sides as one position
+        let fresh = market("0xarb");
+        cache_asks(&engine.arb_quotes, &fresh);
+        assert_eq!(engine.trade_arbs(&[fresh]).await.unwrap(), 1);
+        
+        let position = &engine.db.get_open_positions().unwrap()[0];
+        assert_eq!(position.strategy, "sum_to_one_arb");
+        assert_eq!(position.yes_shares, position.no_shares);
+        assert!((position.cost - position.yes_shares * 0.95).abs() < 1e-6);
+        assert_eq!(engine.db.get_fills_for_market("0xarb").unwrap().len(), 2);
+        assert!(engine.db.get_ledger().unwrap().iter().any(|e| e.cause == crate::execution::types::LedgerCause::Fill));
+    }
+    
+    #[tokio::test]
+    async fn test_restart_resumes_balance_from_ledger() {
+        let path = std::env::temp_dir().join(format!("celsius-ledger-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let path = path.to_string_lossy().into_owned();
+        
+        let mut engine = test_engine_on(PositionDatabase::new(&path).unwrap(), "https://example.invalid");
+        assert!(engine.execute_signal(&test_signal("0xheld"), None).await.unwrap());
+        let balance = engine.simulator.balance();
+        drop(engine);
+        
+        // Same cash after restart, and still a single deposit on the books
+        let engine = test_engine_on(PositionDatabase::new(&path).unwrap(), "https://example.invalid");
+        assert!((engine.simulator.balance() - balance).abs() < 1e-9);
+        engine.persist_ledger();
+        let deposits = engine.db.get_ledger().unwrap().iter().filter(|e| e.cause == crate::execution::types::LedgerCause::Deposit).count();
+        assert_eq!(deposits, 1);
+        
+        drop(engine);
+        let _ = std::fs::remove_file(&path);
+    }
+    
+    #[tokio::test]
+    async fn test_failed_refresh_skips_only_that_market() {
+        // Nothing listens on port 1: every Gamma request fails
+        let mut engine = test_engine_with_gamma("http://127.0.0.1:1");
+        let mut market: Market = serde_json::from_value(serde_json::json!({
+            "id": "0xgone", "question": "", "end_date": (Utc::now() + chrono::Duration::hours(48)).to_rfc3339(),
+            "yes_price": 0.5, "yes_ask": 0.5, "no_ask": 0.5,
+            "volume_24h": 10000.0, "yes_liquidity": 5000.0, "no_liquidity": 5000.0,
+            "closed": false, "share_granularity": null
+        }))
+        .unwrap();
+        
+        assert!(!engine.open_position(&mut market, test_signal("0xgone")).await.unwrap());
+        assert_eq!(engine.db.count_open_positions().unwrap(), 0);
+    }
+    
+    struct FixedForecast(f64);
+    
+    impl crate::data::weather::ForecastProvider for FixedForecast {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+        
+        fn forecast<'a>(
+            &'a self,
+            _city: &'a str,
+            _threshold: f64,
+            _resolution_time: DateTime<Utc>,
+            _kind: crate::data::types::ForecastKind,
+        ) -> futures::future::BoxFuture<'a, Result<crate::data::types::ProbabilisticForecast>> {
+            Box::pin(async move {
+                Ok(crate::data::types::ProbabilisticForecast {
+                    probability: self.0,
+                    confidence: 0.9,
+                    mean_temp: 15.0,
+                    std_dev: 1.5,
+                    model: "fixed".to_string(),
+                })
+            })
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_whitelisted_markets_are_fetched_by_id() {
+        // The listing (/markets) is empty: whitelisted markets must come from /markets/{id}
+        let url = serve_gamma(vec![serde_json::json!({
+            "condition_id": "0xlisted",
+            "question": "Will NYC temperature exceed 60°F on 2026-02-17?",
+            "end_date_iso": (Utc::now() + chrono::Duration::hours(30)).to_rfc3339(),
+            "closed": false,
+        })])
+        .await;
+        let mut engine = test_engine_with_gamma(&url);
+        engine.config.system.whitelist = vec!["0xlisted".to_string(), "0xmissing".to_string()];
+        
+        let ids: Vec<String> = engine.fetch_whitelisted_markets().await.into_iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec!["0xlisted"]);
+    }
+    
+    #[tokio::test]
+    async fn test_ev_pass_flags_positions_whose_forecast_turned() {
+        let url = serve_gamma(vec![serde_json::json!({
+            "condition_id": "0xsour",
+            "question": "Will NYC temperature exceed 60°F on 2026-02-17?",
+            "end_date_iso": (Utc::now() + chrono::Duration::hours(30)).to_rfc3339(),
+            "closed": false,
+        })])
+        .await;
+        let mut engine = test_engine_with_gamma(&url);
+        engine.db.insert_position(&Position::fixture("0xsour")).unwrap();
+        
+        // Bought YES at 0.40; the forecast now gives it 20%
+        engine.strategy = WeatherEdgeStrategy::with_providers(
+            engine.config.strategies.weather.clone(),
+            vec![Box::new(FixedForecast(0.20))],
+        ).unwrap();
+        let flagged = engine.check_negative_ev().await.unwrap();
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].0.market_id, "0xsour");
+        
+        // Still backed by the forecast: nothing to flag
+        engine.strategy = WeatherEdgeStrategy::with_providers(
+            engine.config.strategies.weather.clone(),
+            vec![Box::new(FixedForecast(0.70))],
+        ).unwrap();
+        assert!(engine.check_negative_ev().await.unwrap().is_empty());
+    }
+}
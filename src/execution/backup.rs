@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use crate::execution::persistence::{PositionDatabase, ReadOnlyDatabase};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{info, warn};
+
+const BACKUP_PREFIX: &str = "positions-";
+const BACKUP_EXTENSION: &str = ".db";
+
+/// Copy the database into `dir` under a timestamped name, then prune to the newest `keep`
+pub fn write_backup(db: &ReadOnlyDatabase, dir: &Path, keep: usize) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create backup directory {}", dir.display()))?;
+    
+    // Millisecond timestamps sort lexically, so the file name alone orders backups
+    let name = format!("{}{}{}", BACKUP_PREFIX, Utc::now().format("%Y%m%dT%H%M%S%3fZ"), BACKUP_EXTENSION);
+    let path = dir.join(name);
+    db.backup_to(&path.to_string_lossy())
+        .with_context(|| format!("Failed to write backup {}", path.display()))?;
+    
+    prune_backups(dir, keep)?;
+    Ok(path)
+}
+
+/// Delete all but the newest `keep` backups; other files in the directory are left alone
+fn prune_backups(dir: &Path, keep: usize) -> Result<()> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(BACKUP_PREFIX) && n.ends_with(BACKUP_EXTENSION))
+        })
+        .collect();
+    backups.sort();
+    
+    let excess = backups.len().saturating_sub(keep.max(1));
+    for old in &backups[..excess] {
+        std::fs::remove_file(old).with_context(|| format!("Failed to remove old backup {}", old.display()))?;
+    }
+    Ok(())
+}
+
+/// Back up the database every `interval`, forever; a failed backup is logged and retried next time
+/// Uses its own read-only connection, which waits out the bot's locks rather than contending for them
+pub async fn run_backup_task(db_path: String, dir: String, interval: Duration, keep: usize) -> Result<()> {
+    let db = PositionDatabase::open_readonly(&db_path)?;
+    let dir = PathBuf::from(dir);
+    
+    loop {
+        match write_backup(&db, &dir, keep) {
+            Ok(path) => info!("💾 Database backed up to {}", path.display()),
+            Err(e) => warn!("Database backup failed: {:#}", e),
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::types::Position;
+    
+    #[test]
+    fn test_backup_is_an_openable_copy_and_old_ones_are_pruned() {
+        let dir = std::env::temp_dir().join(format!("celsius-backups-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db_path = std::env::temp_dir().join(format!("celsius-backup-src-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&db_path);
+        let db_path = db_path.to_string_lossy().into_owned();
+        
+        let writer = PositionDatabase::new(&db_path).unwrap();
+        writer.insert_position(&Position {
+            yes_shares: 80.0,
+            entry_price: 0.50,
+            ..Position::fixture("0xbackup")
+        })
+        .unwrap();
+        
+        let db = PositionDatabase::open_readonly(&db_path).unwrap();
+        let path = write_backup(&db, &dir, 2).unwrap();
+        let copy = PositionDatabase::new(&path.to_string_lossy()).unwrap();
+        let open = copy.get_open_positions().unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].market_id, "0xbackup");
+        drop(copy);
+        
+        // Unrelated files survive pruning; only the newest two backups remain
+        std::fs::write(dir.join("notes.txt"), "keep me").unwrap();
+        for _ in 0..3 {
+            std::thread::sleep(Duration::from_millis(2));
+            write_backup(&db, &dir, 2).unwrap();
+        }
+        let mut names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), 3);
+        assert!(names.contains(&"notes.txt".to_string()));
+        assert!(!names.contains(&path.file_name().unwrap().to_string_lossy().into_owned()));
+        
+        std::fs::remove_dir_all(&dir).unwrap();
+        drop((db, writer));
+        std::fs::remove_file(&db_path).unwrap();
+    }
+}
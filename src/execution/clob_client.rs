@@ -1 +1,313 @@
-// TODO: Implement clob_client
+use anyhow::{Context, Result};
+use chrono::Utc;
+use crate::data::http::HttpClient;
+use serde::Deserialize;
+use crate::execution::rate_limit::OrderRateLimiter;
+use crate::execution::risk::check_order_balance_cap;
+use crate::execution::types::{Fill, FillOutcome, Order, OrderType, RejectReason, Token};
+use crate::math::format::fmt_price;
+use tracing::{info, warn};
+
+/// Live Polymarket CLOB client
+pub struct ClobClient {
+    http: HttpClient,
+    base_url: String,
+    max_order_balance_fraction: f64,
+    /// Polygon RPC used to look up the block of each fill's transaction
+    receipt_rpc: Option<String>,
+    rate_limiter: Option<OrderRateLimiter>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+struct OrderResponse {
+    success: bool,
+    #[serde(default)]
+    errorMsg: Option<String>,
+    #[serde(default)]
+    orderID: Option<String>,
+    /// "matched" once the order traded; "live" (resting) and "delayed" haven't yet
+    #[serde(default)]
+    status: Option<String>,
+    /// Trades the order matched, one per counterparty order
+    #[serde(default)]
+    tradeIDs: Vec<String>,
+    /// Settlement transactions for the matched order (the CLOB's spelling)
+    #[serde(default)]
+    transactionsHashes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionReceipt {
+    /// Hex-encoded, e.g. "0x3a1b2c"
+    block_number: String,
+}
+
+impl ClobClient {
+    pub fn new(base_url: String, max_order_balance_fraction: f64) -> Self {
+        Self {
+            http: HttpClient::default(),
+            base_url,
+            max_order_balance_fraction,
+            receipt_rpc: None,
+            rate_limiter: None,
+        }
+    }
+    
+    /// Record the block number of each fill's transaction, looked up on this RPC
+    pub fn with_receipt_rpc(mut self, rpc_url: String) -> Self {
+        self.receipt_rpc = Some(rpc_url);
+        self
+    }
+    
+    /// Refuse to submit more than `per_minute` orders
+    pub fn with_rate_limit(mut self, per_minute: u32) -> Self {
+        self.rate_limiter = Some(OrderRateLimiter::new(per_minute));
+        self
+    }
+    
+    /// Share a connection pool and in-flight limit with the other API clients
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+    
+    /// Submit an order to the CLOB; only a matched order comes back as a fill
+    pub async fn submit_order(&self, order: &Order, available_balance: f64) -> Result<FillOutcome> {
+        // Last-line defense against sizing bugs
+        let cost = order.size * order.price;
+        check_order_balance_cap(cost, available_balance, self.max_order_balance_fraction)?;
+        
+        if let Some(limiter) = &self.rate_limiter {
+            if !limiter.try_acquire() {
+                info!("Order rate limit reached, next slot in {:?}", limiter.retry_after());
+                return Ok(FillOutcome::Rejected(RejectReason::RateLimited));
+            }
+        }
+        
+        // TODO: EIP-712 order signing with the wallet key
+        let payload = serde_json::json!({
+            "market": order.market_id,
+            "outcome": match order.token {
+                Token::Yes => "YES",
+                Token::No => "NO",
+            },
+            "price": order.price,
+            "size": order.size,
+            "orderType": match order.order_type {
+                OrderType::FOK => "FOK",
+                OrderType::GTC => "GTC",
+            },
+        });
+        
+        let _permit = self.http.permit().await;
+        let response: OrderResponse = self.http
+            .post(format!("{}/order", self.base_url))
+            .json(&payload)
+            .send()
+            .await
+            .context("Failed to submit order")?
+            .json()
+            .await
+            .context("Failed to parse order response")?;
+        
+        if !response.success {
+            anyhow::bail!(
+                "Order rejected by CLOB: {}",
+                response.errorMsg.unwrap_or_default()
+            );
+        }
+        
+        if response.status.as_deref() != Some("matched") {
+            // Accepted but not traded: nothing to book until the venue reports the match
+            info!(
+                "Live order {} not matched (status {})",
+                response.orderID.as_deref().unwrap_or("?"),
+                response.status.as_deref().unwrap_or("none")
+            );
+            return Ok(FillOutcome::Rejected(RejectReason::NotFilled));
+        }
+        
+        info!("Live order matched: {:?} {} @ ${}", order.token, order.size, fmt_price(order.price));
+        
+        let tx_hash = response.transactionsHashes.into_iter().next();
+        let block_number = match (&self.receipt_rpc, &tx_hash) {
+            (Some(rpc_url), Some(hash)) => match self.fetch_block_number(rpc_url, hash).await {
+                Ok(block) => block,
+                Err(e) => {
+                    // The order is filled either way; reconciliation can look the block up later
+                    warn!("Receipt lookup failed for {}: {}", hash, e);
+                    None
+                }
+            },
+            _ => None,
+        };
+        
+        Ok(FillOutcome::Filled(Fill {
+            market_id: order.market_id.clone(),
+            token: Some(order.token.clone()),
+            size: order.size,
+            price: order.price,
+            cost,
+            // Filled at the limit; the CLOB response doesn't report an average price
+            slippage: 0.0,
+            timestamp: Utc::now(),
+            tx_hash: tx_hash.clone(),
+            block_number,
+            order_id: response.orderID,
+            // The venue's match id, so a redelivered trade is recognised as the same fill
+            fill_id: response.tradeIDs.into_iter().next(),
+        }))
+    }
+    
+    /// Block a transaction was mined in, None while it's still pending
+    async fn fetch_block_number(&self, rpc_url: &str, tx_hash: &str) -> Result<Option<u64>> {
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_getTransactionReceipt",
+            "params": [tx_hash],
+        });
+        
+        let _permit = self.http.permit().await;
+        let response: RpcResponse<TransactionReceipt> = self.http
+            .post(rpc_url)
+            .json(&payload)
+            .send()
+            .await
+            .context("Failed to fetch transaction receipt")?
+            .json()
+            .await
+            .context("Failed to parse transaction receipt")?;
+        
+        response
+            .result
+            .map(|receipt| {
+                u64::from_str_radix(receipt.block_number.trim_start_matches("0x"), 16)
+                    .context("Invalid block number in receipt")
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::risk::ExecutionError;
+    use crate::strategies::types::Side;
+    
+    #[tokio::test]
+    async fn test_over_budget_order_is_rejected_before_submission() {
+        // Unroutable URL: the guard must fail before any request is attempted
+        let client = ClobClient::new("http://127.0.0.1:9".to_string(), 0.10);
+        let order = Order {
+            market_id: "0xabc".to_string(),
+            side: Side::Yes,
+            token: Token::Yes,
+            price: 0.50,
+            size: 400.0, // $200 against a $100 cap
+            order_type: OrderType::FOK,
+            improvement_ticks: 0,
+        };
+        
+        let err = client.submit_order(&order, 1000.0).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ExecutionError>(),
+            Some(ExecutionError::OrderExceedsBalanceCap { .. })
+        ));
+    }
+    
+    /// Serves the CLOB order endpoint with `order_body` and the RPC receipt lookup
+    async fn serve_clob(order_body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+        
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap();
+                    let request = String::from_utf8_lossy(&buf[..n]);
+                    let body = if request.contains("eth_getTransactionReceipt") {
+                        r#"{"jsonrpc":"2.0","id":1,"result":{"blockNumber":"0x3a1b2c"}}"#
+                    } else {
+                        order_body
+                    };
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        url
+    }
+    
+    fn test_order() -> Order {
+        Order {
+            market_id: "0xabc".to_string(),
+            side: Side::Yes,
+            token: Token::Yes,
+            price: 0.50,
+            size: 100.0,
+            order_type: OrderType::FOK,
+            improvement_ticks: 0,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_live_fill_records_tx_hash_and_block() {
+        use crate::execution::persistence::PositionDatabase;
+        
+        let url = serve_clob(
+            r#"{"success":true,"errorMsg":"","orderID":"0xorder","status":"matched","tradeIDs":["trade-1"],"transactionsHashes":["0xfeed"]}"#,
+        ).await;
+        let client = ClobClient::new(url.clone(), 0.10).with_receipt_rpc(format!("{}/rpc", url));
+        let fill = match client.submit_order(&test_order(), 1000.0).await.unwrap() {
+            FillOutcome::Filled(fill) => fill,
+            other => panic!("expected a fill, got {:?}", other),
+        };
+        
+        let db = PositionDatabase::new(":memory:").unwrap();
+        db.record_fill(&fill).unwrap();
+        let stored = &db.get_fills_for_market("0xabc").unwrap()[0];
+        assert_eq!(stored.tx_hash.as_deref(), Some("0xfeed"));
+        assert_eq!(stored.block_number, Some(0x3a1b2c));
+        assert_eq!(stored.order_id.as_deref(), Some("0xorder"));
+        assert_eq!(stored.fill_id.as_deref(), Some("trade-1"));
+    }
+    
+    #[tokio::test]
+    async fn test_resting_order_is_not_booked_as_a_fill() {
+        let url = serve_clob(
+            r#"{"success":true,"errorMsg":"","orderID":"0xorder","status":"live","transactionsHashes":[]}"#,
+        ).await;
+        let client = ClobClient::new(url, 0.10);
+        
+        let outcome = client.submit_order(&test_order(), 1000.0).await.unwrap();
+        assert!(matches!(outcome, FillOutcome::Rejected(RejectReason::NotFilled)));
+    }
+    
+    #[tokio::test]
+    async fn test_rate_limited_order_is_rejected_not_an_error() {
+        let url = serve_clob(
+            r#"{"success":true,"errorMsg":"","orderID":"0xorder","status":"live","transactionsHashes":[]}"#,
+        ).await;
+        let client = ClobClient::new(url, 0.10).with_rate_limit(1);
+        
+        client.submit_order(&test_order(), 1000.0).await.unwrap();
+        let outcome = client.submit_order(&test_order(), 1000.0).await.unwrap();
+        assert!(matches!(outcome, FillOutcome::Rejected(RejectReason::RateLimited)));
+    }
+}
@@ -4,3 +4,10 @@ pub mod order_manager;
 pub mod risk;
 pub mod simulator;
 pub mod persistence;
+pub mod settlement;
+pub mod recompute;
+pub mod backup;
+pub mod reconcile;
+pub mod rate_limit;
+pub mod store;
+pub mod verify;
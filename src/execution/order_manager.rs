@@ -1 +1,372 @@
-// TODO: Implement order_manager
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+use crate::config::WeatherStrategyConfig;
+use crate::data::types::{BookLevel, Market};
+use crate::execution::types::{Order, OrderType, Token, TradeSide};
+use crate::strategies::types::{OrderIntent, Side, Signal};
+use crate::math::format::{fmt_pct, fmt_price, fmt_usd};
+use tracing::info;
+
+/// Knobs applied when turning a signal into an order
+#[derive(Debug, Clone)]
+pub struct OrderOptions {
+    /// Post resting orders this many ticks below the ask
+    pub price_improvement_ticks: u32,
+    pub tick_size: f64,
+    /// Round share counts down to this increment (None = fractional)
+    pub share_granularity: Option<f64>,
+    /// Skip orders below this many shares (None = no minimum)
+    pub min_order_size: Option<f64>,
+    /// Cap an order at this fraction of the shares resting on its side's asks (None = no cap)
+    pub max_depth_consumption_pct: Option<f64>,
+    /// Ask depth in shares per side when the signal was made (None = no book snapshot)
+    pub yes_depth: Option<f64>,
+    pub no_depth: Option<f64>,
+}
+
+impl OrderOptions {
+    pub fn from_config(config: &WeatherStrategyConfig) -> Self {
+        Self {
+            price_improvement_ticks: config.price_improvement_ticks,
+            tick_size: config.tick_size,
+            share_granularity: None,
+            min_order_size: None,
+            max_depth_consumption_pct: config.max_depth_consumption_pct,
+            yes_depth: None,
+            no_depth: None,
+        }
+    }
+    
+    /// Apply the target market's share increment
+    pub fn with_share_granularity(mut self, granularity: Option<f64>) -> Self {
+        self.share_granularity = granularity;
+        self
+    }
+    
+    /// Apply the target market's price tick, if the venue lists one
+    pub fn with_tick_size(mut self, tick_size: Option<f64>) -> Self {
+        if let Some(tick_size) = tick_size {
+            self.tick_size = tick_size;
+        }
+        self
+    }
+    
+    /// Apply the target market's minimum order size
+    pub fn with_min_order_size(mut self, min_order_size: Option<f64>) -> Self {
+        self.min_order_size = min_order_size;
+        self
+    }
+    
+    /// Apply the target market's book snapshot, if it has one
+    pub fn with_book_depth(mut self, market: Option<&Market>) -> Self {
+        let depth = |levels: &[BookLevel]| (!levels.is_empty()).then(|| levels.iter().map(|l| l.size).sum());
+        self.yes_depth = market.and_then(|m| depth(&m.yes_asks));
+        self.no_depth = market.and_then(|m| depth(&m.no_asks));
+        self
+    }
+}
+
+impl Default for OrderOptions {
+    fn default() -> Self {
+        Self {
+            price_improvement_ticks: 0,
+            tick_size: 0.01,
+            share_granularity: None,
+            min_order_size: None,
+            max_depth_consumption_pct: None,
+            yes_depth: None,
+            no_depth: None,
+        }
+    }
+}
+
+/// Build the order a signal would submit
+/// Returns None for signals without a single side (arb baskets), or when
+/// rounding to the market's share increment leaves nothing to buy
+pub fn build_order(signal: &Signal, options: &OrderOptions) -> Option<Order> {
+    let side = signal.side.clone()?;
+    
+    let token = match side {
+        Side::Yes => Token::Yes,
+        Side::No => Token::No,
+    };
+    
+    // A limit rests (weather, 24h+ horizon); fill-or-kill must fill immediately, capped at the price (arb)
+    let order_type = match signal.intent {
+        OrderIntent::Limit => OrderType::GTC,
+        OrderIntent::FillOrKill => OrderType::FOK,
+    };
+    let limit = signal.entry_price;
+    
+    // Only resting orders can wait for a better price; FOK must take the ask
+    let improvement_ticks = match order_type {
+        OrderType::GTC => options.price_improvement_ticks,
+        OrderType::FOK => 0,
+    };
+    let price = (limit - improvement_ticks as f64 * options.tick_size)
+        .max(options.tick_size);
+    
+    // Signal size is USD, order size is shares
+    let mut size = signal.size / price;
+    let depth = match side {
+        Side::Yes => options.yes_depth,
+        Side::No => options.no_depth,
+    };
+    if let (Some(pct), Some(depth)) = (options.max_depth_consumption_pct, depth) {
+        let cap = depth * pct;
+        if size > cap {
+            info!(
+                "Order for {} clamped from {:.2} to {:.2} shares ({}% of {:.2} on the book)",
+                signal.market_id, size, cap, fmt_pct(pct), depth
+            );
+            size = cap;
+        }
+    }
+    if size <= 0.0 {
+        return None;
+    }
+    if let Some(granularity) = options.share_granularity.filter(|g| *g > 0.0) {
+        // Round down so the order never spends more than the signal sized
+        size = (size / granularity + 1e-9).floor() * granularity;
+        if size < granularity {
+            info!(
+                "Order for {} rounds to zero shares (${} @ ${}, increment {}), skipping",
+                signal.market_id, fmt_usd(signal.size), fmt_price(price), granularity
+            );
+            return None;
+        }
+    }
+    if let Some(min) = options.min_order_size.filter(|min| size < *min) {
+        info!(
+            "Order for {} is {:.2} shares, below the venue minimum of {}, skipping",
+            signal.market_id, size, min
+        );
+        return None;
+    }
+    
+    Some(Order {
+        market_id: signal.market_id.clone(),
+        side,
+        token,
+        price,
+        size,
+        order_type,
+        improvement_ticks,
+    })
+}
+
+/// Exact order parameters for placing a dry-run signal manually
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderPlan {
+    pub market_id: String,
+    pub token_id: Option<String>,
+    pub token: Token,
+    pub side: TradeSide,
+    pub limit_price: f64,
+    pub shares: f64,
+    pub cost_usd: f64,
+    pub order_type: OrderType,
+}
+
+impl OrderPlan {
+    /// Create a buy plan from an order, with the CLOB token id if known
+    pub fn from_order(order: &Order, token_id: Option<String>) -> Self {
+        Self {
+            market_id: order.market_id.clone(),
+            token_id,
+            token: order.token.clone(),
+            side: TradeSide::Buy,
+            limit_price: order.price,
+            shares: order.size,
+            cost_usd: order.size * order.price,
+            order_type: order.order_type.clone(),
+        }
+    }
+    
+    /// Create a plan directly from a signal
+    pub fn from_signal(
+        signal: &Signal,
+        options: &OrderOptions,
+        token_id: Option<String>,
+    ) -> Option<Self> {
+        build_order(signal, options).map(|order| Self::from_order(&order, token_id))
+    }
+    
+    /// Machine-readable plan
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl fmt::Display for OrderPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Market:     {}", self.market_id)?;
+        writeln!(
+            f,
+            "Token id:   {}",
+            self.token_id.as_deref().unwrap_or("(look up on market page)")
+        )?;
+        writeln!(f, "Action:     {:?} {:?}", self.side, self.token)?;
+        writeln!(f, "Limit:      ${}", fmt_price(self.limit_price))?;
+        writeln!(f, "Shares:     {:.2}", self.shares)?;
+        writeln!(f, "Est. cost:  ${}", fmt_usd(self.cost_usd))?;
+        write!(f, "Order type: {:?}", self.order_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::types::Strategy;
+    
+    fn weather_signal() -> Signal {
+        Signal {
+            market_id: "0xabc".to_string(),
+            strategy: Strategy::WeatherEdge,
+            side: Some(Side::No),
+            entry_price: 0.40,
+            intent: OrderIntent::Limit,
+            size: 50.0,
+            edge: Some(0.12),
+            confidence: 0.92,
+            event_key: None,
+            market_volume_24h: None,
+            market_liquidity: None,
+        }
+    }
+    
+    #[test]
+    fn test_signal_serializes_to_order_plan() {
+        let token_id = Some("123456".to_string());
+        let plan = OrderPlan::from_signal(&weather_signal(), &OrderOptions::default(), token_id).unwrap();
+        
+        let json: serde_json::Value = serde_json::from_str(&plan.to_json().unwrap()).unwrap();
+        assert_eq!(json["market_id"], "0xabc");
+        assert_eq!(json["token_id"], "123456");
+        assert_eq!(json["token"], "NO");
+        assert_eq!(json["side"], "BUY");
+        assert_eq!(json["order_type"], "GTC");
+        assert!((json["limit_price"].as_f64().unwrap() - 0.40).abs() < 1e-9);
+        assert!((json["shares"].as_f64().unwrap() - 125.0).abs() < 1e-9);
+        assert!((json["cost_usd"].as_f64().unwrap() - 50.0).abs() < 1e-9);
+        
+        let text = plan.to_string();
+        assert!(text.contains("Buy No"));
+        assert!(text.contains("$0.400"));
+    }
+    
+    #[test]
+    fn test_price_improvement_applies_to_resting_orders_only() {
+        let options = OrderOptions {
+            price_improvement_ticks: 2,
+            ..OrderOptions::default()
+        };
+        
+        let order = build_order(&weather_signal(), &options).unwrap();
+        assert!((order.price - 0.38).abs() < 1e-9);
+        assert!((order.size * order.price - 50.0).abs() < 1e-9);
+        assert_eq!(order.improvement_ticks, 2);
+        
+        let mut arb = weather_signal();
+        arb.strategy = Strategy::SumToOneArb;
+        arb.intent = OrderIntent::FillOrKill;
+        let order = build_order(&arb, &options).unwrap();
+        assert!((order.price - 0.40).abs() < 1e-9);
+        assert_eq!(order.improvement_ticks, 0);
+    }
+    
+    #[test]
+    fn test_order_intent_sets_price_and_order_type() {
+        // Limit: rests as a GTC at the entry price
+        let mut signal = weather_signal();
+        signal.intent = OrderIntent::Limit;
+        let order = build_order(&signal, &OrderOptions::default()).unwrap();
+        assert_eq!(order.order_type, OrderType::GTC);
+        assert!((order.price - 0.40).abs() < 1e-9);
+        
+        // Fill-or-kill: FOK capped at the entry price, never improved
+        signal.intent = OrderIntent::FillOrKill;
+        let options = OrderOptions { price_improvement_ticks: 3, ..OrderOptions::default() };
+        let order = build_order(&signal, &options).unwrap();
+        assert_eq!(order.order_type, OrderType::FOK);
+        assert!((order.price - 0.40).abs() < 1e-9);
+        assert_eq!(order.improvement_ticks, 0);
+    }
+    
+    #[test]
+    fn test_whole_share_market_rounds_size_down() {
+        let options = OrderOptions::default().with_share_granularity(Some(1.0));
+        
+        // $50.50 @ $0.40 = 126.25 shares -> 126 whole shares, $50.40
+        let mut signal = weather_signal();
+        signal.size = 50.50;
+        let plan = OrderPlan::from_signal(&signal, &options, None).unwrap();
+        assert_eq!(plan.shares, 126.0);
+        assert!((plan.cost_usd - 50.40).abs() < 1e-9);
+        
+        // $0.30 @ $0.40 = 0.75 shares -> nothing to buy
+        signal.size = 0.30;
+        assert!(build_order(&signal, &options).is_none());
+        
+        // Fractional markets are untouched
+        assert!((build_order(&signal, &OrderOptions::default()).unwrap().size - 0.75).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_order_below_venue_minimum_is_skipped() {
+        let options = OrderOptions::default().with_min_order_size(Some(5.0));
+        
+        // $50 @ $0.40 = 125 shares
+        assert!(build_order(&weather_signal(), &options).is_some());
+        
+        // $1.60 @ $0.40 = 4 shares
+        let mut signal = weather_signal();
+        signal.size = 1.60;
+        assert!(build_order(&signal, &options).is_none());
+    }
+    
+    #[test]
+    fn test_large_order_clamped_to_depth_consumption_cap() {
+        let mut market: Market = serde_json::from_value(serde_json::json!({
+            "id": "0xabc", "question": "Will NYC temperature exceed 60°F on 2026-02-17?",
+            "end_date": "2026-02-17T00:00:00Z",
+            "yes_price": 0.6, "yes_ask": 0.61, "no_ask": 0.40,
+            "volume_24h": 10000.0, "yes_liquidity": 5000.0, "no_liquidity": 5000.0,
+            "closed": false, "share_granularity": null
+        }))
+        .unwrap();
+        market.apply_depth(
+            vec![BookLevel { price: 0.61, size: 5000.0 }],
+            vec![BookLevel { price: 0.40, size: 150.0 }, BookLevel { price: 0.42, size: 100.0 }],
+        );
+        let options = OrderOptions {
+            max_depth_consumption_pct: Some(0.20),
+            ..OrderOptions::default()
+        }
+        .with_book_depth(Some(&market));
+        
+        // $50 @ $0.40 = 125 NO shares, but 20% of the 250 on the NO asks is 50
+        let order = build_order(&weather_signal(), &options).unwrap();
+        assert!((order.size - 50.0).abs() < 1e-9);
+        
+        // Small orders pass untouched
+        let mut signal = weather_signal();
+        signal.size = 10.0;
+        assert!((build_order(&signal, &options).unwrap().size - 25.0).abs() < 1e-9);
+        
+        // A cap below one whole share leaves nothing to place
+        market.apply_depth(Vec::new(), vec![BookLevel { price: 0.40, size: 4.0 }]);
+        let options = options.with_book_depth(Some(&market)).with_share_granularity(Some(1.0));
+        assert!(build_order(&weather_signal(), &options).is_none());
+    }
+    
+    #[test]
+    fn test_arb_signal_without_side_has_no_plan() {
+        let mut signal = weather_signal();
+        signal.strategy = Strategy::SumToOneArb;
+        signal.side = None;
+        
+        assert!(OrderPlan::from_signal(&signal, &OrderOptions::default(), None).is_none());
+    }
+}
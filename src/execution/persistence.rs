@@ -1,16 +1,74 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection};
-use crate::execution::types::{Position, Fill};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::{params, Connection, OpenFlags};
+use std::time::Duration;
+use tracing::warn;
+use crate::execution::store::PositionReader;
+use crate::execution::types::{DeadLetter, Order, OrderType, Position, Fill, LedgerCause, LedgerEntry, Rejection, Token};
+use crate::math::money::{round_money, sum_money};
 use crate::strategies::types::Side;
+use crate::math::format::fmt_price;
+
+/// DB state key for the highest equity seen, the drawdown reference
+const PEAK_EQUITY_STATE_KEY: &str = "peak_equity";
 
 pub struct PositionDatabase {
     conn: Connection,
 }
 
+/// A database opened read-only, for reporting alongside the live writer
+/// Only `PositionReader` queries are exposed, and SQLite itself rejects writes on the connection
+pub struct ReadOnlyDatabase(PositionDatabase);
+
 impl PositionDatabase {
     pub fn new(db_path: &str) -> Result<Self> {
-        let conn = Connection::open(db_path)?;
+        Self::init(Connection::open(db_path)?)
+    }
+    
+    /// Separate read-only connection to an existing database; waits up to 5s on the writer's locks
+    pub fn open_readonly(db_path: &str) -> Result<ReadOnlyDatabase> {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        Ok(ReadOnlyDatabase(Self { conn }))
+    }
+    
+    /// Open with bounded retries, for databases on slow or briefly locked mounts
+    /// Each attempt waits up to the current backoff for locks, then backs off exponentially
+    pub async fn open_with_retry(db_path: &str, attempts: u32, initial_backoff: Duration) -> Result<Self> {
+        let attempts = attempts.max(1);
+        let mut backoff = initial_backoff;
+        
+        for attempt in 1..=attempts {
+            let result = Connection::open(db_path)
+                .and_then(|conn| conn.busy_timeout(backoff).map(|_| conn))
+                .map_err(anyhow::Error::from)
+                .and_then(Self::init);
+            
+            match result {
+                Ok(db) => return Ok(db),
+                Err(e) if attempt < attempts => {
+                    warn!(
+                        "Database {} unavailable (attempt {}/{}): {}; retrying in {:?}",
+                        db_path, attempt, attempts, e, backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => {
+                    return Err(e.context(format!(
+                        "Failed to open database {} after {} attempts",
+                        db_path, attempts
+                    )));
+                }
+            }
+        }
+        
+        unreachable!("loop returns on the last attempt")
+    }
+    
+    fn init(conn: Connection) -> Result<Self> {
+        // WAL: read-only connections (reports, backups) read a snapshot and never block the bot's writes
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
         
         // Create tables
         conn.execute_batch(
@@ -27,7 +85,11 @@ impl PositionDatabase {
                 opened_at TIMESTAMP NOT NULL,
                 closed_at TIMESTAMP,
                 pnl REAL,
-                status TEXT NOT NULL DEFAULT 'open'
+                status TEXT NOT NULL DEFAULT 'open',
+                event_key TEXT,
+                tags TEXT,
+                notes TEXT,
+                question_hash TEXT
             );
             
             CREATE TABLE IF NOT EXISTS orders (
@@ -62,16 +124,116 @@ impl PositionDatabase {
                 FOREIGN KEY(position_id) REFERENCES positions(id)
             );
             
+            CREATE TABLE IF NOT EXISTS intents (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                intent_key TEXT NOT NULL UNIQUE,
+                market_id TEXT NOT NULL,
+                side TEXT NOT NULL,
+                token TEXT NOT NULL,
+                price REAL NOT NULL,
+                size REAL NOT NULL,
+                created_at TIMESTAMP NOT NULL,
+                resolved_at TIMESTAMP,
+                status TEXT NOT NULL DEFAULT 'pending'
+            );
+            
+            CREATE TABLE IF NOT EXISTS dead_letters (
+                market_id TEXT PRIMARY KEY,
+                question TEXT NOT NULL,
+                error TEXT NOT NULL,
+                first_seen TIMESTAMP NOT NULL,
+                last_seen TIMESTAMP NOT NULL,
+                occurrences INTEGER NOT NULL DEFAULT 1
+            );
+            
+            CREATE TABLE IF NOT EXISTS rejections (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                market_id TEXT NOT NULL,
+                strategy TEXT NOT NULL,
+                side TEXT,
+                entry_price REAL,
+                size REAL,
+                edge REAL,
+                reason TEXT NOT NULL,
+                detail TEXT,
+                rejected_at TIMESTAMP NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS fills (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                market_id TEXT NOT NULL,
+                token TEXT,
+                size REAL NOT NULL,
+                price REAL NOT NULL,
+                slippage REAL NOT NULL,
+                filled_at TIMESTAMP NOT NULL,
+                tx_hash TEXT,
+                block_number INTEGER,
+                order_id TEXT,
+                fill_id TEXT
+            );
+            
+            CREATE TABLE IF NOT EXISTS fill_discrepancies (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                market_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                detail TEXT NOT NULL,
+                recorded_at TIMESTAMP NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS ledger (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                cause TEXT NOT NULL,
+                market_id TEXT,
+                position_id INTEGER,
+                amount REAL NOT NULL,
+                balance_after REAL NOT NULL,
+                recorded_at TIMESTAMP NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS bot_state (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TIMESTAMP NOT NULL
+            );
+            
             CREATE INDEX IF NOT EXISTS idx_positions_status ON positions(status);
             CREATE INDEX IF NOT EXISTS idx_positions_market_id ON positions(market_id);
             CREATE INDEX IF NOT EXISTS idx_positions_opened_at ON positions(opened_at);
             CREATE INDEX IF NOT EXISTS idx_orders_status ON orders(status);
+            CREATE INDEX IF NOT EXISTS idx_intents_status ON intents(status);
+            CREATE INDEX IF NOT EXISTS idx_rejections_rejected_at ON rejections(rejected_at);
+            CREATE INDEX IF NOT EXISTS idx_fills_market_id ON fills(market_id);
             "#
         )?;
         
+        // Migrations for databases created before a column existed
+        ensure_column(&conn, "positions", "event_key", "TEXT")?;
+        ensure_column(&conn, "positions", "tags", "TEXT")?;
+        ensure_column(&conn, "positions", "notes", "TEXT")?;
+        ensure_column(&conn, "positions", "question_hash", "TEXT")?;
+        ensure_column(&conn, "fills", "token", "TEXT")?;
+        ensure_column(&conn, "fills", "tx_hash", "TEXT")?;
+        ensure_column(&conn, "fills", "block_number", "INTEGER")?;
+        ensure_column(&conn, "fills", "fill_id", "TEXT")?;
+        ensure_column(&conn, "fills", "order_id", "TEXT")?;
+        ensure_column(&conn, "ledger", "position_id", "INTEGER")?;
+        // Fills were once unique by fill_id alone, which merged partial fills of one order
+        conn.execute_batch(
+            "DROP INDEX IF EXISTS idx_fills_fill_id;
+             CREATE UNIQUE INDEX IF NOT EXISTS idx_fills_order_fill ON fills(order_id, fill_id);",
+        )?;
+        
         Ok(Self { conn })
     }
     
+    /// Write a consistent copy of the whole database to `path`, which must not exist yet
+    /// Safe while the bot is running: in WAL mode the copy reads a snapshot and writers carry on
+    pub fn backup_to(&self, path: &str) -> Result<()> {
+        self.conn.execute("VACUUM INTO ?1", params![path])?;
+        Ok(())
+    }
+    
     /// Insert new position
     pub fn insert_position(&self, pos: &Position) -> Result<i64> {
         let side_str = pos.side.as_ref().map(|s| match s {
@@ -80,18 +242,22 @@ impl PositionDatabase {
         });
         
         self.conn.execute(
-            "INSERT INTO positions (market_id, strategy, side, yes_shares, no_shares, entry_price, cost, opened_at, status)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT INTO positions (market_id, strategy, side, yes_shares, no_shares, entry_price, cost, opened_at, status, event_key, tags, notes, question_hash)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 pos.market_id,
                 pos.strategy,
                 side_str,
                 pos.yes_shares,
                 pos.no_shares,
-                pos.entry_price,
-                pos.cost,
+                round_money(pos.entry_price),
+                round_money(pos.cost),
                 pos.opened_at.to_rfc3339(),
                 pos.status,
+                pos.event_key,
+                tags_to_column(&pos.tags),
+                pos.notes,
+                pos.question_hash,
             ],
         )?;
         
@@ -100,44 +266,66 @@ impl PositionDatabase {
     
     /// Get all open positions
     pub fn get_open_positions(&self) -> Result<Vec<Position>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, market_id, strategy, side, yes_shares, no_shares, entry_price, cost, opened_at, closed_at, pnl, status
-             FROM positions
-             WHERE status = 'open'"
-        )?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM positions WHERE status = 'open'",
+            POSITION_COLUMNS
+        ))?;
         
-        let positions = stmt.query_map([], |row| {
-            let side_str: Option<String> = row.get(3)?;
-            let side = side_str.map(|s| if s == "YES" { Side::Yes } else { Side::No });
-            
-            let opened_at_str: String = row.get(8)?;
-            let opened_at = DateTime::parse_from_rfc3339(&opened_at_str)
-                .unwrap()
-                .with_timezone(&Utc);
-            
-            let closed_at: Option<String> = row.get(9)?;
-            let closed_at = closed_at.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
-            
-            Ok(Position {
-                id: Some(row.get(0)?),
-                market_id: row.get(1)?,
-                strategy: row.get(2)?,
-                side,
-                yes_shares: row.get(4)?,
-                no_shares: row.get(5)?,
-                entry_price: row.get(6)?,
-                cost: row.get(7)?,
-                opened_at,
-                closed_at,
-                pnl: row.get(10)?,
-                status: row.get(11)?,
-            })
-        })?;
-
+        let positions = stmt.query_map([], position_from_row)?;
         positions.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
     }
     
+    /// Get every closed position, oldest first
+    pub fn get_closed_positions(&self) -> Result<Vec<Position>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM positions WHERE status = 'closed' ORDER BY id",
+            POSITION_COLUMNS
+        ))?;
+        
+        let positions = stmt.query_map([], position_from_row)?;
+        positions.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Get positions for one strategy, optionally filtered by status
+    pub fn get_positions_by_strategy(
+        &self,
+        strategy: &str,
+        status: Option<&str>,
+    ) -> Result<Vec<Position>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM positions
+             WHERE strategy = ?1
+             AND (?2 IS NULL OR status = ?2)
+             ORDER BY opened_at",
+            POSITION_COLUMNS
+        ))?;
+        
+        let positions = stmt.query_map(params![strategy, status], position_from_row)?;
+        positions.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Get positions carrying exactly `tag` (case-sensitive, no wildcards), oldest first
+    pub fn get_positions_by_tag(&self, tag: &str) -> Result<Vec<Position>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM positions
+             WHERE instr(',' || tags || ',', ',' || ?1 || ',') > 0
+             ORDER BY opened_at",
+            POSITION_COLUMNS
+        ))?;
+        
+        let positions = stmt.query_map(params![tag.trim()], position_from_row)?;
+        positions.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Replace a position's tags and notes
+    pub fn update_position_annotations(&self, id: i64, tags: &[String], notes: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE positions SET tags = ?1, notes = ?2 WHERE id = ?3",
+            params![tags_to_column(tags), notes, id],
+        )?;
+        Ok(())
+    }
+    
     /// Count open positions
     pub fn count_open_positions(&self) -> Result<usize> {
         let count: usize = self.conn.query_row(
@@ -163,47 +351,92 @@ impl PositionDatabase {
         Ok(count)
     }
     
+    /// Count open positions on the same underlying event (correlation check)
+    pub fn count_open_positions_for_event(&self, event_key: &str) -> Result<usize> {
+        let count: usize = self.conn.query_row(
+            "SELECT COUNT(*) FROM positions
+             WHERE event_key = ?1
+             AND status = 'open'",
+            params![event_key],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+    
     /// Count trades today
     pub fn count_trades_today(&self) -> Result<usize> {
-        let today = Utc::now().format("%Y-%m-%d").to_string();
-        
+        self.count_trades_on(Utc::now().date_naive())
+    }
+    
+    /// Count trades opened on a given UTC date
+    pub fn count_trades_on(&self, date: NaiveDate) -> Result<usize> {
         let count: usize = self.conn.query_row(
             "SELECT COUNT(*) FROM positions
              WHERE DATE(opened_at) = ?1",
-            params![today],
+            params![date.format("%Y-%m-%d").to_string()],
             |row| row.get(0),
         )?;
         Ok(count)
     }
     
+    /// Realized P&L of each position closed on a given UTC date
+    pub fn get_closed_pnls_on(&self, date: NaiveDate) -> Result<Vec<f64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT pnl FROM positions
+             WHERE DATE(closed_at) = ?1
+             AND pnl IS NOT NULL"
+        )?;
+        
+        let pnls = stmt.query_map(params![date.format("%Y-%m-%d").to_string()], |row| row.get(0))?;
+        pnls.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Losing trades in a row, counting back from the most recently closed position
+    pub fn consecutive_losses(&self) -> Result<u32> {
+        let mut stmt = self.conn.prepare(
+            "SELECT pnl FROM positions
+             WHERE closed_at IS NOT NULL
+             AND pnl IS NOT NULL
+             ORDER BY closed_at DESC, id DESC"
+        )?;
+        
+        let mut streak = 0;
+        let mut pnls = stmt.query_map([], |row| row.get::<_, f64>(0))?;
+        while let Some(pnl) = pnls.next().transpose()? {
+            if pnl >= 0.0 {
+                break;
+            }
+            streak += 1;
+        }
+        Ok(streak)
+    }
+    
     /// Get daily P&L
     pub fn get_daily_pnl(&self) -> Result<f64> {
         let today = Utc::now().format("%Y-%m-%d").to_string();
         
-        let pnl: Option<f64> = self.conn.query_row(
-            "SELECT SUM(COALESCE(pnl, 0)) FROM positions
-             WHERE DATE(opened_at) = ?1",
-            params![today],
-            |row| row.get(0),
+        // Summed in fixed point rather than SQL floating point
+        let mut stmt = self.conn.prepare(
+            "SELECT COALESCE(pnl, 0) FROM positions
+             WHERE DATE(opened_at) = ?1"
         )?;
+        let pnls = stmt
+            .query_map(params![today], |row| row.get::<_, f64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
         
-        Ok(pnl.unwrap_or(0.0))
+        Ok(sum_money(pnls))
     }
     
-    /// Get peak equity
-    pub fn get_peak_equity(&self) -> Result<f64> {
-        // Calculate cumulative P&L and find peak
-        let peak: Option<f64> = self.conn.query_row(
-            "SELECT MAX(cumulative_pnl) FROM (
-                SELECT SUM(COALESCE(pnl, 0)) OVER (ORDER BY opened_at) as cumulative_pnl
-                FROM positions
-                WHERE pnl IS NOT NULL
-            )",
-            [],
-            |row| row.get(0),
-        )?;
-        
-        Ok(peak.unwrap_or(0.0))
+    /// Raise the stored equity high-water mark to `equity` if that's higher; returns the mark
+    pub fn update_peak_equity(&self, equity: f64) -> Result<f64> {
+        let stored = self.get_state(PEAK_EQUITY_STATE_KEY)?.and_then(|v| v.parse::<f64>().ok());
+        match stored {
+            Some(peak) if peak >= equity => Ok(peak),
+            _ => {
+                self.set_state(PEAK_EQUITY_STATE_KEY, &equity.to_string())?;
+                Ok(equity)
+            }
+        }
     }
     
     /// Update position status
@@ -212,7 +445,30 @@ impl PositionDatabase {
             "UPDATE positions
              SET status = ?1, closed_at = ?2, pnl = ?3
              WHERE id = ?4",
-            params![status, Utc::now().to_rfc3339(), pnl, id],
+            params![status, Utc::now().to_rfc3339(), pnl.map(round_money), id],
+        )?;
+        Ok(())
+    }
+    
+    /// Most recent losing close on this market or (if given) any market in the same event
+    pub fn last_loss_at(&self, market_id: &str, event_key: Option<&str>) -> Result<Option<DateTime<Utc>>> {
+        let closed_at: Option<String> = self.conn.query_row(
+            "SELECT MAX(closed_at) FROM positions
+             WHERE status = 'closed' AND pnl < 0
+             AND (market_id = ?1 OR (?2 IS NOT NULL AND event_key = ?2))",
+            params![market_id, event_key],
+            |row| row.get(0),
+        )?;
+        Ok(closed_at
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)))
+    }
+    
+    /// Overwrite a position's P&L, leaving its status and close time alone
+    pub fn update_position_pnl(&self, id: i64, pnl: f64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE positions SET pnl = ?1 WHERE id = ?2",
+            params![round_money(pnl), id],
         )?;
         Ok(())
     }
@@ -226,6 +482,57 @@ impl PositionDatabase {
         Ok(())
     }
     
+    /// Shrink a position after a partial exit, with its cost basis net of the proceeds
+    pub fn reduce_position(&self, id: i64, yes_shares: f64, no_shares: f64, cost: f64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE positions SET yes_shares = ?1, no_shares = ?2, cost = ?3 WHERE id = ?4",
+            params![yes_shares, no_shares, round_money(cost), id],
+        )?;
+        Ok(())
+    }
+    
+    /// Record a submitted order as pending until it fills
+    pub fn insert_order(&self, order: &Order, position_id: Option<i64>) -> Result<i64> {
+        let side_str = match order.side {
+            Side::Yes => "YES",
+            Side::No => "NO",
+        };
+        let token_str = match order.token {
+            Token::Yes => "YES",
+            Token::No => "NO",
+        };
+        let type_str = match order.order_type {
+            OrderType::FOK => "FOK",
+            OrderType::GTC => "GTC",
+        };
+        
+        self.conn.execute(
+            "INSERT INTO orders (position_id, market_id, side, token, price, size, order_type, submitted_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                position_id,
+                order.market_id,
+                side_str,
+                token_str,
+                order.price,
+                order.size,
+                type_str,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+    
+    /// Count orders submitted but not yet filled
+    pub fn count_pending_orders(&self) -> Result<usize> {
+        let count: usize = self.conn.query_row(
+            "SELECT COUNT(*) FROM orders WHERE status = 'pending'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+    
     /// Get pending orders
     pub fn get_pending_orders(&self) -> Result<Vec<(i64, String)>> {
         let mut stmt = self.conn.prepare(
@@ -236,15 +543,344 @@ impl PositionDatabase {
         orders.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
     }
     
-    /// Mark order as filled
-    pub fn mark_order_filled(&self, id: i64) -> Result<()> {
+    /// Mark order as filled, linking the position it opened
+    pub fn mark_order_filled(&self, id: i64, position_id: Option<i64>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE orders SET status = 'filled', filled_at = ?1, position_id = COALESCE(?2, position_id) WHERE id = ?3",
+            params![Utc::now().to_rfc3339(), position_id, id],
+        )?;
+        Ok(())
+    }
+    
+    /// Mark order as rejected or failed; it no longer counts as pending
+    pub fn mark_order_rejected(&self, id: i64) -> Result<()> {
+        self.conn.execute("UPDATE orders SET status = 'rejected' WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+    
+    /// Expire every pending order, returning how many there were
+    pub fn expire_pending_orders(&self) -> Result<usize> {
+        let expired = self.conn.execute("UPDATE orders SET status = 'expired' WHERE status = 'pending'", [])?;
+        Ok(expired)
+    }
+    
+    /// Record an order intent before it is submitted (write-ahead)
+    pub fn record_intent(&self, intent_key: &str, order: &Order) -> Result<i64> {
+        let side_str = match order.side {
+            Side::Yes => "YES",
+            Side::No => "NO",
+        };
+        let token_str = match order.token {
+            Token::Yes => "YES",
+            Token::No => "NO",
+        };
+        
+        self.conn.execute(
+            "INSERT INTO intents (intent_key, market_id, side, token, price, size, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                intent_key,
+                order.market_id,
+                side_str,
+                token_str,
+                order.price,
+                order.size,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+    
+    /// Mark an intent as resolved ("filled", "rejected", "failed", "unknown", "flagged")
+    /// "unknown" isn't final: recovery reports it alongside never-resolved ones
+    pub fn resolve_intent(&self, intent_key: &str, status: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE intents SET status = ?1, resolved_at = ?2 WHERE intent_key = ?3",
+            params![status, Utc::now().to_rfc3339(), intent_key],
+        )?;
+        Ok(())
+    }
+    
+    /// Get intents that were recorded but never resolved, or whose submission outcome is unknown
+    pub fn get_pending_intents(&self) -> Result<Vec<PendingIntent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT intent_key, market_id, token, price, size, created_at
+             FROM intents WHERE status IN ('pending', 'unknown') ORDER BY id"
+        )?;
+        
+        let intents = stmt.query_map([], |row| {
+            let created_at: String = row.get(5)?;
+            Ok(PendingIntent {
+                intent_key: row.get(0)?,
+                market_id: row.get(1)?,
+                token: row.get(2)?,
+                price: row.get(3)?,
+                size: row.get(4)?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .unwrap()
+                    .with_timezone(&Utc),
+            })
+        })?;
+        
+        intents.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Record a declined trade
+    pub fn log_rejection(&self, rejection: &Rejection) -> Result<()> {
+        let side_str = rejection.side.as_ref().map(|s| match s {
+            Side::Yes => "YES",
+            Side::No => "NO",
+        });
+        
+        self.conn.execute(
+            "INSERT INTO rejections (market_id, strategy, side, entry_price, size, edge, reason, detail, rejected_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                rejection.market_id,
+                rejection.strategy,
+                side_str,
+                rejection.entry_price,
+                rejection.size,
+                rejection.edge,
+                rejection.reason,
+                rejection.detail,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+    
+    /// Record an unparseable market; repeats bump its count and keep the latest question and error
+    pub fn record_dead_letter(&self, letter: &DeadLetter) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO dead_letters (market_id, question, error, first_seen, last_seen)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(market_id) DO UPDATE SET
+                question = excluded.question,
+                error = excluded.error,
+                last_seen = excluded.last_seen,
+                occurrences = occurrences + 1",
+            params![letter.market_id, letter.question, letter.error, now],
+        )?;
+        Ok(())
+    }
+    
+    /// Unparseable markets with how many times each was seen, most recent first
+    pub fn get_dead_letters(&self) -> Result<Vec<(DeadLetter, usize)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT market_id, question, error, occurrences FROM dead_letters
+             ORDER BY last_seen DESC"
+        )?;
+        let letters = stmt.query_map([], |row| {
+            Ok((
+                DeadLetter {
+                    market_id: row.get(0)?,
+                    question: row.get(1)?,
+                    error: row.get(2)?,
+                },
+                row.get(3)?,
+            ))
+        })?;
+        letters.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Rejection counts by reason since a point in time, most common first
+    pub fn rejection_summary(&self, since: DateTime<Utc>) -> Result<Vec<(String, usize)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT reason, COUNT(*) FROM rejections
+             WHERE rejected_at >= ?1
+             GROUP BY reason
+             ORDER BY COUNT(*) DESC, reason"
+        )?;
+        
+        let rows = stmt.query_map(params![since.to_rfc3339()], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Record an executed fill and its realized slippage
+    /// Returns false, storing nothing, if a fill with the same order_id and fill_id is already recorded
+    pub fn record_fill(&self, fill: &Fill) -> Result<bool> {
+        let inserted = self.conn.execute(
+            "INSERT INTO fills (market_id, token, size, price, slippage, filled_at, tx_hash, block_number, order_id, fill_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(order_id, fill_id) DO NOTHING",
+            params![
+                fill.market_id,
+                fill.token.as_ref().map(token_to_column),
+                fill.size,
+                round_money(fill.price),
+                fill.slippage,
+                fill.timestamp.to_rfc3339(),
+                fill.tx_hash,
+                fill.block_number.map(|b| b as i64),
+                fill.order_id,
+                fill.fill_id,
+            ],
+        )?;
+        
+        if inserted == 0 {
+            warn!("Ignoring duplicate fill {} on {}", fill.fill_id.as_deref().unwrap_or(""), fill.market_id);
+        }
+        Ok(inserted > 0)
+    }
+    
+    /// Fills recorded for a market, oldest first
+    pub fn get_fills_for_market(&self, market_id: &str) -> Result<Vec<Fill>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT market_id, size, price, slippage, filled_at, token, tx_hash, block_number, order_id, fill_id FROM fills
+             WHERE market_id = ?1
+             ORDER BY filled_at, id"
+        )?;
+        
+        let fills = stmt.query_map(params![market_id], |row| {
+            let size: f64 = row.get(1)?;
+            let price: f64 = row.get(2)?;
+            let filled_at: String = row.get(4)?;
+            let token: Option<String> = row.get(5)?;
+            let block_number: Option<i64> = row.get(7)?;
+            Ok(Fill {
+                market_id: row.get(0)?,
+                token: token.as_deref().map(token_from_column),
+                size,
+                price,
+                cost: round_money(size * price),
+                slippage: row.get(3)?,
+                timestamp: DateTime::parse_from_rfc3339(&filled_at)
+                    .unwrap()
+                    .with_timezone(&Utc),
+                tx_hash: row.get(6)?,
+                block_number: block_number.map(|b| b as u64),
+                order_id: row.get(8)?,
+                fill_id: row.get(9)?,
+            })
+        })?;
+        
+        fills.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Settlement proceeds ledgered for a position, None if it never settled
+    /// Rows written before settlements carried a position id count for every position in their market
+    pub fn get_settlement_proceeds(&self, position_id: i64, market_id: &str) -> Result<Option<f64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT amount FROM ledger
+             WHERE cause = 'settlement'
+               AND (position_id = ?1 OR (position_id IS NULL AND market_id = ?2))"
+        )?;
+        
+        let amounts = stmt
+            .query_map(params![position_id, market_id], |row| row.get::<_, f64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(if amounts.is_empty() { None } else { Some(sum_money(amounts)) })
+    }
+    
+    /// Proceeds of partial sales (scale-outs) ledgered against a position
+    pub fn get_sale_proceeds(&self, position_id: i64) -> Result<f64> {
+        let mut stmt = self.conn.prepare(
+            "SELECT amount FROM ledger WHERE cause = 'sale' AND position_id = ?1"
+        )?;
+        
+        let amounts = stmt
+            .query_map(params![position_id], |row| row.get::<_, f64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(sum_money(amounts))
+    }
+    
+    /// Mean realized slippage per market, worst execution first
+    pub fn avg_slippage_by_market(&self) -> Result<Vec<(String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT market_id, AVG(slippage) FROM fills
+             GROUP BY market_id
+             ORDER BY AVG(slippage) DESC, market_id"
+        )?;
+        
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Append a paper-trading balance change
+    pub fn record_ledger_entry(&self, entry: &LedgerEntry) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO ledger (cause, market_id, position_id, amount, balance_after, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.cause.as_str(),
+                entry.market_id,
+                entry.position_id,
+                round_money(entry.amount),
+                round_money(entry.balance_after),
+                entry.timestamp.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+    
+    /// Balance after the last ledger entry, if anything was ever recorded
+    pub fn last_ledger_balance(&self) -> Result<Option<f64>> {
+        let balance = self.conn.query_row(
+            "SELECT balance_after FROM ledger ORDER BY id DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        );
+        
+        match balance {
+            Ok(balance) => Ok(Some(balance)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+    
+    /// Full ledger in the order it was written
+    pub fn get_ledger(&self) -> Result<Vec<LedgerEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT cause, market_id, amount, balance_after, recorded_at, position_id FROM ledger ORDER BY id"
+        )?;
+        
+        let entries = stmt.query_map([], |row| {
+            let cause: String = row.get(0)?;
+            let recorded_at: String = row.get(4)?;
+            Ok(LedgerEntry {
+                timestamp: DateTime::parse_from_rfc3339(&recorded_at)
+                    .unwrap()
+                    .with_timezone(&Utc),
+                cause: LedgerCause::parse(&cause).unwrap_or(LedgerCause::Adjustment),
+                market_id: row.get(1)?,
+                position_id: row.get(5)?,
+                amount: row.get(2)?,
+                balance_after: row.get(3)?,
+            })
+        })?;
+        
+        entries.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Store a small piece of bot state (pause flag, etc.)
+    pub fn set_state(&self, key: &str, value: &str) -> Result<()> {
         self.conn.execute(
-            "UPDATE orders SET status = 'filled', filled_at = ?1 WHERE id = ?2",
-            params![Utc::now().to_rfc3339(), id],
+            "INSERT INTO bot_state (key, value, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![key, value, Utc::now().to_rfc3339()],
         )?;
         Ok(())
     }
     
+    /// Read a piece of bot state
+    pub fn get_state(&self, key: &str) -> Result<Option<String>> {
+        let value = self.conn.query_row(
+            "SELECT value FROM bot_state WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        );
+        
+        match value {
+            Ok(value) => Ok(Some(value)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+    
     /// Log circuit breaker event
     pub fn log_circuit_breaker_event(&self, reason: &str, notes: Option<&str>) -> Result<()> {
         self.conn.execute(
@@ -255,6 +891,83 @@ impl PositionDatabase {
         Ok(())
     }
     
+    /// Close out unreset breaker events with who reset them and why; returns rows updated
+    pub fn record_circuit_breaker_reset(&self, operator: &str, note: &str) -> Result<usize> {
+        let entry = format!("reset by {}: {}", operator, note);
+        let updated = self.conn.execute(
+            "UPDATE circuit_breaker_events
+             SET reset_at = ?1,
+                 notes = CASE WHEN notes IS NULL THEN ?2 ELSE notes || char(10) || ?2 END
+             WHERE reset_at IS NULL",
+            params![Utc::now().to_rfc3339(), entry],
+        )?;
+        Ok(updated)
+    }
+    
+    /// Breaker events not yet reset
+    pub fn count_unreset_circuit_breaker_events(&self) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM circuit_breaker_events WHERE reset_at IS NULL",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+    
+    /// All circuit breaker events, oldest first
+    pub fn get_circuit_breaker_events(&self) -> Result<Vec<CircuitBreakerEvent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT reason, triggered_at, reset_at, notes FROM circuit_breaker_events ORDER BY id"
+        )?;
+        
+        let events = stmt.query_map([], |row| {
+            let triggered_at: String = row.get(1)?;
+            let reset_at: Option<String> = row.get(2)?;
+            Ok(CircuitBreakerEvent {
+                reason: row.get(0)?,
+                triggered_at: DateTime::parse_from_rfc3339(&triggered_at)
+                    .unwrap()
+                    .with_timezone(&Utc),
+                reset_at: reset_at
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                notes: row.get(3)?,
+            })
+        })?;
+        
+        events.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Record a fill that didn't match its order
+    pub fn record_fill_discrepancy(&self, market_id: &str, kind: &str, detail: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO fill_discrepancies (market_id, kind, detail, recorded_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![market_id, kind, detail, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+    
+    /// Discrepancy kinds recorded for a market, oldest first
+    pub fn get_fill_discrepancies(&self, market_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT kind FROM fill_discrepancies WHERE market_id = ?1 ORDER BY id"
+        )?;
+        let kinds = stmt.query_map(params![market_id], |row| row.get(0))?;
+        kinds.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+    }
+    
+    /// Count circuit breaker trips on a given UTC date
+    pub fn count_circuit_breaker_events_on(&self, date: NaiveDate) -> Result<usize> {
+        let count: usize = self.conn.query_row(
+            "SELECT COUNT(*) FROM circuit_breaker_events
+             WHERE DATE(triggered_at) = ?1",
+            params![date.format("%Y-%m-%d").to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+    
     /// Log emergency exit
     pub fn log_emergency_exit(
         &self,
@@ -271,9 +984,116 @@ impl PositionDatabase {
     }
 }
 
+/// Order intent recorded before submission but never resolved
+#[derive(Debug, Clone)]
+pub struct PendingIntent {
+    pub intent_key: String,
+    pub market_id: String,
+    pub token: String,
+    pub price: f64,
+    pub size: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A circuit breaker trip and, once cleared, its manual reset
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerEvent {
+    pub reason: String,
+    pub triggered_at: DateTime<Utc>,
+    pub reset_at: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+}
+
+/// What crash recovery found
+#[derive(Debug, Default)]
+pub struct RecoveryReport {
+    pub open_positions: usize,
+    /// Orders still pending at startup; expired
+    pub pending_orders: usize,
+    /// Intents with no recorded or an unknown outcome; flagged for manual review
+    pub orphaned_intents: Vec<PendingIntent>,
+}
+
+/// Column list matching `position_from_row`
+const POSITION_COLUMNS: &str = "id, market_id, strategy, side, yes_shares, no_shares, entry_price, cost, opened_at, closed_at, pnl, status, event_key, tags, notes, question_hash";
+
+/// Tags are stored comma-separated (commas inside a tag are dropped)
+fn tags_to_column(tags: &[String]) -> Option<String> {
+    let cleaned: Vec<String> = tags
+        .iter()
+        .map(|t| t.replace(',', "").trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    (!cleaned.is_empty()).then(|| cleaned.join(","))
+}
+
+fn tags_from_column(column: Option<String>) -> Vec<String> {
+    column
+        .map(|s| s.split(',').map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn token_to_column(token: &Token) -> &'static str {
+    match token {
+        Token::Yes => "YES",
+        Token::No => "NO",
+    }
+}
+
+fn token_from_column(column: &str) -> Token {
+    if column == "NO" { Token::No } else { Token::Yes }
+}
+
+fn position_from_row(row: &rusqlite::Row) -> rusqlite::Result<Position> {
+    let side_str: Option<String> = row.get(3)?;
+    let side = side_str.map(|s| if s == "YES" { Side::Yes } else { Side::No });
+    
+    let opened_at_str: String = row.get(8)?;
+    let opened_at = DateTime::parse_from_rfc3339(&opened_at_str)
+        .unwrap()
+        .with_timezone(&Utc);
+    
+    let closed_at: Option<String> = row.get(9)?;
+    let closed_at = closed_at.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    
+    Ok(Position {
+        id: Some(row.get(0)?),
+        market_id: row.get(1)?,
+        strategy: row.get(2)?,
+        side,
+        yes_shares: row.get(4)?,
+        no_shares: row.get(5)?,
+        entry_price: row.get(6)?,
+        cost: row.get(7)?,
+        opened_at,
+        closed_at,
+        pnl: row.get(10)?,
+        status: row.get(11)?,
+        event_key: row.get(12)?,
+        tags: tags_from_column(row.get(13)?),
+        notes: row.get(14)?,
+        question_hash: row.get(15)?,
+    })
+}
+
+/// Add a column to an existing table if it's missing
+fn ensure_column(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|name| name.ok())
+        .any(|name| name == column);
+    
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl), [])?;
+    }
+    Ok(())
+}
+
 /// Crash recovery function
-pub async fn recover_from_crash(db: &PositionDatabase) -> Result<()> {
-    use tracing::{info, warn};
+pub async fn recover_from_crash(db: &PositionDatabase) -> Result<RecoveryReport> {
+    use tracing::info;
     
     info!("Performing crash recovery...");
     
@@ -292,12 +1112,299 @@ pub async fn recover_from_crash(db: &PositionDatabase) -> Result<()> {
         );
     }
     
-    // Check for pending orders
-    let pending_orders = db.get_pending_orders()?;
-    info!("Found {} pending orders", pending_orders.len());
+    // Orders only fill in-process (nothing rests at a venue), so a pending one was cut off
+    // mid-submission; expire it so it stops counting against max_open_orders
+    let pending_orders = db.expire_pending_orders()?;
+    if pending_orders > 0 {
+        warn!("Expired {} pending orders left by the previous run", pending_orders);
+    }
     
-    // TODO: Check order status via CLOB API
+    // Intents without an outcome crashed between "decided" and "submitted/recorded"; "unknown"
+    // ones timed out mid-submission. We can't tell whether the exchange saw either, so flag
+    // rather than resubmit.
+    let orphaned_intents = db.get_pending_intents()?;
+    for intent in &orphaned_intents {
+        warn!(
+            "Orphaned order intent {}: market={}, {} {} @ ${} (created {})",
+            intent.intent_key, intent.market_id, intent.size, intent.token, fmt_price(intent.price), intent.created_at
+        );
+        db.resolve_intent(&intent.intent_key, "flagged")?;
+    }
     
     info!("Crash recovery complete");
-    Ok(())
+    Ok(RecoveryReport {
+        open_positions: open_positions.len(),
+        pending_orders,
+        orphaned_intents,
+    })
+}
+
+impl ReadOnlyDatabase {
+    /// Write a consistent copy of the whole database to `path`, which must not exist yet
+    pub fn backup_to(&self, path: &str) -> Result<()> {
+        self.0.backup_to(path)
+    }
+}
+
+impl PositionReader for ReadOnlyDatabase {
+    fn get_open_positions(&self) -> Result<Vec<Position>> {
+        self.0.get_open_positions()
+    }
+    
+    fn count_open_positions(&self) -> Result<usize> {
+        self.0.count_open_positions()
+    }
+    
+    fn count_trades_on(&self, date: NaiveDate) -> Result<usize> {
+        self.0.count_trades_on(date)
+    }
+    
+    fn get_closed_pnls_on(&self, date: NaiveDate) -> Result<Vec<f64>> {
+        self.0.get_closed_pnls_on(date)
+    }
+    
+    fn count_circuit_breaker_events_on(&self, date: NaiveDate) -> Result<usize> {
+        self.0.count_circuit_breaker_events_on(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn position(market_id: &str, strategy: &str) -> Position {
+        Position {
+            strategy: strategy.to_string(),
+            yes_shares: 80.0,
+            entry_price: 0.50,
+            ..Position::fixture(market_id)
+        }
+    }
+    
+    #[test]
+    fn test_tags_and_notes_round_trip_and_migrate() {
+        // Database from before positions had tags/notes
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE positions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                market_id TEXT NOT NULL,
+                strategy TEXT NOT NULL,
+                side TEXT,
+                yes_shares REAL NOT NULL DEFAULT 0.0,
+                no_shares REAL NOT NULL DEFAULT 0.0,
+                entry_price REAL NOT NULL,
+                cost REAL NOT NULL,
+                opened_at TIMESTAMP NOT NULL,
+                closed_at TIMESTAMP,
+                pnl REAL,
+                status TEXT NOT NULL DEFAULT 'open'
+            );
+            INSERT INTO positions (market_id, strategy, side, entry_price, cost, opened_at)
+            VALUES ('0xold', 'weather_edge', 'YES', 0.5, 40.0, '2026-01-01T00:00:00+00:00');",
+        )
+        .unwrap();
+        let db = PositionDatabase::init(conn).unwrap();
+        
+        let old = &db.get_open_positions().unwrap()[0];
+        assert!(old.tags.is_empty());
+        assert_eq!(old.notes, None);
+        
+        let mut tagged = position("0xabc", "weather_edge");
+        tagged.tags = vec!["manual override".to_string(), "high conviction".to_string()];
+        tagged.notes = Some("sized up after NOAA update".to_string());
+        let id = db.insert_position(&tagged).unwrap();
+        db.insert_position(&position("0xdef", "weather_edge")).unwrap();
+        
+        let found = db.get_positions_by_tag("high conviction").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, Some(id));
+        assert_eq!(found[0].tags, tagged.tags);
+        assert_eq!(found[0].notes.as_deref(), Some("sized up after NOAA update"));
+        
+        // Whole tags only, matched literally
+        assert!(db.get_positions_by_tag("high").unwrap().is_empty());
+        assert!(db.get_positions_by_tag("high%").unwrap().is_empty());
+        assert!(db.get_positions_by_tag("high_conviction").unwrap().is_empty());
+        assert!(db.get_positions_by_tag("High Conviction").unwrap().is_empty());
+        
+        db.update_position_annotations(id, &["test".to_string()], None).unwrap();
+        assert!(db.get_positions_by_tag("high conviction").unwrap().is_empty());
+        assert_eq!(db.get_positions_by_tag("test").unwrap()[0].notes, None);
+    }
+    
+    #[tokio::test]
+    async fn test_open_with_retry_waits_out_a_locked_database() {
+        let path = std::env::temp_dir().join(format!("celsius-locked-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let path_str = path.to_string_lossy().into_owned();
+        
+        // Another process holds an exclusive lock for a while
+        let locker = Connection::open(&path).unwrap();
+        locker.execute_batch("BEGIN EXCLUSIVE; CREATE TABLE lock_probe (id INTEGER);").unwrap();
+        let holder = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            locker.execute_batch("COMMIT").unwrap();
+        });
+        
+        // Without retries the schema setup fails on the lock
+        let no_retry = PositionDatabase::open_with_retry(&path_str, 1, Duration::from_millis(10)).await;
+        let err = no_retry.err().unwrap();
+        assert!(format!("{:#}", err).contains("after 1 attempts"));
+        
+        let db = PositionDatabase::open_with_retry(&path_str, 6, Duration::from_millis(20)).await.unwrap();
+        assert_eq!(db.count_open_positions().unwrap(), 0);
+        
+        holder.join().unwrap();
+        drop(db);
+        let _ = std::fs::remove_file(&path);
+    }
+    
+    #[test]
+    fn test_readonly_handle_queries_but_cannot_write() {
+        let path = std::env::temp_dir().join(format!("celsius-readonly-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let path_str = path.to_string_lossy().into_owned();
+        
+        let writer = PositionDatabase::new(&path_str).unwrap();
+        writer.insert_position(&position("0xabc", "weather_edge")).unwrap();
+        
+        // ReadOnlyDatabase only implements PositionReader: insert/update don't exist on it
+        let reader = PositionDatabase::open_readonly(&path_str).unwrap();
+        assert_eq!(reader.count_open_positions().unwrap(), 1);
+        assert_eq!(reader.get_open_positions().unwrap()[0].market_id, "0xabc");
+        
+        // Even the underlying connection refuses writes
+        assert!(reader.0.conn.execute("DELETE FROM positions", []).is_err());
+        
+        // And it sees the writer's later commits
+        writer.insert_position(&position("0xdef", "weather_edge")).unwrap();
+        assert_eq!(reader.count_open_positions().unwrap(), 2);
+        
+        drop((reader, writer));
+        std::fs::remove_file(&path).unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_recovery_surfaces_orphaned_intent() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let order = Order {
+            market_id: "0xabc".to_string(),
+            side: Side::Yes,
+            token: Token::Yes,
+            price: 0.40,
+            size: 100.0,
+            order_type: OrderType::GTC,
+            improvement_ticks: 0,
+        };
+        
+        db.record_intent("completed", &order).unwrap();
+        db.resolve_intent("completed", "filled").unwrap();
+        // Crash before the outcome was written
+        db.record_intent("orphan", &order).unwrap();
+        db.insert_order(&order, None).unwrap();
+        // Submission timed out: may or may not have reached the venue
+        db.record_intent("timed-out", &order).unwrap();
+        db.resolve_intent("timed-out", "unknown").unwrap();
+        
+        let report = recover_from_crash(&db).await.unwrap();
+        assert_eq!(report.pending_orders, 1);
+        assert_eq!(db.count_pending_orders().unwrap(), 0);
+        assert_eq!(report.orphaned_intents.len(), 2);
+        assert_eq!(report.orphaned_intents[0].intent_key, "orphan");
+        assert_eq!(report.orphaned_intents[0].market_id, "0xabc");
+        assert_eq!(report.orphaned_intents[1].intent_key, "timed-out");
+        
+        // Flagged, so a second recovery doesn't report it again
+        assert!(db.get_pending_intents().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_get_positions_by_strategy_and_status() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        
+        db.insert_position(&position("w1", "weather_edge")).unwrap();
+        let closed = db.insert_position(&position("w2", "weather_edge")).unwrap();
+        db.insert_position(&position("a1", "sum_to_one_arb")).unwrap();
+        db.update_position_status(closed, "closed", Some(12.0)).unwrap();
+        
+        let weather = db.get_positions_by_strategy("weather_edge", None).unwrap();
+        assert_eq!(weather.len(), 2);
+        assert!(weather.iter().all(|p| p.strategy == "weather_edge"));
+        
+        let open_weather = db.get_positions_by_strategy("weather_edge", Some("open")).unwrap();
+        assert_eq!(open_weather.len(), 1);
+        assert_eq!(open_weather[0].market_id, "w1");
+        
+        let closed_weather = db.get_positions_by_strategy("weather_edge", Some("closed")).unwrap();
+        assert_eq!(closed_weather.len(), 1);
+        assert_eq!(closed_weather[0].pnl, Some(12.0));
+        
+        let arb = db.get_positions_by_strategy("sum_to_one_arb", Some("open")).unwrap();
+        assert_eq!(arb.len(), 1);
+        assert_eq!(arb[0].market_id, "a1");
+    }
+    
+    #[test]
+    fn test_avg_slippage_by_market() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let fill = |market_id: &str, slippage: f64| Fill {
+            market_id: market_id.to_string(),
+            token: Some(Token::Yes),
+            size: 100.0,
+            price: 0.50 * (1.0 + slippage),
+            cost: 50.0 * (1.0 + slippage),
+            slippage,
+            timestamp: Utc::now(),
+            tx_hash: None,
+            block_number: None,
+            order_id: None,
+            fill_id: None,
+        };
+        
+        db.record_fill(&fill("thin", 0.010)).unwrap();
+        db.record_fill(&fill("thin", 0.030)).unwrap();
+        db.record_fill(&fill("deep", 0.001)).unwrap();
+        db.record_fill(&fill("deep", 0.003)).unwrap();
+        
+        let averages = db.avg_slippage_by_market().unwrap();
+        assert_eq!(averages.len(), 2);
+        assert_eq!(averages[0].0, "thin");
+        assert!((averages[0].1 - 0.020).abs() < 1e-9);
+        assert_eq!(averages[1].0, "deep");
+        assert!((averages[1].1 - 0.002).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_duplicate_fill_id_is_stored_once() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let fill = |order_id: Option<&str>, fill_id: Option<&str>| Fill {
+            market_id: "0xabc".to_string(),
+            token: Some(Token::Yes),
+            size: 100.0,
+            price: 0.50,
+            cost: 50.0,
+            slippage: 0.0,
+            timestamp: Utc::now(),
+            tx_hash: None,
+            block_number: None,
+            order_id: order_id.map(str::to_string),
+            fill_id: fill_id.map(str::to_string),
+        };
+        
+        // The CLOB redelivers the same fill event (same order, same trade)
+        assert!(db.record_fill(&fill(Some("0xorder"), Some("trade-1"))).unwrap());
+        assert!(!db.record_fill(&fill(Some("0xorder"), Some("trade-1"))).unwrap());
+        
+        // A later partial fill of the same order is a fill of its own
+        assert!(db.record_fill(&fill(Some("0xorder"), Some("trade-2"))).unwrap());
+        
+        // Paper fills have no ids and are never merged
+        assert!(db.record_fill(&fill(None, None)).unwrap());
+        assert!(db.record_fill(&fill(None, None)).unwrap());
+        
+        let fills = db.get_fills_for_market("0xabc").unwrap();
+        assert_eq!(fills.len(), 4);
+        assert_eq!(fills.iter().filter(|f| f.order_id.as_deref() == Some("0xorder")).count(), 2);
+    }
 }
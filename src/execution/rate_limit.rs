@@ -0,0 +1,81 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket capping order submissions per minute
+/// Starts full, so up to `per_minute` orders can go out at once before throttling kicks in
+#[derive(Debug)]
+pub struct OrderRateLimiter {
+    per_minute: u32,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl OrderRateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        let per_minute = per_minute.max(1);
+        Self {
+            per_minute,
+            bucket: Mutex::new(Bucket {
+                tokens: per_minute as f64,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+    
+    /// Take a token if one is available; false means the order should be deferred
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+    
+    fn try_acquire_at(&self, now: Instant) -> bool {
+        let capacity = self.per_minute as f64;
+        let mut bucket = self.bucket.lock().unwrap();
+        
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * capacity / 60.0).min(capacity);
+        bucket.refilled_at = now;
+        
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+    
+    /// Time until the next token, for logging
+    pub fn retry_after(&self) -> Duration {
+        let bucket = self.bucket.lock().unwrap();
+        let missing = (1.0 - bucket.tokens).max(0.0);
+        Duration::from_secs_f64(missing * 60.0 / self.per_minute as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_submissions_beyond_rate_are_throttled() {
+        let limiter = OrderRateLimiter::new(6);
+        let start = Instant::now();
+        
+        // A full bucket's worth go straight through, the next is throttled
+        assert!((0..6).all(|_| limiter.try_acquire_at(start)));
+        assert!(!limiter.try_acquire_at(start));
+        
+        // One token refills every 10s at 6/min
+        assert!(!limiter.try_acquire_at(start + Duration::from_secs(9)));
+        assert!(limiter.try_acquire_at(start + Duration::from_secs(10)));
+        assert!(!limiter.try_acquire_at(start + Duration::from_secs(10)));
+        
+        // A long quiet spell refills to capacity, not beyond
+        let later = start + Duration::from_secs(600);
+        assert_eq!((0..10).filter(|_| limiter.try_acquire_at(later)).count(), 6);
+    }
+}
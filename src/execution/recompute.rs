@@ -0,0 +1,135 @@
+use anyhow::Result;
+use crate::execution::persistence::PositionDatabase;
+use crate::math::money::{from_money, to_money};
+use crate::math::format::fmt_usd;
+use tracing::{info, warn};
+
+/// Stored P&L further than this from the recomputed value is reported
+const PNL_TOLERANCE_USD: f64 = 0.01;
+
+/// A closed position whose stored P&L disagrees with its fills and settlement
+#[derive(Debug, Clone, PartialEq)]
+pub struct PnlDiscrepancy {
+    pub position_id: i64,
+    pub market_id: String,
+    pub stored: Option<f64>,
+    pub recomputed: f64,
+}
+
+/// Re-derive each closed position's P&L as settlement and sale proceeds minus the cost of its fills
+/// Positions without recorded fills or a ledgered settlement are skipped, since there is
+/// nothing authoritative to compare against. With `correct`, mismatches are overwritten.
+pub fn recompute_pnl(db: &PositionDatabase, correct: bool) -> Result<Vec<PnlDiscrepancy>> {
+    let mut discrepancies = Vec::new();
+    
+    for position in db.get_closed_positions()? {
+        let Some(id) = position.id else { continue };
+        
+        // Fills belonging to this position: in its market, from when it was opened until it closed
+        let fills: Vec<_> = db
+            .get_fills_for_market(&position.market_id)?
+            .into_iter()
+            .filter(|f| f.timestamp >= position.opened_at)
+            .filter(|f| position.closed_at.is_none_or(|closed| f.timestamp <= closed))
+            .collect();
+        if fills.is_empty() {
+            continue;
+        }
+        let Some(proceeds) = db.get_settlement_proceeds(id, &position.market_id)? else {
+            continue;
+        };
+        
+        let sales = db.get_sale_proceeds(id)?;
+        let cost: rust_decimal::Decimal = fills.iter().map(|f| to_money(f.cost)).sum();
+        let recomputed = from_money(to_money(proceeds) + to_money(sales) - cost);
+        
+        let matches = position.pnl.is_some_and(|pnl| (pnl - recomputed).abs() <= PNL_TOLERANCE_USD);
+        if matches {
+            continue;
+        }
+        
+        warn!(
+            "Position {} ({}): stored P&L {:?}, fills and settlement give ${}",
+            id, position.market_id, position.pnl, fmt_usd(recomputed)
+        );
+        if correct {
+            db.update_position_pnl(id, recomputed)?;
+            info!("Corrected P&L for position {}", id);
+        }
+        
+        discrepancies.push(PnlDiscrepancy {
+            position_id: id,
+            market_id: position.market_id,
+            stored: position.pnl,
+            recomputed,
+        });
+    }
+    
+    Ok(discrepancies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use crate::execution::types::{Fill, LedgerCause, LedgerEntry, Position, Token};
+    
+    #[test]
+    fn test_wrong_stored_pnl_is_recomputed_from_fills() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let opened_at = Utc::now() - Duration::hours(2);
+        
+        // Two fills: 50 @ $0.40 and 50 @ $0.44 = $42 total
+        for (size, price, minutes) in [(50.0, 0.40, 0), (50.0, 0.44, 1)] {
+            db.record_fill(&Fill {
+                market_id: "0xabc".to_string(),
+                token: Some(Token::Yes),
+                size,
+                price,
+                cost: size * price,
+                slippage: 0.0,
+                timestamp: opened_at + Duration::minutes(minutes),
+                tx_hash: None,
+                block_number: None,
+                order_id: None,
+                fill_id: None,
+            })
+            .unwrap();
+        }
+        
+        let id = db
+            .insert_position(&Position {
+                entry_price: 0.42,
+                cost: 42.0,
+                opened_at,
+                ..Position::fixture("0xabc")
+            })
+            .unwrap();
+        
+        // 10 shares scaled out at $0.90, the other 90 won ($90 payout)
+        for (cause, amount) in [(LedgerCause::Sale, 9.0), (LedgerCause::Settlement, 90.0)] {
+            db.record_ledger_entry(&LedgerEntry {
+                timestamp: Utc::now(),
+                cause,
+                market_id: Some("0xabc".to_string()),
+                position_id: Some(id),
+                amount,
+                balance_after: 2057.0,
+            })
+            .unwrap();
+        }
+        // Stored as if only the first fill counted
+        db.update_position_status(id, "closed", Some(80.0)).unwrap();
+        
+        let report = recompute_pnl(&db, false).unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].stored, Some(80.0));
+        assert!((report[0].recomputed - 57.0).abs() < 1e-9);
+        
+        // Report-only left the row alone; correcting fixes it and then nothing is left to report
+        assert_eq!(db.get_closed_positions().unwrap()[0].pnl, Some(80.0));
+        recompute_pnl(&db, true).unwrap();
+        assert_eq!(db.get_closed_positions().unwrap()[0].pnl, Some(57.0));
+        assert!(recompute_pnl(&db, false).unwrap().is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use crate::config::RiskConfig;
 use crate::strategies::types::Signal;
 use crate::execution::persistence::PositionDatabase;
+use crate::math::format::{fmt_pct, fmt_usd};
 use tracing::{error, warn, info};
 
 #[derive(Debug, Clone)]
@@ -21,6 +24,7 @@ impl RiskManager {
         signal: &Signal,
         db: &PositionDatabase,
         current_balance: f64,
+        equity: f64,
     ) -> Result<(), ValidationError> {
         // 1. Capital check
         if signal.size > current_balance {
@@ -33,6 +37,20 @@ impl RiskManager {
             return Err(ValidationError::MaxPositionsReached(open_count));
         }
         
+        let strategy = signal.strategy.name();
+        if let Some(&limit) = self.config.max_open_positions_per_strategy.get(strategy) {
+            let strategy_count = db.get_positions_by_strategy(strategy, Some("open"))?.len();
+            if strategy_count >= limit {
+                return Err(ValidationError::StrategyPositionLimitReached(strategy.to_string(), strategy_count));
+            }
+        }
+        
+        // 2b. Resting orders (a burst of GTC orders can pile up before any fill)
+        let pending_orders = db.count_pending_orders()?;
+        if pending_orders >= self.config.max_open_orders {
+            return Err(ValidationError::MaxOpenOrdersReached(pending_orders));
+        }
+        
         // 3. Daily trades
         let today_trades = db.count_trades_today()?;
         if today_trades >= self.config.max_daily_trades {
@@ -45,9 +63,9 @@ impl RiskManager {
             return Err(ValidationError::DailyLossLimitHit(daily_pnl));
         }
         
-        // 5. Drawdown check
-        let peak = db.get_peak_equity()?.max(current_balance);
-        let drawdown = (peak - current_balance) / peak;
+        // 5. Drawdown check, on equity: cash moved into open positions isn't lost
+        let peak = db.update_peak_equity(equity)?;
+        let drawdown = (peak - equity) / peak;
         if drawdown > self.config.max_drawdown_pct {
             return Err(ValidationError::DrawdownExceeded(drawdown));
         }
@@ -64,6 +82,11 @@ impl RiskManager {
             ));
         }
         
+        // 6b. Market activity (same gates as the screen; prices may have moved since)
+        if let (Some(volume), Some(liquidity)) = (signal.market_volume_24h, signal.market_liquidity) {
+            check_market_activity(volume, liquidity, &self.config)?;
+        }
+        
         // 7. Edge validation (flag suspiciously high edges)
         if let Some(edge) = signal.edge {
             if edge > 0.30 {
@@ -73,8 +96,23 @@ impl RiskManager {
         }
         
         // 8. Correlation check (weather markets only)
-        // TODO: Extract city from market_id for correlation check
-        // For now, skip this check
+        // Markets on the same city/day/kind move together, so cap them as one event
+        if let Some(event_key) = &signal.event_key {
+            let event_count = db.count_open_positions_for_event(event_key)?;
+            if event_count >= self.config.max_positions_per_event {
+                return Err(ValidationError::EventLimitReached(event_key.clone(), event_count));
+            }
+        }
+        
+        // 8b. Cooldown after a loss on this market or a sibling (e.g. a persistent model bias)
+        if self.config.loss_cooldown_hours > 0 {
+            let last_loss = db.last_loss_at(&signal.market_id, signal.event_key.as_deref())?;
+            check_loss_cooldown(
+                last_loss,
+                chrono::Duration::hours(self.config.loss_cooldown_hours as i64),
+                chrono::Utc::now(),
+            )?;
+        }
         
         // 9. Claude AI validation would go here
         // (implemented separately in strategy layer)
@@ -93,6 +131,12 @@ pub enum ValidationError {
     #[error("Max positions reached: {0}")]
     MaxPositionsReached(usize),
     
+    #[error("Max positions reached for {0}: {1}")]
+    StrategyPositionLimitReached(String, usize),
+    
+    #[error("Max open orders reached: {0}")]
+    MaxOpenOrdersReached(usize),
+    
     #[error("Daily trades exceeded: {0}")]
     DailyTradesExceeded(usize),
     
@@ -114,6 +158,18 @@ pub enum ValidationError {
     #[error("Correlation limit exceeded")]
     CorrelationLimitExceeded,
     
+    #[error("Event limit reached for {0}: {1} open")]
+    EventLimitReached(String, usize),
+    
+    #[error("24h volume too low: ${0:.0} < ${1:.0}")]
+    VolumeTooLow(f64, f64),
+    
+    #[error("Liquidity too low: ${0:.0} < ${1:.0}")]
+    LiquidityTooLow(f64, f64),
+    
+    #[error("Loss cooldown: {0}m left")]
+    LossCooldown(i64),
+    
     #[error("Claude AI rejected signal")]
     ClaudeRejected,
 
@@ -121,11 +177,90 @@ pub enum ValidationError {
     DatabaseError(#[from] anyhow::Error),
 }
 
+impl ValidationError {
+    /// Stable code for grouping rejections
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationError::InsufficientBalance(..) => "insufficient_balance",
+            ValidationError::MaxPositionsReached(_) => "max_positions",
+            ValidationError::StrategyPositionLimitReached(..) => "max_strategy_positions",
+            ValidationError::MaxOpenOrdersReached(_) => "max_open_orders",
+            ValidationError::DailyTradesExceeded(_) => "daily_trades",
+            ValidationError::DailyLossLimitHit(_) => "daily_loss",
+            ValidationError::DrawdownExceeded(_) => "drawdown",
+            ValidationError::PositionTooLarge(_) => "position_too_large",
+            ValidationError::PositionExceedsPercentage(..) => "position_pct",
+            ValidationError::EdgeTooGoodToBeTrue(_) => "edge_too_good",
+            ValidationError::CorrelationLimitExceeded => "correlation",
+            ValidationError::EventLimitReached(..) => "event_limit",
+            ValidationError::VolumeTooLow(..) => "volume_too_low",
+            ValidationError::LiquidityTooLow(..) => "liquidity_too_low",
+            ValidationError::LossCooldown(_) => "loss_cooldown",
+            ValidationError::ClaudeRejected => "claude_rejected",
+            ValidationError::DatabaseError(_) => "database_error",
+        }
+    }
+}
+
+/// Errors raised by the execution backends themselves
+#[derive(Debug, thiserror::Error)]
+pub enum ExecutionError {
+    #[error("Order cost ${cost:.2} exceeds balance cap ${cap:.2}")]
+    OrderExceedsBalanceCap { cost: f64, cap: f64 },
+}
+
+/// Refuse an order whose cost exceeds a fraction of the available balance
+/// Independent of `validate_trade` so a sizing bug can't reach the exchange
+pub fn check_order_balance_cap(
+    cost: f64,
+    balance: f64,
+    max_fraction: f64,
+) -> Result<(), ExecutionError> {
+    let cap = balance * max_fraction;
+    if cost > cap {
+        error!("Order rejected by balance cap: ${} > ${}", fmt_usd(cost), fmt_usd(cap));
+        return Err(ExecutionError::OrderExceedsBalanceCap { cost, cap });
+    }
+    Ok(())
+}
+
+/// Volume and liquidity gates, shared by the market screen and `validate_trade`
+/// Volume says the market trades; liquidity says we can get in and out at size
+/// Fail while `now` is within `cooldown` of the last losing close
+pub fn check_loss_cooldown(
+    last_loss: Option<chrono::DateTime<chrono::Utc>>,
+    cooldown: chrono::Duration,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<(), ValidationError> {
+    match last_loss {
+        Some(at) if now < at + cooldown => {
+            Err(ValidationError::LossCooldown((at + cooldown - now).num_minutes()))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn check_market_activity(
+    volume_24h: f64,
+    liquidity: f64,
+    config: &RiskConfig,
+) -> Result<(), ValidationError> {
+    if volume_24h < config.min_volume_24h {
+        return Err(ValidationError::VolumeTooLow(volume_24h, config.min_volume_24h));
+    }
+    if liquidity < config.min_liquidity_usd {
+        return Err(ValidationError::LiquidityTooLow(liquidity, config.min_liquidity_usd));
+    }
+    Ok(())
+}
+
 /// Circuit breaker to stop all trading on critical events
+/// Clones share state: any task can trip it and every task sees it immediately
+#[derive(Debug, Clone)]
 pub struct CircuitBreaker {
-    triggered: bool,
-    reason: Option<CircuitBreakerReason>,
-    trigger_time: Option<SystemTime>,
+    triggered: Arc<AtomicBool>,
+    /// Reason and trip time; the lock also serializes trigger/reset
+    state: Arc<Mutex<Option<(CircuitBreakerReason, SystemTime)>>>,
 }
 
 #[derive(Debug, Clone)]
@@ -136,19 +271,53 @@ pub enum CircuitBreakerReason {
     Latency(Duration),
     ApiErrors(usize),
     LeggedPositionStuck,
+    /// A fill didn't match its order (wrong token, size or price)
+    FillDiscrepancy,
     RpcFailure,
+    /// Balance fell below the configured floor
+    BalanceFloor(f64),
+    /// Open positions and venue holdings differ by this many shares
+    PositionMismatch(f64),
 }
 
 impl std::fmt::Display for CircuitBreakerReason {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            CircuitBreakerReason::DailyLoss(loss) => write!(f, "DailyLoss(${:.2})", loss),
-            CircuitBreakerReason::Drawdown(dd) => write!(f, "Drawdown({:.1}%)", dd * 100.0),
-            CircuitBreakerReason::FillRate(rate) => write!(f, "FillRate({:.1}%)", rate * 100.0),
+            CircuitBreakerReason::DailyLoss(loss) => write!(f, "DailyLoss(${})", fmt_usd(*loss)),
+            CircuitBreakerReason::Drawdown(dd) => write!(f, "Drawdown({}%)", fmt_pct(*dd)),
+            CircuitBreakerReason::FillRate(rate) => write!(f, "FillRate({}%)", fmt_pct(*rate)),
             CircuitBreakerReason::Latency(dur) => write!(f, "Latency({:?})", dur),
             CircuitBreakerReason::ApiErrors(count) => write!(f, "ApiErrors({})", count),
             CircuitBreakerReason::LeggedPositionStuck => write!(f, "LeggedPositionStuck"),
+            CircuitBreakerReason::FillDiscrepancy => write!(f, "FillDiscrepancy"),
             CircuitBreakerReason::RpcFailure => write!(f, "RpcFailure"),
+            CircuitBreakerReason::BalanceFloor(balance) => write!(f, "BalanceFloor(${})", fmt_usd(*balance)),
+            CircuitBreakerReason::PositionMismatch(shares) => write!(f, "PositionMismatch({} shares)", shares),
+        }
+    }
+}
+
+impl CircuitBreakerReason {
+    /// Higher is worse; a more severe reason escalates an existing trip
+    pub fn severity(&self) -> u8 {
+        match self {
+            CircuitBreakerReason::Latency(_)
+            | CircuitBreakerReason::FillRate(_)
+            | CircuitBreakerReason::ApiErrors(_) => 1,
+            CircuitBreakerReason::RpcFailure => 2,
+            CircuitBreakerReason::Drawdown(_) | CircuitBreakerReason::BalanceFloor(_) => 3,
+            CircuitBreakerReason::DailyLoss(_) => 4,
+            CircuitBreakerReason::LeggedPositionStuck
+            | CircuitBreakerReason::FillDiscrepancy
+            | CircuitBreakerReason::PositionMismatch(_) => 5,
+        }
+    }
+    
+    /// Minimum time tripped before a reset is considered
+    pub fn cooldown(&self) -> Duration {
+        match self {
+            CircuitBreakerReason::DailyLoss(_) => Duration::from_secs(86400),
+            _ => Duration::from_secs(3600),
         }
     }
 }
@@ -156,62 +325,89 @@ impl std::fmt::Display for CircuitBreakerReason {
 impl CircuitBreaker {
     pub fn new() -> Self {
         Self {
-            triggered: false,
-            reason: None,
-            trigger_time: None,
+            triggered: Arc::new(AtomicBool::new(false)),
+            state: Arc::new(Mutex::new(None)),
         }
     }
     
     pub fn is_triggered(&self) -> bool {
-        self.triggered
+        self.triggered.load(Ordering::SeqCst)
     }
     
-    pub fn trigger(&mut self, reason: CircuitBreakerReason, db: &PositionDatabase) -> Result<()> {
-        if self.triggered {
-            return Ok(()); // Already triggered
-        }
-        
-        error!("🔴 CIRCUIT BREAKER TRIGGERED: {}", reason);
-        
-        self.triggered = true;
-        self.reason = Some(reason.clone());
-        self.trigger_time = Some(SystemTime::now());
+    /// Reason for the current trip, if any
+    pub fn reason(&self) -> Option<CircuitBreakerReason> {
+        self.state.lock().unwrap().as_ref().map(|(reason, _)| reason.clone())
+    }
+    
+    /// Trip the breaker, or escalate an existing trip to a more severe reason
+    /// Escalation replaces the reason and restarts the cooldown; equal or milder reasons are ignored
+    pub fn trigger(&self, reason: CircuitBreakerReason, db: &PositionDatabase) -> Result<()> {
+        let notes = {
+            let mut state = self.state.lock().unwrap();
+            let notes = match state.as_ref() {
+                Some((current, _)) if reason.severity() <= current.severity() => {
+                    return Ok(()); // Already triggered for something at least as bad
+                }
+                Some((current, _)) => {
+                    error!("🔴 CIRCUIT BREAKER ESCALATED: {} -> {}", current, reason);
+                    Some(format!("escalated from {}", current))
+                }
+                None => {
+                    error!("🔴 CIRCUIT BREAKER TRIGGERED: {}", reason);
+                    None
+                }
+            };
+            
+            *state = Some((reason.clone(), SystemTime::now()));
+            self.triggered.store(true, Ordering::SeqCst);
+            notes
+        };
         
         // Log to database
-        db.log_circuit_breaker_event(&reason.to_string(), None)?;
+        db.log_circuit_breaker_event(&reason.to_string(), notes.as_deref())?;
         
         Ok(())
     }
     
     pub fn can_reset(&self) -> Result<String, String> {
-        if !self.triggered {
+        let state = self.state.lock().unwrap();
+        let Some((reason, trigger_time)) = state.as_ref() else {
             return Ok("Circuit breaker not triggered".to_string());
-        }
+        };
         
-        let elapsed = self.trigger_time
-            .unwrap()
+        let elapsed = trigger_time
             .elapsed()
             .unwrap_or(Duration::from_secs(0));
         
-        match &self.reason {
-            Some(CircuitBreakerReason::DailyLoss(_)) => {
-                if elapsed < Duration::from_secs(86400) {
+        match reason {
+            CircuitBreakerReason::DailyLoss(_) => {
+                if elapsed < reason.cooldown() {
                     Err("Must wait 24h before reset".to_string())
                 } else {
                     Ok("Manual review required".to_string())
                 }
             }
-            Some(CircuitBreakerReason::LeggedPositionStuck) => {
+            CircuitBreakerReason::LeggedPositionStuck => {
                 Err("Manual confirmation required: Position closed via UI?".to_string())
             }
-            Some(CircuitBreakerReason::RpcFailure) => {
+            CircuitBreakerReason::FillDiscrepancy => {
+                Err("Manual review required: check recorded fill discrepancies".to_string())
+            }
+            CircuitBreakerReason::RpcFailure => {
                 Ok("Test both RPCs, require both healthy".to_string())
             }
+            CircuitBreakerReason::BalanceFloor(_) => {
+                Err("Manual review required: top up the balance or lower min_balance_floor_usd".to_string())
+            }
+            CircuitBreakerReason::PositionMismatch(_) => {
+                Err("Manual review required: reconcile open positions with venue holdings".to_string())
+            }
             _ => {
-                if elapsed < Duration::from_secs(3600) {
+                let cooldown = reason.cooldown();
+                if elapsed < cooldown {
                     Err(format!(
                         "Cooldown: {} minutes remaining",
-                        (3600 - elapsed.as_secs()) / 60
+                        (cooldown - elapsed).as_secs() / 60
                     ))
                 } else {
                     Ok("Can reset".to_string())
@@ -220,11 +416,57 @@ impl CircuitBreaker {
         }
     }
     
-    pub fn reset(&mut self) {
-        info!("Circuit breaker reset");
-        self.triggered = false;
-        self.reason = None;
-        self.trigger_time = None;
+    /// Automatic reset by the system, recorded on the open breaker events as such
+    pub fn reset(&self, db: &PositionDatabase) -> Result<()> {
+        let note = {
+            let mut state = self.state.lock().unwrap();
+            let note = match state.take() {
+                Some((reason, _)) => format!("automatic reset after {}", reason),
+                None => "automatic reset".to_string(),
+            };
+            self.triggered.store(false, Ordering::SeqCst);
+            note
+        };
+        info!("Circuit breaker reset (system)");
+        
+        db.record_circuit_breaker_reset("system", &note)?;
+        Ok(())
+    }
+    
+    /// Clear a trip once every breaker event is reset in the database, i.e. an operator reset it
+    /// from another process (`--reset-breaker`); returns whether it was cleared
+    pub fn sync_reset(&self, db: &PositionDatabase) -> Result<bool> {
+        if !self.is_triggered() || db.count_unreset_circuit_breaker_events()? > 0 {
+            return Ok(false);
+        }
+        
+        let mut state = self.state.lock().unwrap();
+        if let Some((reason, _)) = state.take() {
+            warn!("Circuit breaker ({}) reset from the command line", reason);
+        }
+        self.triggered.store(false, Ordering::SeqCst);
+        Ok(true)
+    }
+    
+    /// Manual reset by an operator, recorded on the open breaker events
+    /// This is the confirmation `can_reset` asks for, so it isn't gated on it
+    pub fn reset_with_reason(&self, operator: &str, note: &str, db: &PositionDatabase) -> Result<()> {
+        if operator.trim().is_empty() || note.trim().is_empty() {
+            anyhow::bail!("Manual circuit breaker reset needs an operator and a note");
+        }
+        
+        {
+            let mut state = self.state.lock().unwrap();
+            match state.as_ref() {
+                Some((reason, _)) => warn!("Circuit breaker ({}) reset by {}: {}", reason, operator, note),
+                None => info!("Circuit breaker not triggered; recording reset by {}: {}", operator, note),
+            }
+            *state = None;
+            self.triggered.store(false, Ordering::SeqCst);
+        }
+        
+        db.record_circuit_breaker_reset(operator, note)?;
+        Ok(())
     }
 }
 
@@ -233,3 +475,318 @@ impl Default for CircuitBreaker {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::execution::types::Position;
+    use crate::strategies::types::{OrderIntent, Side, Strategy};
+    use std::collections::HashMap;
+    
+    fn test_config() -> RiskConfig {
+        RiskConfig {
+            max_position_size_usd: 50.0,
+            max_position_pct: 0.10,
+            max_open_positions: 10,
+            max_open_positions_per_strategy: HashMap::new(),
+            max_open_orders: 3,
+            max_daily_trades: 10,
+            max_daily_loss_usd: 50.0,
+            max_drawdown_pct: 0.15,
+            max_positions_per_city_per_day: 1,
+            max_positions_per_event: 1,
+            loss_cooldown_hours: 24,
+            claude_validation_weather: false,
+            claude_validation_arb: false,
+            min_liquidity_usd: 5000.0,
+            min_volume_24h: 5000.0,
+            max_gas_gwei: 100,
+            max_order_balance_fraction: 0.25,
+            verify_fills: true,
+            fill_price_band: 0.02,
+            halt_on_fill_discrepancy: false,
+            max_orders_per_minute: None,
+            min_balance_floor_usd: None,
+            reconcile_halt_shares: 10.0,
+        }
+    }
+    
+    fn signal(market_id: &str, event_key: Option<&str>) -> Signal {
+        Signal {
+            market_id: market_id.to_string(),
+            strategy: Strategy::WeatherEdge,
+            side: Some(Side::Yes),
+            entry_price: 0.55,
+            intent: OrderIntent::Limit,
+            size: 40.0,
+            edge: Some(0.12),
+            confidence: 0.9,
+            event_key: event_key.map(str::to_string),
+            market_volume_24h: None,
+            market_liquidity: None,
+        }
+    }
+    
+    fn open_position(market_id: &str, event_key: Option<&str>) -> Position {
+        Position {
+            yes_shares: 70.0,
+            entry_price: 0.55,
+            cost: 38.5,
+            event_key: event_key.map(str::to_string),
+            ..Position::fixture(market_id)
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_drawdown_measured_on_equity() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let risk = RiskManager::new(test_config());
+        assert!(risk.validate_trade(&signal("a", None), &db, 1000.0, 1000.0).await.is_ok());
+        
+        // Cash spent on positions still marked near entry isn't a drawdown
+        assert!(risk.validate_trade(&signal("a", None), &db, 600.0, 990.0).await.is_ok());
+        
+        // Positions marked down 20% from the peak are
+        let result = risk.validate_trade(&signal("a", None), &db, 600.0, 800.0).await;
+        assert!(matches!(result, Err(ValidationError::DrawdownExceeded(dd)) if (dd - 0.20).abs() < 1e-9));
+    }
+    
+    #[tokio::test]
+    async fn test_second_threshold_on_same_event_hits_limit() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let risk = RiskManager::new(test_config());
+        let nyc_day = "new york:2026-02-17:temperature";
+        
+        // Already holding the 60°F threshold for the NYC day
+        db.insert_position(&open_position("nyc-60f", Some(nyc_day))).unwrap();
+        
+        // The 65°F threshold for the same day is the same bet
+        let result = risk.validate_trade(&signal("nyc-65f", Some(nyc_day)), &db, 1000.0, 1000.0).await;
+        assert!(matches!(result, Err(ValidationError::EventLimitReached(_, 1))));
+        
+        // A different day is a different event
+        let other_day = signal("nyc-60f-next", Some("new york:2026-02-18:temperature"));
+        assert!(risk.validate_trade(&other_day, &db, 1000.0, 1000.0).await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_recent_loss_blocks_market_and_event_until_cooldown_ends() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let risk = RiskManager::new(test_config());
+        let nyc_day = "new york:2026-02-17:temperature";
+        
+        let id = db.insert_position(&open_position("nyc-60f", Some(nyc_day))).unwrap();
+        db.update_position_status(id, "closed", Some(-20.0)).unwrap();
+        
+        // Same market, and a sibling threshold on the same day
+        let result = risk.validate_trade(&signal("nyc-60f", None), &db, 1000.0, 1000.0).await;
+        assert!(matches!(result, Err(ValidationError::LossCooldown(_))));
+        let result = risk.validate_trade(&signal("nyc-65f", Some(nyc_day)), &db, 1000.0, 1000.0).await;
+        assert!(matches!(result, Err(ValidationError::LossCooldown(_))));
+        
+        // Unrelated market is unaffected
+        assert!(risk.validate_trade(&signal("chicago-70f", None), &db, 1000.0, 1000.0).await.is_ok());
+        
+        // Eligible again once the cooldown has passed
+        let last_loss = db.last_loss_at("nyc-60f", None).unwrap();
+        assert!(last_loss.is_some());
+        let cooldown = chrono::Duration::hours(24);
+        let later = last_loss.unwrap() + cooldown + chrono::Duration::minutes(1);
+        assert!(check_loss_cooldown(last_loss, cooldown, later).is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_strategy_at_its_cap_while_another_has_room() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let mut config = test_config();
+        config.max_open_positions_per_strategy = HashMap::from([
+            ("sum_to_one_arb".to_string(), 2),
+            ("weather_edge".to_string(), 5),
+        ]);
+        let risk = RiskManager::new(config);
+        
+        for market_id in ["arb-1", "arb-2"] {
+            let mut position = open_position(market_id, None);
+            position.strategy = "sum_to_one_arb".to_string();
+            db.insert_position(&position).unwrap();
+        }
+        
+        let mut arb = signal("arb-3", None);
+        arb.strategy = Strategy::SumToOneArb;
+        let result = risk.validate_trade(&arb, &db, 1000.0, 1000.0).await;
+        assert!(matches!(result, Err(ValidationError::StrategyPositionLimitReached(ref s, 2)) if s == "sum_to_one_arb"));
+        
+        assert!(risk.validate_trade(&signal("nyc-60f", None), &db, 1000.0, 1000.0).await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_pending_orders_at_limit_block_submission() {
+        use crate::execution::types::{Order, OrderType, Token};
+        
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let risk = RiskManager::new(test_config());
+        let order = |market_id: &str| Order {
+            market_id: market_id.to_string(),
+            side: Side::Yes,
+            token: Token::Yes,
+            price: 0.50,
+            size: 50.0,
+            order_type: OrderType::GTC,
+            improvement_ticks: 1,
+        };
+        
+        // Three resting orders, no positions yet
+        for market_id in ["a", "b", "c"] {
+            db.insert_order(&order(market_id), None).unwrap();
+        }
+        assert_eq!(db.count_open_positions().unwrap(), 0);
+        
+        let result = risk.validate_trade(&signal("d", None), &db, 1000.0, 1000.0).await;
+        assert!(matches!(result, Err(ValidationError::MaxOpenOrdersReached(3))));
+        
+        // One fills and frees a slot
+        let (id, _) = db.get_pending_orders().unwrap()[0].clone();
+        db.mark_order_filled(id, None).unwrap();
+        assert!(risk.validate_trade(&signal("d", None), &db, 1000.0, 1000.0).await.is_ok());
+    }
+    
+    #[test]
+    fn test_more_severe_reason_escalates_trip() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let breaker = CircuitBreaker::new();
+        
+        breaker.trigger(CircuitBreakerReason::Latency(Duration::from_secs(5)), &db).unwrap();
+        assert!(matches!(breaker.reason(), Some(CircuitBreakerReason::Latency(_))));
+        assert!(breaker.can_reset().unwrap_err().starts_with("Cooldown"));
+        
+        // Daily loss while tripped for latency: escalate to the 24h cooldown
+        breaker.trigger(CircuitBreakerReason::DailyLoss(75.0), &db).unwrap();
+        assert!(matches!(breaker.reason(), Some(CircuitBreakerReason::DailyLoss(_))));
+        assert_eq!(breaker.can_reset().unwrap_err(), "Must wait 24h before reset");
+        
+        // A milder reason doesn't downgrade it
+        breaker.trigger(CircuitBreakerReason::ApiErrors(10), &db).unwrap();
+        assert!(matches!(breaker.reason(), Some(CircuitBreakerReason::DailyLoss(_))));
+        
+        assert_eq!(db.count_circuit_breaker_events_on(Utc::now().date_naive()).unwrap(), 2);
+    }
+    
+    #[test]
+    fn test_manual_reset_records_operator_and_note() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let breaker = CircuitBreaker::new();
+        breaker.trigger(CircuitBreakerReason::LeggedPositionStuck, &db).unwrap();
+        
+        assert!(breaker.reset_with_reason("", "closed via UI", &db).is_err());
+        assert!(breaker.is_triggered());
+        
+        breaker.reset_with_reason("alice", "NO leg closed via UI", &db).unwrap();
+        assert!(!breaker.is_triggered());
+        
+        let events = db.get_circuit_breaker_events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].reset_at.is_some());
+        assert_eq!(events[0].notes.as_deref(), Some("reset by alice: NO leg closed via UI"));
+    }
+    
+    #[test]
+    fn test_system_reset_is_recorded() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let breaker = CircuitBreaker::new();
+        breaker.trigger(CircuitBreakerReason::RpcFailure, &db).unwrap();
+        
+        breaker.reset(&db).unwrap();
+        assert!(!breaker.is_triggered());
+        
+        let events = db.get_circuit_breaker_events().unwrap();
+        assert!(events[0].reset_at.is_some());
+        assert_eq!(events[0].notes.as_deref(), Some("reset by system: automatic reset after RpcFailure"));
+    }
+    
+    #[test]
+    fn test_reset_recorded_elsewhere_clears_the_breaker() {
+        let path = std::env::temp_dir().join(format!("celsius-breaker-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = PositionDatabase::new(path.to_str().unwrap()).unwrap();
+        let breaker = CircuitBreaker::new();
+        breaker.trigger(CircuitBreakerReason::PositionMismatch(12.0), &db).unwrap();
+        assert!(!breaker.sync_reset(&db).unwrap());
+        
+        // `--reset-breaker` in another process only touches the database
+        let cli = PositionDatabase::new(path.to_str().unwrap()).unwrap();
+        cli.record_circuit_breaker_reset("alice", "positions reconciled by hand").unwrap();
+        
+        assert!(breaker.sync_reset(&db).unwrap());
+        assert!(!breaker.is_triggered());
+        drop((db, cli));
+        let _ = std::fs::remove_file(&path);
+    }
+    
+    #[test]
+    fn test_breaker_tripped_in_one_task_is_seen_by_another() {
+        let breaker = CircuitBreaker::new();
+        let ready = Arc::new(std::sync::Barrier::new(2));
+        
+        let observer = {
+            let breaker = breaker.clone();
+            let ready = Arc::clone(&ready);
+            std::thread::spawn(move || {
+                assert!(!breaker.is_triggered());
+                ready.wait();
+                // Spin until the other task's trip becomes visible
+                let deadline = std::time::Instant::now() + Duration::from_secs(5);
+                while !breaker.is_triggered() {
+                    assert!(std::time::Instant::now() < deadline, "trip never observed");
+                    std::thread::yield_now();
+                }
+                breaker.reason()
+            })
+        };
+        
+        let tripper = {
+            let breaker = breaker.clone();
+            let ready = Arc::clone(&ready);
+            std::thread::spawn(move || {
+                let db = PositionDatabase::new(":memory:").unwrap();
+                ready.wait();
+                breaker.trigger(CircuitBreakerReason::RpcFailure, &db).unwrap();
+                // A second trip from anywhere is a no-op
+                breaker.trigger(CircuitBreakerReason::ApiErrors(3), &db).unwrap();
+                db.count_circuit_breaker_events_on(Utc::now().date_naive()).unwrap()
+            })
+        };
+        
+        assert_eq!(tripper.join().unwrap(), 1);
+        assert!(matches!(observer.join().unwrap(), Some(CircuitBreakerReason::RpcFailure)));
+        
+        breaker.reset(&PositionDatabase::new(":memory:").unwrap()).unwrap();
+        assert!(!breaker.is_triggered());
+        assert!(breaker.reason().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_volume_and_liquidity_gates_are_independent() {
+        let risk = RiskManager::new(test_config());
+        let db = PositionDatabase::new(":memory:").unwrap();
+        
+        // Busy market with a thin book
+        let mut thin = signal("thin", None);
+        thin.market_volume_24h = Some(50_000.0);
+        thin.market_liquidity = Some(1_000.0);
+        let result = risk.validate_trade(&thin, &db, 1000.0, 1000.0).await;
+        assert!(matches!(result, Err(ValidationError::LiquidityTooLow(l, _)) if l == 1_000.0));
+        
+        // Deep book nobody trades
+        let mut quiet = signal("quiet", None);
+        quiet.market_volume_24h = Some(500.0);
+        quiet.market_liquidity = Some(40_000.0);
+        let result = risk.validate_trade(&quiet, &db, 1000.0, 1000.0).await;
+        assert!(matches!(result, Err(ValidationError::VolumeTooLow(v, _)) if v == 500.0));
+        
+        let mut healthy = signal("healthy", None);
+        healthy.market_volume_24h = Some(50_000.0);
+        healthy.market_liquidity = Some(40_000.0);
+        assert!(risk.validate_trade(&healthy, &db, 1000.0, 1000.0).await.is_ok());
+    }
+}
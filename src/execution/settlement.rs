@@ -0,0 +1,153 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use crate::execution::persistence::PositionDatabase;
+use crate::execution::simulator::PaperTradingSimulator;
+use crate::execution::store::PositionStore;
+use crate::strategies::types::Side;
+use crate::math::format::fmt_usd;
+use tracing::info;
+
+/// A market's reported outcome
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub market_id: String,
+    pub winner: Side,
+    pub resolved_at: DateTime<Utc>,
+}
+
+impl Resolution {
+    /// Past the dispute window, so the outcome can no longer change
+    pub fn is_final(&self, grace: Duration, now: DateTime<Utc>) -> bool {
+        now - self.resolved_at >= grace
+    }
+}
+
+/// Close open positions in markets resolved more than `grace` ago
+/// Markets still inside the window are left open for a later pass.
+/// Returns the ids of the positions settled.
+/// Positions are read from `db` and closed through `store` (the database, or it and a mirror)
+pub fn settle_resolved_positions(
+    db: &PositionDatabase,
+    store: &dyn PositionStore,
+    simulator: &PaperTradingSimulator,
+    resolutions: &[Resolution],
+    grace: Duration,
+    now: DateTime<Utc>,
+) -> Result<Vec<i64>> {
+    let mut settled = Vec::new();
+    
+    for resolution in resolutions {
+        if !resolution.is_final(grace, now) {
+            info!(
+                "Market {} resolved {}m ago, waiting out the {}m grace period",
+                resolution.market_id,
+                (now - resolution.resolved_at).num_minutes(),
+                grace.num_minutes()
+            );
+            continue;
+        }
+        
+        for position in db.get_open_positions()? {
+            if position.market_id != resolution.market_id {
+                continue;
+            }
+            let Some(id) = position.id else { continue };
+            
+            // Winning shares pay $1, losing shares nothing; shares sold earlier were paid at sale
+            let payout = match resolution.winner {
+                Side::Yes => position.yes_shares,
+                Side::No => position.no_shares,
+            };
+            let pnl = payout + db.get_sale_proceeds(id)? - position.cost;
+            
+            store.update_position_status(id, "closed", Some(pnl))?;
+            simulator.settle_position(id, &position, payout);
+            info!(
+                "Settled position {} in {}: {:?} won, P&L ${}",
+                id, position.market_id, resolution.winner, fmt_usd(pnl)
+            );
+            settled.push(id);
+        }
+    }
+    
+    Ok(settled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::execution::types::Position;
+    
+    #[test]
+    fn test_just_resolved_market_waits_for_grace_period() {
+        let config = Config::for_test();
+        let grace = Duration::seconds(config.system.settlement_grace_secs as i64);
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let simulator = PaperTradingSimulator::new(config.paper_trading, 1.0);
+        let balance = simulator.balance();
+        
+        db.insert_position(&Position::fixture("fresh")).unwrap();
+        let old = db.insert_position(&Position::fixture("final")).unwrap();
+        
+        let now = Utc::now();
+        let resolutions = vec![
+            Resolution {
+                market_id: "fresh".to_string(),
+                winner: Side::Yes,
+                resolved_at: now - Duration::minutes(5),
+            },
+            Resolution {
+                market_id: "final".to_string(),
+                winner: Side::Yes,
+                resolved_at: now - grace - Duration::minutes(5),
+            },
+        ];
+        
+        let settled = settle_resolved_positions(&db, &db, &simulator, &resolutions, grace, now).unwrap();
+        assert_eq!(settled, vec![old]);
+        
+        // The preliminary outcome is left alone
+        let open = db.get_open_positions().unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].market_id, "fresh");
+        
+        let closed = db.get_positions_by_strategy("weather_edge", Some("closed")).unwrap();
+        assert_eq!(closed[0].pnl, Some(60.0));
+        assert!((simulator.balance() - (balance + 100.0)).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_two_positions_in_one_market_settle_separately() {
+        use crate::execution::reconcile::VenueHoldings;
+        
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let yes = Position::fixture("0xboth");
+        let no = Position {
+            side: Some(Side::No),
+            yes_shares: 0.0,
+            no_shares: 50.0,
+            entry_price: 0.50,
+            cost: 25.0,
+            ..Position::fixture("0xboth")
+        };
+        let yes_id = db.insert_position(&yes).unwrap();
+        let no_id = db.insert_position(&no).unwrap();
+        let simulator = PaperTradingSimulator::new(Config::for_test().paper_trading, 1.0)
+            .with_open_positions(&[yes.clone(), no.clone()]);
+        
+        // Settling one position leaves the other's shares held
+        simulator.settle_position(no_id, &no, 0.0);
+        let held = simulator.held_shares().unwrap();
+        assert_eq!(held["0xboth"], (100.0, 0.0));
+        simulator.settle_position(yes_id, &yes, 100.0);
+        assert!(simulator.held_shares().unwrap().is_empty());
+        
+        // Each position's proceeds are its own
+        for entry in simulator.take_ledger() {
+            db.record_ledger_entry(&entry).unwrap();
+        }
+        assert_eq!(db.get_settlement_proceeds(yes_id, "0xboth").unwrap(), Some(100.0));
+        assert_eq!(db.get_settlement_proceeds(no_id, "0xboth").unwrap(), Some(0.0));
+    }
+}
@@ -1,78 +1,382 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::Utc;
-use rand::Rng;
-use crate::execution::types::{Order, Fill, Position};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::execution::types::{Order, Fill, FillOutcome, LedgerCause, LedgerEntry, Position, RejectReason};
 use crate::config::PaperTradingConfig;
+use crate::execution::rate_limit::OrderRateLimiter;
+use crate::execution::reconcile::VenueHoldings;
+use crate::execution::risk::check_order_balance_cap;
+use crate::math::money::{from_money, to_money};
+use rust_decimal::Decimal;
+use serde::Deserialize;
 use crate::strategies::types::Side;
+use crate::math::format::{fmt_pct, fmt_price, fmt_usd};
 use tracing::info;
 
+/// Current price of a held token, for marking open positions to market
+pub trait PriceSource {
+    fn price(&self, market_id: &str, side: &Side) -> Option<f64>;
+}
+
+impl<F: Fn(&str, &Side) -> Option<f64>> PriceSource for F {
+    fn price(&self, market_id: &str, side: &Side) -> Option<f64> {
+        self(market_id, side)
+    }
+}
+
+/// Outcome of one real order, for replaying live execution in paper mode
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RecordedFill {
+    pub filled: bool,
+    /// Realized slippage vs the limit (negative = price improvement)
+    #[serde(default)]
+    pub slippage: f64,
+}
+
+/// Load recorded fills from a JSON array, e.g. `[{ "filled": true, "slippage": 0.003 }]`
+pub fn load_recorded_fills(path: &str) -> Result<Vec<RecordedFill>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read recorded fills from {}", path))?;
+    let fills: Vec<RecordedFill> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse recorded fills in {}", path))?;
+    anyhow::ensure!(!fills.is_empty(), "No recorded fills in {}", path);
+    Ok(fills)
+}
+
+/// Shares bought by a simulated fill and not yet closed
+#[derive(Debug, Clone)]
+struct Holding {
+    market_id: String,
+    side: Side,
+    shares: f64,
+    entry_price: f64,
+}
+
+/// Paper trading backend
+/// Internally synchronized so one simulator can be shared across analysis tasks
 pub struct PaperTradingSimulator {
     config: PaperTradingConfig,
-    balance: f64,
+    /// Cash only; deployed capital lives in `holdings`
+    /// Fixed point so thousands of fills don't accumulate rounding error
+    balance: Arc<Mutex<Decimal>>,
+    holdings: Mutex<Vec<Holding>>,
+    /// Every balance change since start, oldest first
+    ledger: Mutex<Vec<LedgerEntry>>,
+    max_order_balance_fraction: f64,
+    rng: Mutex<StdRng>,
+    /// When set, order outcomes are drawn from these instead of fill_rate/slippage_pct
+    recorded_fills: Option<Vec<RecordedFill>>,
+    rate_limiter: Option<OrderRateLimiter>,
 }
 
 impl PaperTradingSimulator {
-    pub fn new(config: PaperTradingConfig) -> Self {
+    pub fn new(config: PaperTradingConfig, max_order_balance_fraction: f64) -> Self {
         let balance = config.initial_balance_usd;
-        info!("Paper trading simulator initialized with ${:.2}", balance);
+        info!("Paper trading simulator initialized with ${}", fmt_usd(balance));
+        
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
         
         Self {
             config,
-            balance,
+            balance: Arc::new(Mutex::new(to_money(balance))),
+            holdings: Mutex::new(Vec::new()),
+            ledger: Mutex::new(vec![LedgerEntry {
+                timestamp: Utc::now(),
+                cause: LedgerCause::Deposit,
+                market_id: None,
+                position_id: None,
+                amount: balance,
+                balance_after: balance,
+            }]),
+            max_order_balance_fraction,
+            rng: Mutex::new(rng),
+            recorded_fills: None,
+            rate_limiter: None,
         }
     }
     
+    /// Replay recorded live fills instead of the parametric model
+    /// Each order draws a recorded outcome uniformly (price improvement ticks are ignored)
+    pub fn with_recorded_fills(mut self, fills: Vec<RecordedFill>) -> Self {
+        info!("Paper fills replay {} recorded outcomes", fills.len());
+        self.recorded_fills = Some(fills).filter(|f| !f.is_empty());
+        self
+    }
+    
+    /// Refuse orders beyond `per_minute` (hedges are exempt: a half-built arb is worse)
+    pub fn with_rate_limit(mut self, per_minute: u32) -> Self {
+        self.rate_limiter = Some(OrderRateLimiter::new(per_minute));
+        self
+    }
+    
+    /// Resume an earlier run's cash (its ledger's last balance) instead of depositing afresh
+    pub fn with_restored_balance(self, balance: f64) -> Self {
+        *self.balance.lock().unwrap() = to_money(balance);
+        self.ledger.lock().unwrap().clear();
+        info!("Paper balance restored from ledger: ${}", fmt_usd(balance));
+        self
+    }
+    
+    /// Hold the shares of positions opened in an earlier run, at entry (cash is untouched)
+    pub fn with_open_positions(self, positions: &[Position]) -> Self {
+        let mut holdings = self.holdings.lock().unwrap();
+        for position in positions {
+            for (side, shares) in [(Side::Yes, position.yes_shares), (Side::No, position.no_shares)] {
+                if shares > 0.0 {
+                    holdings.push(Holding {
+                        market_id: position.market_id.clone(),
+                        side,
+                        shares,
+                        entry_price: position.entry_price,
+                    });
+                }
+            }
+        }
+        drop(holdings);
+        self
+    }
+    
     /// Simulate order execution
-    pub fn execute_order(&mut self, order: &Order) -> Result<Option<Fill>> {
-        // Simulate fill rate (70% by default)
-        let mut rng = rand::thread_rng();
-        let will_fill = rng.gen::<f64>() < self.config.fill_rate;
+    pub async fn execute_order(&self, order: &Order) -> Result<FillOutcome> {
+        if let Some(limiter) = &self.rate_limiter {
+            if !limiter.try_acquire() {
+                info!("Order rate limit reached, next slot in {:?}", limiter.retry_after());
+                return Ok(FillOutcome::Rejected(RejectReason::RateLimited));
+            }
+        }
+        
+        if let Some(recorded) = &self.recorded_fills {
+            return self
+                .simulate_fill(order, |rng| {
+                    let fill = recorded[rng.gen_range(0..recorded.len())];
+                    (fill.filled, fill.slippage)
+                })
+                .await;
+        }
+        
+        let fill_rate = self.modeled_fill_rate(order);
+        let slippage_pct = self.config.slippage_pct;
+        self.simulate_fill(order, |rng| modeled_draw(rng, fill_rate, slippage_pct)).await
+    }
+    
+    /// Simulate an order crossing the spread to complete a legged arb
+    /// Its limit is already the worst acceptable price, so it fills there or not at all
+    pub async fn execute_hedge(&self, order: &Order) -> Result<FillOutcome> {
+        let fill_rate = self.config.hedge_fill_rate;
+        self.simulate_fill(order, |rng| modeled_draw(rng, fill_rate, 0.0)).await
+    }
+    
+    /// Sell a fill's shares back at the modeled slippage below its price; returns the proceeds
+    pub fn unwind(&self, fill: &Fill) -> f64 {
+        let proceeds = fill.size * fill.price * (1.0 - self.config.slippage_pct);
+        self.close_holdings(&fill.market_id, proceeds);
+        info!("Unwound {} shares of {} for ${}", fill.size, fill.market_id, fmt_usd(proceeds));
+        proceeds
+    }
+    
+    /// `draw` decides whether the order fills and at what slippage
+    async fn simulate_fill(
+        &self,
+        order: &Order,
+        draw: impl FnOnce(&mut StdRng) -> (bool, f64),
+    ) -> Result<FillOutcome> {
+        // Last-line defense against sizing bugs
+        let intended_cost = order.size * order.price;
+        check_order_balance_cap(intended_cost, self.balance(), self.max_order_balance_fraction)?;
+        
+        // Simulate exchange round-trip
+        let latency = self.sample_latency();
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+        
+        let (will_fill, slippage) = draw(&mut self.rng.lock().unwrap());
         
         if !will_fill {
             info!("Order not filled (simulated rejection)");
-            return Ok(None);
+            return Ok(FillOutcome::Rejected(RejectReason::NotFilled));
         }
         
-        // Apply simulated slippage
-        let slippage = rng.gen::<f64>() * self.config.slippage_pct;
-        let executed_price = order.price * (1.0 + slippage);
+        if let Some(max) = self.config.max_slippage_pct {
+            if slippage > max {
+                info!("Order rejected: slippage {}% > {}%", fmt_pct(slippage), fmt_pct(max));
+                return Ok(FillOutcome::Rejected(RejectReason::ExcessiveSlippage { slippage, max }));
+            }
+        }
         
-        let cost = order.size * executed_price;
+        let executed_price = order.price * (1.0 + slippage);
+        let cost = to_money(order.size * executed_price);
         
-        // Check balance
-        if cost > self.balance {
-            info!("Insufficient balance for order");
-            return Ok(None);
+        // Check and deduct under one lock so concurrent fills can't both spend the same cash
+        {
+            let mut balance = self.balance.lock().unwrap();
+            if cost > *balance {
+                info!("Insufficient balance for order");
+                return Ok(FillOutcome::Rejected(RejectReason::InsufficientBalance {
+                    cost: from_money(cost),
+                    balance: from_money(*balance),
+                }));
+            }
+            *balance -= cost;
+            
+            // Limit price and slippage as separate entries, so execution cost shows up on its own
+            let at_limit = to_money(order.size * order.price).min(cost);
+            let market_id = Some(order.market_id.as_str());
+            self.record(LedgerCause::Fill, market_id, None, -at_limit, *balance + (cost - at_limit));
+            if cost > at_limit {
+                self.record(LedgerCause::Slippage, market_id, None, at_limit - cost, *balance);
+            }
         }
+        let cost = from_money(cost);
         
-        // Deduct from balance
-        self.balance -= cost;
+        self.holdings.lock().unwrap().push(Holding {
+            market_id: order.market_id.clone(),
+            side: order.side.clone(),
+            shares: order.size,
+            entry_price: executed_price,
+        });
         
         info!(
-            "Order filled: {:?} {} shares @ ${:.3} (slippage: {:.2}%)",
+            "Order filled: {:?} {} shares @ ${} (slippage: {}%)",
             order.token,
             order.size,
-            executed_price,
-            slippage * 100.0
+            fmt_price(executed_price),
+            fmt_pct(slippage)
         );
         
-        Ok(Some(Fill {
+        Ok(FillOutcome::Filled(Fill {
             market_id: order.market_id.clone(),
+            token: Some(order.token.clone()),
             size: order.size,
             price: executed_price,
             cost,
+            slippage,
             timestamp: Utc::now(),
+            tx_hash: None,
+            block_number: None,
+            order_id: None,
+            fill_id: None,
         }))
     }
     
-    /// Get current balance
+    /// Chance an order fills; each tick of price improvement waits for a seller further away
+    pub fn modeled_fill_rate(&self, order: &Order) -> f64 {
+        self.config.fill_rate * self.config.improved_fill_decay.powi(order.improvement_ticks as i32)
+    }
+    
+    /// Draw a latency from the configured range
+    fn sample_latency(&self) -> Duration {
+        if self.config.latency_max_ms == 0 {
+            return Duration::ZERO;
+        }
+        
+        let min = self.config.latency_min_ms;
+        let max = self.config.latency_max_ms.max(min);
+        Duration::from_millis(self.rng.lock().unwrap().gen_range(min..=max))
+    }
+    
+    /// Get current cash balance
     pub fn balance(&self) -> f64 {
-        self.balance
+        from_money(*self.balance.lock().unwrap())
+    }
+    
+    /// Cash plus open holdings marked to market
+    /// Holdings without a current price are carried at entry
+    pub fn equity(&self, prices: &impl PriceSource) -> f64 {
+        let holdings_value: f64 = self.holdings
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|h| {
+                let mark = prices.price(&h.market_id, &h.side).unwrap_or(h.entry_price);
+                h.shares * mark
+            })
+            .sum();
+        
+        self.balance() + holdings_value
     }
     
     /// Add to balance (simulate winnings)
-    pub fn add_to_balance(&mut self, amount: f64) {
-        self.balance += amount;
+    pub fn add_to_balance(&self, amount: f64) {
+        self.credit(LedgerCause::Adjustment, None, None, amount);
+    }
+    
+    /// Drop a market's holdings and credit the proceeds to cash
+    pub fn close_holdings(&self, market_id: &str, proceeds: f64) {
+        self.holdings.lock().unwrap().retain(|h| h.market_id != market_id);
+        self.credit(LedgerCause::Settlement, Some(market_id), None, proceeds);
+    }
+    
+    /// Settle one position: drop only its shares from the market's holdings, so other positions
+    /// in the market keep theirs, and credit the payout against its id
+    pub fn settle_position(&self, position_id: i64, position: &Position, payout: f64) {
+        self.remove_shares(&position.market_id, &Side::Yes, position.yes_shares);
+        self.remove_shares(&position.market_id, &Side::No, position.no_shares);
+        self.credit(LedgerCause::Settlement, Some(&position.market_id), Some(position_id), payout);
+    }
+    
+    /// Sell up to `shares` of one side of a market's holdings at `price`, crediting the proceeds
+    /// of what was actually held; returns them
+    pub fn sell_holdings(&self, position_id: i64, market_id: &str, side: &Side, shares: f64, price: f64) -> f64 {
+        let proceeds = self.remove_shares(market_id, side, shares) * price;
+        self.credit(LedgerCause::Sale, Some(market_id), Some(position_id), proceeds);
+        proceeds
+    }
+    
+    /// Take up to `shares` off one side of a market's holdings; returns how many were held
+    fn remove_shares(&self, market_id: &str, side: &Side, shares: f64) -> f64 {
+        let mut remaining = shares;
+        let mut holdings = self.holdings.lock().unwrap();
+        for holding in holdings.iter_mut().filter(|h| h.market_id == market_id && h.side == *side) {
+            let taken = remaining.min(holding.shares);
+            holding.shares -= taken;
+            remaining -= taken;
+        }
+        holdings.retain(|h| h.shares > 0.0);
+        shares - remaining
+    }
+    
+    /// All balance changes so far, oldest first
+    pub fn ledger(&self) -> Vec<LedgerEntry> {
+        self.ledger.lock().unwrap().clone()
+    }
+    
+    /// Remove and return ledger entries not yet taken (for persisting)
+    pub fn take_ledger(&self) -> Vec<LedgerEntry> {
+        std::mem::take(&mut *self.ledger.lock().unwrap())
+    }
+    
+    /// Change the balance and log why
+    fn credit(&self, cause: LedgerCause, market_id: Option<&str>, position_id: Option<i64>, amount: f64) {
+        let amount = to_money(amount);
+        let mut balance = self.balance.lock().unwrap();
+        *balance += amount;
+        self.record(cause, market_id, position_id, amount, *balance);
+    }
+    
+    fn record(
+        &self,
+        cause: LedgerCause,
+        market_id: Option<&str>,
+        position_id: Option<i64>,
+        amount: Decimal,
+        balance_after: Decimal,
+    ) {
+        self.ledger.lock().unwrap().push(LedgerEntry {
+            timestamp: Utc::now(),
+            cause,
+            market_id: market_id.map(str::to_string),
+            position_id,
+            amount: from_money(amount),
+            balance_after: from_money(balance_after),
+        });
     }
     
     /// Create simulated position from fills
@@ -81,6 +385,7 @@ impl PaperTradingSimulator {
         fill: &Fill,
         side: Side,
         strategy: &str,
+        event_key: Option<String>,
     ) -> Position {
         let (yes_shares, no_shares) = match side {
             Side::Yes => (fill.size, 0.0),
@@ -100,6 +405,321 @@ impl PaperTradingSimulator {
             closed_at: None,
             pnl: None,
             status: "open".to_string(),
+            event_key,
+            tags: Vec::new(),
+            notes: None,
+            question_hash: None,
         }
     }
 }
+
+/// The paper venue: what the simulator believes it holds
+/// It is seeded from the same database, so reconciling against it is a self-check of the
+/// bot's own bookkeeping (a fill recorded twice or not at all), blind to trades made elsewhere
+impl VenueHoldings for PaperTradingSimulator {
+    fn held_shares(&self) -> Result<HashMap<String, (f64, f64)>> {
+        let mut held: HashMap<String, (f64, f64)> = HashMap::new();
+        for holding in self.holdings.lock().unwrap().iter() {
+            let shares = held.entry(holding.market_id.clone()).or_default();
+            match holding.side {
+                Side::Yes => shares.0 += holding.shares,
+                Side::No => shares.1 += holding.shares,
+            }
+        }
+        Ok(held)
+    }
+}
+
+/// Parametric outcome: fills with `fill_rate`, slippage uniform in [0, slippage_pct)
+fn modeled_draw(rng: &mut StdRng, fill_rate: f64, slippage_pct: f64) -> (bool, f64) {
+    let will_fill = rng.gen::<f64>() < fill_rate;
+    (will_fill, rng.gen::<f64>() * slippage_pct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::risk::ExecutionError;
+    use crate::execution::types::{OrderType, Token};
+    use std::time::Instant;
+    
+    fn test_config() -> PaperTradingConfig {
+        PaperTradingConfig {
+            enabled: true,
+            fill_rate: 1.0,
+            slippage_pct: 0.005,
+            max_slippage_pct: None,
+            initial_balance_usd: 2000.0,
+            latency_min_ms: 0,
+            latency_max_ms: 0,
+            seed: Some(42),
+            improved_fill_decay: 0.75,
+            hedge_fill_rate: 1.0,
+            recorded_fills_path: None,
+        }
+    }
+    
+    fn test_order() -> Order {
+        Order {
+            market_id: "0xabc".to_string(),
+            side: Side::Yes,
+            token: Token::Yes,
+            price: 0.60,
+            size: 50.0,
+            order_type: OrderType::GTC,
+            improvement_ticks: 0,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_recorded_fills_replace_modeled_slippage() {
+        let recorded: Vec<RecordedFill> = serde_json::from_str(
+            r#"[{ "filled": true, "slippage": 0.001 }, { "filled": true, "slippage": 0.012 },
+                { "filled": true, "slippage": -0.002 }, { "filled": false }]"#,
+        )
+        .unwrap();
+        let sim = PaperTradingSimulator::new(test_config(), 1.0).with_recorded_fills(recorded);
+        let order = Order { size: 1.0, ..test_order() };
+        
+        let mut slippages = Vec::new();
+        let mut misses = 0;
+        for _ in 0..400 {
+            match sim.execute_order(&order).await.unwrap() {
+                FillOutcome::Filled(fill) => slippages.push(fill.slippage),
+                FillOutcome::Rejected(RejectReason::NotFilled) => misses += 1,
+                FillOutcome::Rejected(reason) => panic!("unexpected rejection {:?}", reason),
+            }
+        }
+        
+        // Only recorded values, in roughly recorded proportions (config's 0.5% uniform model is unused)
+        for recorded in [0.001, 0.012, -0.002] {
+            let count = slippages.iter().filter(|s| **s == recorded).count();
+            assert!((70..=130).contains(&count), "{} drawn {} times", recorded, count);
+        }
+        assert_eq!(slippages.len() + misses, 400);
+        assert!((70..=130).contains(&misses));
+    }
+    
+    #[tokio::test]
+    async fn test_execute_order_waits_for_configured_latency() {
+        let mut config = test_config();
+        config.latency_min_ms = 50;
+        config.latency_max_ms = 80;
+        let sim = PaperTradingSimulator::new(config, 1.0);
+        
+        let start = Instant::now();
+        let outcome = sim.execute_order(&test_order()).await.unwrap();
+        
+        assert!(matches!(outcome, FillOutcome::Filled(_)));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+    
+    #[tokio::test]
+    async fn test_seeded_simulators_are_reproducible() {
+        let a = PaperTradingSimulator::new(test_config(), 1.0);
+        let b = PaperTradingSimulator::new(test_config(), 1.0);
+        
+        let fill_a = a.execute_order(&test_order()).await.unwrap().fill().unwrap();
+        let fill_b = b.execute_order(&test_order()).await.unwrap().fill().unwrap();
+        
+        assert_eq!(fill_a.price, fill_b.price);
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_fills_match_serial_balance() {
+        let mut config = test_config();
+        config.slippage_pct = 0.0;
+        let sim = Arc::new(PaperTradingSimulator::new(config, 1.0));
+        
+        let mut tasks = Vec::new();
+        for _ in 0..20 {
+            let sim = Arc::clone(&sim);
+            tasks.push(tokio::spawn(async move {
+                sim.execute_order(&test_order()).await.unwrap()
+            }));
+        }
+        for task in tasks {
+            assert!(matches!(task.await.unwrap(), FillOutcome::Filled(_)));
+        }
+        
+        // 20 fills x 50 shares x $0.60 = $600, no lost updates
+        assert!((sim.balance() - 1400.0).abs() < 1e-9);
+    }
+    
+    #[tokio::test]
+    async fn test_over_budget_order_is_rejected_by_balance_cap() {
+        // $2,000 balance with a 10% cap -> $200 max per order
+        let sim = PaperTradingSimulator::new(test_config(), 0.10);
+        
+        let mut order = test_order();
+        order.size = 500.0; // 500 shares @ $0.60 = $300
+        
+        let err = sim.execute_order(&order).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ExecutionError>(),
+            Some(ExecutionError::OrderExceedsBalanceCap { .. })
+        ));
+        assert_eq!(sim.balance(), 2000.0);
+    }
+    
+    #[tokio::test]
+    async fn test_improved_price_fills_less_often_but_cheaper() {
+        let mut config = test_config();
+        config.slippage_pct = 0.0;
+        let sim = PaperTradingSimulator::new(config, 1.0);
+        
+        let at_ask = test_order();
+        let mut improved = test_order();
+        improved.price = 0.58;
+        improved.improvement_ticks = 2;
+        
+        assert!(sim.modeled_fill_rate(&improved) < sim.modeled_fill_rate(&at_ask));
+        assert!((sim.modeled_fill_rate(&improved) - 0.5625).abs() < 1e-9);
+        
+        // Seeded, so this settles deterministically within a few attempts
+        let fill = loop {
+            if let FillOutcome::Filled(fill) = sim.execute_order(&improved).await.unwrap() {
+                break fill;
+            }
+        };
+        let base = sim.execute_order(&at_ask).await.unwrap().fill().unwrap();
+        assert!(fill.price < base.price);
+    }
+    
+    #[tokio::test]
+    async fn test_equity_stays_flat_when_opening_a_position() {
+        let mut config = test_config();
+        config.slippage_pct = 0.0;
+        let sim = PaperTradingSimulator::new(config, 1.0);
+        
+        sim.execute_order(&test_order()).await.unwrap().fill().unwrap();
+        
+        // 50 shares @ $0.60 moved $30 from cash into the position
+        assert!((sim.balance() - 1970.0).abs() < 1e-9);
+        let at_entry = |_: &str, _: &Side| Some(0.60);
+        assert!((sim.equity(&at_entry) - 2000.0).abs() < 1e-9);
+        
+        // Marked down to $0.40: $10 unrealized loss
+        let marked_down = |_: &str, _: &Side| Some(0.40);
+        assert!((sim.equity(&marked_down) - 1990.0).abs() < 1e-9);
+        
+        // Closing at $0.40 realizes it
+        sim.close_holdings("0xabc", 50.0 * 0.40);
+        assert!((sim.balance() - 1990.0).abs() < 1e-9);
+        assert!((sim.equity(&marked_down) - 1990.0).abs() < 1e-9);
+    }
+    
+    #[tokio::test]
+    async fn test_ledger_replays_fills_and_settlement_to_balance() {
+        let mut config = test_config();
+        config.slippage_pct = 0.02;
+        let sim = PaperTradingSimulator::new(config, 1.0);
+        
+        sim.execute_order(&test_order()).await.unwrap().fill().unwrap();
+        sim.execute_order(&test_order()).await.unwrap().fill().unwrap();
+        // Won: 100 shares pay out $1 each
+        sim.close_holdings("0xabc", 100.0);
+        
+        let ledger = sim.ledger();
+        let causes: Vec<LedgerCause> = ledger.iter().map(|e| e.cause).collect();
+        assert_eq!(
+            causes,
+            vec![
+                LedgerCause::Deposit,
+                LedgerCause::Fill,
+                LedgerCause::Slippage,
+                LedgerCause::Fill,
+                LedgerCause::Slippage,
+                LedgerCause::Settlement,
+            ]
+        );
+        
+        // Fills are booked at the limit: 50 shares @ $0.60
+        assert!((ledger[1].amount + 30.0).abs() < 1e-9);
+        assert!(ledger[2].amount <= 0.0);
+        
+        // Replaying the amounts reproduces every running balance and the final one
+        let mut running = 0.0;
+        for entry in &ledger {
+            running += entry.amount;
+            assert!((running - entry.balance_after).abs() < 1e-9);
+        }
+        assert!((running - sim.balance()).abs() < 1e-9);
+    }
+    
+    #[tokio::test]
+    async fn test_sale_is_paid_only_for_shares_held() {
+        let mut config = test_config();
+        config.slippage_pct = 0.0;
+        let sim = PaperTradingSimulator::new(config, 1.0);
+        sim.execute_order(&test_order()).await.unwrap().fill().unwrap();
+        let balance = sim.balance();
+        
+        // Nothing held on the NO side
+        assert_eq!(sim.sell_holdings(1, "0xabc", &Side::No, 20.0, 0.50), 0.0);
+        
+        // Asked to sell 80 of the 50 YES shares held: paid for 50
+        let proceeds = sim.sell_holdings(1, "0xabc", &Side::Yes, 80.0, 0.70);
+        assert!((proceeds - 35.0).abs() < 1e-9);
+        assert!((sim.balance() - balance - 35.0).abs() < 1e-9);
+        assert!(sim.held_shares().unwrap().is_empty());
+        let sale = sim.ledger().last().unwrap().clone();
+        assert_eq!((sale.cause, sale.position_id), (LedgerCause::Sale, Some(1)));
+    }
+    
+    #[tokio::test]
+    async fn test_balance_stays_exact_over_many_small_changes() {
+        let mut config = test_config();
+        config.slippage_pct = 0.0;
+        let sim = PaperTradingSimulator::new(config, 1.0);
+        
+        for _ in 0..1000 {
+            sim.add_to_balance(0.1);
+            sim.add_to_balance(-0.07);
+        }
+        // 50 shares @ $0.60
+        sim.execute_order(&test_order()).await.unwrap().fill().unwrap();
+        
+        assert_eq!(sim.balance(), 2000.0);
+    }
+    
+    #[tokio::test]
+    async fn test_fill_rate_miss_reports_not_filled() {
+        let mut config = test_config();
+        config.fill_rate = 0.0;
+        let sim = PaperTradingSimulator::new(config, 1.0);
+        
+        let outcome = sim.execute_order(&test_order()).await.unwrap();
+        assert!(matches!(outcome, FillOutcome::Rejected(RejectReason::NotFilled)));
+    }
+    
+    #[tokio::test]
+    async fn test_unaffordable_fill_reports_insufficient_balance() {
+        // Cap disabled (2x balance) so the balance check is what rejects
+        let sim = PaperTradingSimulator::new(test_config(), 2.0);
+        
+        let mut order = test_order();
+        order.size = 5000.0; // $3,000 against a $2,000 balance
+        
+        let outcome = sim.execute_order(&order).await.unwrap();
+        assert!(matches!(
+            outcome,
+            FillOutcome::Rejected(RejectReason::InsufficientBalance { balance, .. }) if balance == 2000.0
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_slippage_over_max_reports_excessive_slippage() {
+        let mut config = test_config();
+        config.slippage_pct = 0.05;
+        config.max_slippage_pct = Some(0.0);
+        let sim = PaperTradingSimulator::new(config, 1.0);
+        
+        let outcome = sim.execute_order(&test_order()).await.unwrap();
+        assert!(matches!(
+            outcome,
+            FillOutcome::Rejected(RejectReason::ExcessiveSlippage { .. })
+        ));
+        assert_eq!(sim.balance(), 2000.0);
+    }
+}
@@ -0,0 +1,303 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use crate::data::http::HttpClient;
+use crate::execution::persistence::PositionDatabase;
+use crate::execution::types::Position;
+use tracing::warn;
+
+/// Somewhere positions are written
+pub trait PositionStore {
+    /// Store a new position, returning its id
+    fn insert_position(&self, pos: &Position) -> Result<i64>;
+    
+    fn update_position_status(&self, id: i64, status: &str, pnl: Option<f64>) -> Result<()>;
+    
+    /// Shrink a position after a partial exit
+    fn reduce_position(&self, id: i64, yes_shares: f64, no_shares: f64, cost: f64) -> Result<()>;
+    
+    /// Replace a position's tags and notes
+    fn update_position_annotations(&self, id: i64, tags: &[String], notes: Option<&str>) -> Result<()>;
+}
+
+impl PositionStore for PositionDatabase {
+    fn insert_position(&self, pos: &Position) -> Result<i64> {
+        PositionDatabase::insert_position(self, pos)
+    }
+    
+    fn update_position_status(&self, id: i64, status: &str, pnl: Option<f64>) -> Result<()> {
+        PositionDatabase::update_position_status(self, id, status, pnl)
+    }
+    
+    fn reduce_position(&self, id: i64, yes_shares: f64, no_shares: f64, cost: f64) -> Result<()> {
+        PositionDatabase::reduce_position(self, id, yes_shares, no_shares, cost)
+    }
+    
+    fn update_position_annotations(&self, id: i64, tags: &[String], notes: Option<&str>) -> Result<()> {
+        PositionDatabase::update_position_annotations(self, id, tags, notes)
+    }
+}
+
+impl<T: PositionStore + ?Sized> PositionStore for &T {
+    fn insert_position(&self, pos: &Position) -> Result<i64> {
+        (**self).insert_position(pos)
+    }
+    
+    fn update_position_status(&self, id: i64, status: &str, pnl: Option<f64>) -> Result<()> {
+        (**self).update_position_status(id, status, pnl)
+    }
+    
+    fn reduce_position(&self, id: i64, yes_shares: f64, no_shares: f64, cost: f64) -> Result<()> {
+        (**self).reduce_position(id, yes_shares, no_shares, cost)
+    }
+    
+    fn update_position_annotations(&self, id: i64, tags: &[String], notes: Option<&str>) -> Result<()> {
+        (**self).update_position_annotations(id, tags, notes)
+    }
+}
+
+/// Queries reporting needs; implemented by both read-write and read-only databases
+pub trait PositionReader {
+    fn get_open_positions(&self) -> Result<Vec<Position>>;
+    
+    fn count_open_positions(&self) -> Result<usize>;
+    
+    fn count_trades_on(&self, date: NaiveDate) -> Result<usize>;
+    
+    fn get_closed_pnls_on(&self, date: NaiveDate) -> Result<Vec<f64>>;
+    
+    fn count_circuit_breaker_events_on(&self, date: NaiveDate) -> Result<usize>;
+}
+
+impl PositionReader for PositionDatabase {
+    fn get_open_positions(&self) -> Result<Vec<Position>> {
+        PositionDatabase::get_open_positions(self)
+    }
+    
+    fn count_open_positions(&self) -> Result<usize> {
+        PositionDatabase::count_open_positions(self)
+    }
+    
+    fn count_trades_on(&self, date: NaiveDate) -> Result<usize> {
+        PositionDatabase::count_trades_on(self, date)
+    }
+    
+    fn get_closed_pnls_on(&self, date: NaiveDate) -> Result<Vec<f64>> {
+        PositionDatabase::get_closed_pnls_on(self, date)
+    }
+    
+    fn count_circuit_breaker_events_on(&self, date: NaiveDate) -> Result<usize> {
+        PositionDatabase::count_circuit_breaker_events_on(self, date)
+    }
+}
+
+/// Writes to a primary and a secondary store
+/// The primary is authoritative (its ids and errors are returned); secondary failures are only logged
+pub struct TeeStore<P, S> {
+    primary: P,
+    secondary: S,
+}
+
+impl<P: PositionStore, S: PositionStore> TeeStore<P, S> {
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self { primary, secondary }
+    }
+}
+
+impl<P: PositionStore, S: PositionStore> PositionStore for TeeStore<P, S> {
+    fn insert_position(&self, pos: &Position) -> Result<i64> {
+        let id = self.primary.insert_position(pos)?;
+        
+        // Mirror under the primary's id so the copies can be matched up
+        let mirrored = Position { id: Some(id), ..pos.clone() };
+        if let Err(e) = self.secondary.insert_position(&mirrored) {
+            warn!("Secondary store failed to insert position {}: {}", id, e);
+        }
+        Ok(id)
+    }
+    
+    fn update_position_status(&self, id: i64, status: &str, pnl: Option<f64>) -> Result<()> {
+        self.primary.update_position_status(id, status, pnl)?;
+        if let Err(e) = self.secondary.update_position_status(id, status, pnl) {
+            warn!("Secondary store failed to update position {}: {}", id, e);
+        }
+        Ok(())
+    }
+    
+    fn reduce_position(&self, id: i64, yes_shares: f64, no_shares: f64, cost: f64) -> Result<()> {
+        self.primary.reduce_position(id, yes_shares, no_shares, cost)?;
+        if let Err(e) = self.secondary.reduce_position(id, yes_shares, no_shares, cost) {
+            warn!("Secondary store failed to reduce position {}: {}", id, e);
+        }
+        Ok(())
+    }
+    
+    fn update_position_annotations(&self, id: i64, tags: &[String], notes: Option<&str>) -> Result<()> {
+        self.primary.update_position_annotations(id, tags, notes)?;
+        if let Err(e) = self.secondary.update_position_annotations(id, tags, notes) {
+            warn!("Secondary store failed to annotate position {}: {}", id, e);
+        }
+        Ok(())
+    }
+}
+
+/// Mirrors position writes to an HTTP endpoint as JSON (fire-and-forget)
+/// POST {url}/positions for inserts, POST {url}/positions/{id} with the changed fields for updates
+pub struct HttpPositionStore {
+    http: HttpClient,
+    url: String,
+}
+
+impl HttpPositionStore {
+    pub fn new(url: String) -> Self {
+        Self {
+            http: HttpClient::default(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+    
+    /// Share a connection pool and in-flight limit with the other API clients
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+    
+    /// Send in the background so a slow endpoint never holds up a trade
+    fn post(&self, url: String, body: serde_json::Value) -> Result<()> {
+        let handle = tokio::runtime::Handle::try_current()?;
+        let http = self.http.clone();
+        handle.spawn(async move {
+            let _permit = http.permit().await;
+            let result = http.post(&url).json(&body).send().await.and_then(|r| r.error_for_status());
+            if let Err(e) = result {
+                warn!("Position mirror POST {} failed: {}", url, e);
+            }
+        });
+        Ok(())
+    }
+}
+
+impl PositionStore for HttpPositionStore {
+    fn insert_position(&self, pos: &Position) -> Result<i64> {
+        let body = serde_json::json!({
+            "id": pos.id,
+            "market_id": pos.market_id,
+            "strategy": pos.strategy,
+            "side": pos.side.as_ref().map(|s| format!("{:?}", s).to_uppercase()),
+            "yes_shares": pos.yes_shares,
+            "no_shares": pos.no_shares,
+            "entry_price": pos.entry_price,
+            "cost": pos.cost,
+            "opened_at": pos.opened_at.to_rfc3339(),
+            "status": pos.status,
+            "event_key": pos.event_key,
+            "tags": pos.tags,
+            "notes": pos.notes,
+        });
+        self.post(format!("{}/positions", self.url), body)?;
+        Ok(pos.id.unwrap_or_default())
+    }
+    
+    fn update_position_status(&self, id: i64, status: &str, pnl: Option<f64>) -> Result<()> {
+        let body = serde_json::json!({ "status": status, "pnl": pnl });
+        self.post(format!("{}/positions/{}", self.url, id), body)
+    }
+    
+    fn reduce_position(&self, id: i64, yes_shares: f64, no_shares: f64, cost: f64) -> Result<()> {
+        let body = serde_json::json!({ "yes_shares": yes_shares, "no_shares": no_shares, "cost": cost });
+        self.post(format!("{}/positions/{}", self.url, id), body)
+    }
+    
+    fn update_position_annotations(&self, id: i64, tags: &[String], notes: Option<&str>) -> Result<()> {
+        let body = serde_json::json!({ "tags": tags, "notes": notes });
+        self.post(format!("{}/positions/{}", self.url, id), body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    
+    /// Records what it was asked to write, optionally failing
+    #[derive(Default)]
+    struct RecordingStore {
+        inserted: RefCell<Vec<Position>>,
+        /// Ids of positions updated after insert, in order
+        updated: RefCell<Vec<i64>>,
+        fail: bool,
+    }
+    
+    impl PositionStore for RecordingStore {
+        fn insert_position(&self, pos: &Position) -> Result<i64> {
+            if self.fail {
+                anyhow::bail!("mirror unreachable");
+            }
+            self.inserted.borrow_mut().push(pos.clone());
+            Ok(pos.id.unwrap_or_default())
+        }
+        
+        fn update_position_status(&self, id: i64, _status: &str, _pnl: Option<f64>) -> Result<()> {
+            self.updated.borrow_mut().push(id);
+            Ok(())
+        }
+        
+        fn reduce_position(&self, id: i64, _yes_shares: f64, _no_shares: f64, _cost: f64) -> Result<()> {
+            self.updated.borrow_mut().push(id);
+            Ok(())
+        }
+        
+        fn update_position_annotations(&self, id: i64, _tags: &[String], _notes: Option<&str>) -> Result<()> {
+            self.updated.borrow_mut().push(id);
+            Ok(())
+        }
+    }
+    
+    fn position(market_id: &str) -> Position {
+        Position {
+            yes_shares: 80.0,
+            entry_price: 0.50,
+            ..Position::fixture(market_id)
+        }
+    }
+    
+    #[test]
+    fn test_tee_store_forwards_inserts_to_both() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let mirror = RecordingStore::default();
+        let tee = TeeStore::new(&db, &mirror);
+        
+        let first = tee.insert_position(&position("0xabc")).unwrap();
+        let second = tee.insert_position(&position("0xdef")).unwrap();
+        
+        assert_eq!(db.count_open_positions().unwrap(), 2);
+        let mirrored = mirror.inserted.borrow();
+        assert_eq!(mirrored.len(), 2);
+        assert_eq!(mirrored[0].id, Some(first));
+        assert_eq!(mirrored[1].id, Some(second));
+        assert_eq!(mirrored[1].market_id, "0xdef");
+        
+        // A failing secondary doesn't fail the write
+        let broken = RecordingStore { fail: true, ..Default::default() };
+        let tee = TeeStore::new(&db, &broken);
+        assert!(tee.insert_position(&position("0x123")).is_ok());
+        assert_eq!(db.count_open_positions().unwrap(), 3);
+    }
+    
+    #[test]
+    fn test_tee_store_mirrors_every_update() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let mirror = RecordingStore::default();
+        let tee = TeeStore::new(&db, &mirror);
+        let id = tee.insert_position(&position("0xabc")).unwrap();
+        
+        // Scale-out, review flag, settlement
+        tee.reduce_position(id, 40.0, 0.0, 10.0).unwrap();
+        tee.update_position_annotations(id, &["scaled_out".to_string()], None).unwrap();
+        tee.update_position_status(id, "closed", Some(30.0)).unwrap();
+        
+        assert_eq!(*mirror.updated.borrow(), vec![id, id, id]);
+        let closed = &db.get_positions_by_strategy("weather_edge", Some("closed")).unwrap()[0];
+        assert_eq!(closed.yes_shares, 40.0);
+        assert_eq!(closed.tags, vec!["scaled_out"]);
+    }
+}
@@ -1,13 +1,22 @@
 use chrono::{DateTime, Utc};
-use crate::strategies::types::Side;
+use serde::{Deserialize, Serialize};
+use crate::strategies::types::{Side, Signal};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum Token {
     Yes,
     No,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum OrderType {
     FOK,  // Fill-or-Kill
     GTC,  // Good-til-Cancel
@@ -21,18 +30,73 @@ pub struct Order {
     pub price: f64,
     pub size: f64,
     pub order_type: OrderType,
+    /// Ticks posted inside the ask (resting GTC orders only)
+    pub improvement_ticks: u32,
 }
 
 #[derive(Debug, Clone)]
 pub struct Fill {
     pub market_id: String,
+    /// Token the backend reports as executed (None for fills recorded before this was kept)
+    pub token: Option<Token>,
     pub size: f64,
     pub price: f64,
     pub cost: f64,
+    /// Realized slippage vs the order's limit price (0.01 = 1% worse)
+    pub slippage: f64,
     pub timestamp: DateTime<Utc>,
+    /// On-chain settlement transaction (live fills only)
+    pub tx_hash: Option<String>,
+    /// Block the settlement transaction was mined in, when looked up
+    pub block_number: Option<u64>,
+    /// Venue order this fill executed against (None = paper)
+    pub order_id: Option<String>,
+    /// Venue's id for this trade; one order can fill in several, and a repeat delivery
+    /// of the same (order_id, fill_id) is stored once (None = paper)
+    pub fill_id: Option<String>,
 }
 
+/// Result of submitting an order to an execution backend
 #[derive(Debug, Clone)]
+pub enum FillOutcome {
+    Filled(Fill),
+    Rejected(RejectReason),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectReason {
+    /// Order rested without filling (simulated fill-rate miss)
+    NotFilled,
+    InsufficientBalance { cost: f64, balance: f64 },
+    ExcessiveSlippage { slippage: f64, max: f64 },
+    /// Over the orders-per-minute limit; not sent
+    RateLimited,
+}
+
+impl FillOutcome {
+    pub fn fill(self) -> Option<Fill> {
+        match self {
+            FillOutcome::Filled(fill) => Some(fill),
+            FillOutcome::Rejected(_) => None,
+        }
+    }
+}
+
+/// Whether a failed submission may still have reached the venue: a timeout, or a connection
+/// lost after the request went out. Its outcome is unknown, not failed, until reconciled.
+pub fn submission_outcome_unknown(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            e.is_timeout() || (e.is_request() && !e.is_connect()) || e.is_body() || e.is_decode()
+        } else {
+            cause.is::<tokio::time::error::Elapsed>()
+        }
+    })
+}
+
+/// On-chain references (tx hash, block) stay on the fills: one position can span several
+/// fills, each settled in its own transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Position {
     pub id: Option<i64>,
     pub market_id: String,
@@ -46,4 +110,192 @@ pub struct Position {
     pub closed_at: Option<DateTime<Utc>>,
     pub pnl: Option<f64>,
     pub status: String,
+    pub event_key: Option<String>,
+    /// Operator labels for filtering ("manual override", "test", ...)
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+    /// `Market::criteria_hash` at entry, to notice later amendments
+    pub question_hash: Option<String>,
+}
+
+#[cfg(test)]
+impl Position {
+    /// Open 100-share YES weather position bought at 0.40; override fields with `..`
+    pub fn fixture(market_id: &str) -> Self {
+        Self {
+            id: None,
+            market_id: market_id.to_string(),
+            strategy: "weather_edge".to_string(),
+            side: Some(Side::Yes),
+            yes_shares: 100.0,
+            no_shares: 0.0,
+            entry_price: 0.40,
+            cost: 40.0,
+            opened_at: Utc::now(),
+            closed_at: None,
+            pnl: None,
+            status: "open".to_string(),
+            event_key: None,
+            tags: Vec::new(),
+            notes: None,
+            question_hash: None,
+        }
+    }
+}
+
+/// A market that passed the weather screen but whose question couldn't be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub market_id: String,
+    pub question: String,
+    pub error: String,
+}
+
+/// A trade the bot decided not to take, kept for threshold tuning
+#[derive(Debug, Clone)]
+pub struct Rejection {
+    pub market_id: String,
+    pub strategy: String,
+    pub side: Option<Side>,
+    pub entry_price: Option<f64>,
+    pub size: Option<f64>,
+    pub edge: Option<f64>,
+    /// Short machine-readable code ("edge_below_min", "max_positions", ...)
+    pub reason: String,
+    pub detail: String,
+}
+
+impl Rejection {
+    /// Record a validated signal that didn't make it to an order
+    pub fn from_signal(signal: &Signal, reason: &str, detail: String) -> Self {
+        Self {
+            market_id: signal.market_id.clone(),
+            strategy: signal.strategy.name().to_string(),
+            side: signal.side.clone(),
+            entry_price: Some(signal.entry_price),
+            size: Some(signal.size),
+            edge: signal.edge,
+            reason: reason.to_string(),
+            detail,
+        }
+    }
+}
+
+/// Why a paper balance changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerCause {
+    /// Starting balance
+    Deposit,
+    /// Shares bought at the order's limit price
+    Fill,
+    /// Extra paid over the limit price
+    Slippage,
+    Fee,
+    /// Proceeds from a resolved or closed position
+    Settlement,
+    /// Proceeds from selling shares before resolution
+    Sale,
+    /// Manual credit or debit
+    Adjustment,
+}
+
+impl LedgerCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerCause::Deposit => "deposit",
+            LedgerCause::Fill => "fill",
+            LedgerCause::Slippage => "slippage",
+            LedgerCause::Fee => "fee",
+            LedgerCause::Settlement => "settlement",
+            LedgerCause::Sale => "sale",
+            LedgerCause::Adjustment => "adjustment",
+        }
+    }
+    
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "deposit" => Some(LedgerCause::Deposit),
+            "fill" => Some(LedgerCause::Fill),
+            "slippage" => Some(LedgerCause::Slippage),
+            "fee" => Some(LedgerCause::Fee),
+            "settlement" => Some(LedgerCause::Settlement),
+            "sale" => Some(LedgerCause::Sale),
+            "adjustment" => Some(LedgerCause::Adjustment),
+            _ => None,
+        }
+    }
+}
+
+/// One balance change; replaying the amounts in order reproduces the balance
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerEntry {
+    pub timestamp: DateTime<Utc>,
+    pub cause: LedgerCause,
+    pub market_id: Option<String>,
+    /// Position a settlement paid out to (None for entries not tied to one)
+    pub position_id: Option<i64>,
+    /// Signed change in cash (negative = spent)
+    pub amount: f64,
+    pub balance_after: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::types::{OrderIntent, Strategy};
+    
+    #[tokio::test]
+    async fn test_timed_out_submission_has_unknown_outcome() {
+        // Accepts the connection, never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/order", listener.local_addr().unwrap());
+        let client = reqwest::Client::builder().timeout(std::time::Duration::from_millis(50)).build().unwrap();
+        let timeout = anyhow::Error::from(client.post(&url).send().await.unwrap_err()).context("submit failed");
+        assert!(submission_outcome_unknown(&timeout));
+        
+        // Refused before anything was sent, or refused by the venue: definitely not placed
+        drop(listener);
+        let refused = anyhow::Error::from(client.post(&url).send().await.unwrap_err());
+        assert!(!submission_outcome_unknown(&refused));
+        assert!(!submission_outcome_unknown(&anyhow::anyhow!("Insufficient balance")));
+    }
+    
+    #[test]
+    fn test_signal_and_position_round_trip_through_json() {
+        let signal = Signal {
+            market_id: "0xabc".to_string(),
+            strategy: Strategy::WeatherEdge,
+            side: Some(Side::No),
+            entry_price: 0.44,
+            intent: OrderIntent::Limit,
+            size: 25.0,
+            edge: Some(0.12),
+            confidence: 0.9,
+            event_key: Some("new york:2026-02-17:temperature".to_string()),
+            market_volume_24h: None,
+            market_liquidity: Some(8000.0),
+        };
+        let json = serde_json::to_value(&signal).unwrap();
+        assert_eq!(json["strategy"], "weather_edge");
+        assert_eq!(json["side"], "NO");
+        assert!(json["market_volume_24h"].is_null());
+        assert_eq!(serde_json::from_value::<Signal>(json).unwrap(), signal);
+        
+        let position = Position {
+            id: Some(7),
+            yes_shares: 80.0,
+            entry_price: 0.50,
+            opened_at: "2026-02-16T12:00:00Z".parse().unwrap(),
+            pnl: Some(-40.0),
+            status: "closed".to_string(),
+            tags: vec!["manual override".to_string()],
+            question_hash: Some("00ff".to_string()),
+            ..Position::fixture("0xabc")
+        };
+        let json = serde_json::to_string(&position).unwrap();
+        assert!(json.contains(r#""side":"YES""#));
+        assert!(json.contains(r#""opened_at":"2026-02-16T12:00:00Z""#));
+        assert_eq!(serde_json::from_str::<Position>(&json).unwrap(), position);
+    }
 }
@@ -0,0 +1,147 @@
+use anyhow::Result;
+use crate::config::RiskConfig;
+use crate::execution::persistence::PositionDatabase;
+use crate::execution::risk::{CircuitBreaker, CircuitBreakerReason};
+use crate::execution::types::{Fill, Order, Token};
+use tracing::error;
+
+/// A way a fill differs from the order that produced it
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum FillDiscrepancy {
+    #[error("Fill is for market {actual}, order was for {expected}")]
+    WrongMarket { expected: String, actual: String },
+    
+    #[error("Fill executed {actual:?}, order was for {expected:?}")]
+    WrongToken { expected: Token, actual: Token },
+    
+    #[error("Fill size {actual} != order size {expected}")]
+    SizeMismatch { expected: f64, actual: f64 },
+    
+    #[error("Fill price ${actual:.3} is more than ${band:.3} from intended ${expected:.3}")]
+    PriceOutOfBand { expected: f64, actual: f64, band: f64 },
+}
+
+impl FillDiscrepancy {
+    /// Short code stored with the discrepancy
+    pub fn code(&self) -> &'static str {
+        match self {
+            FillDiscrepancy::WrongMarket { .. } => "wrong_market",
+            FillDiscrepancy::WrongToken { .. } => "wrong_token",
+            FillDiscrepancy::SizeMismatch { .. } => "size_mismatch",
+            FillDiscrepancy::PriceOutOfBand { .. } => "price_out_of_band",
+        }
+    }
+}
+
+/// Compare a fill to its order; an unreported token is not checked
+pub fn compare_fill(order: &Order, fill: &Fill, price_band: f64) -> Vec<FillDiscrepancy> {
+    let mut discrepancies = Vec::new();
+    
+    if fill.market_id != order.market_id {
+        discrepancies.push(FillDiscrepancy::WrongMarket {
+            expected: order.market_id.clone(),
+            actual: fill.market_id.clone(),
+        });
+    }
+    if let Some(token) = &fill.token {
+        if *token != order.token {
+            discrepancies.push(FillDiscrepancy::WrongToken {
+                expected: order.token.clone(),
+                actual: token.clone(),
+            });
+        }
+    }
+    if (fill.size - order.size).abs() > 1e-6 {
+        discrepancies.push(FillDiscrepancy::SizeMismatch { expected: order.size, actual: fill.size });
+    }
+    if (fill.price - order.price).abs() > price_band + 1e-9 {
+        discrepancies.push(FillDiscrepancy::PriceOutOfBand {
+            expected: order.price,
+            actual: fill.price,
+            band: price_band,
+        });
+    }
+    
+    discrepancies
+}
+
+/// Check a fill against its order, recording each discrepancy and tripping the
+/// breaker if configured. Returns whether the fill matched.
+pub fn verify_fill(
+    order: &Order,
+    fill: &Fill,
+    config: &RiskConfig,
+    db: &PositionDatabase,
+    breaker: &CircuitBreaker,
+) -> Result<bool> {
+    if !config.verify_fills {
+        return Ok(true);
+    }
+    
+    let discrepancies = compare_fill(order, fill, config.fill_price_band);
+    for discrepancy in &discrepancies {
+        error!("Fill discrepancy on {}: {}", order.market_id, discrepancy);
+        db.record_fill_discrepancy(&order.market_id, discrepancy.code(), &discrepancy.to_string())?;
+    }
+    
+    if !discrepancies.is_empty() && config.halt_on_fill_discrepancy {
+        breaker.trigger(CircuitBreakerReason::FillDiscrepancy, db)?;
+    }
+    Ok(discrepancies.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::config::Config;
+    use crate::execution::types::OrderType;
+    use crate::strategies::types::Side;
+    
+    fn order() -> Order {
+        Order {
+            market_id: "0xverify".to_string(),
+            side: Side::Yes,
+            token: Token::Yes,
+            price: 0.40,
+            size: 100.0,
+            order_type: OrderType::FOK,
+            improvement_ticks: 0,
+        }
+    }
+    
+    fn fill(token: Token, price: f64) -> Fill {
+        Fill {
+            market_id: "0xverify".to_string(),
+            token: Some(token),
+            size: 100.0,
+            price,
+            cost: 100.0 * price,
+            slippage: 0.0,
+            timestamp: Utc::now(),
+            tx_hash: None,
+            block_number: None,
+            order_id: None,
+            fill_id: None,
+        }
+    }
+    
+    #[test]
+    fn test_wrong_token_fill_is_recorded_as_discrepancy() {
+        let mut config = Config::for_test().risk;
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let breaker = CircuitBreaker::new();
+        
+        assert!(verify_fill(&order(), &fill(Token::Yes, 0.401), &config, &db, &breaker).unwrap());
+        assert!(db.get_fill_discrepancies("0xverify").unwrap().is_empty());
+        
+        assert!(!verify_fill(&order(), &fill(Token::No, 0.401), &config, &db, &breaker).unwrap());
+        assert_eq!(db.get_fill_discrepancies("0xverify").unwrap(), vec!["wrong_token"]);
+        assert!(!breaker.is_triggered());
+        
+        // Optionally halts trading
+        config.halt_on_fill_discrepancy = true;
+        assert!(!verify_fill(&order(), &fill(Token::Yes, 0.45), &config, &db, &breaker).unwrap());
+        assert!(matches!(breaker.reason(), Some(CircuitBreakerReason::FillDiscrepancy)));
+    }
+}
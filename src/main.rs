@@ -4,9 +4,14 @@ mod strategies;
 mod execution;
 mod ai;
 mod monitoring;
+mod engine;
+mod math;
 
 use anyhow::Result;
 use config::{Config, EnvConfig};
+use engine::WeatherEngine;
+use data::http::HttpClient;
+use monitoring::alerts::TelegramAlerter;
 use execution::persistence::PositionDatabase;
 
 #[tokio::main]
@@ -21,6 +26,7 @@ async fn main() -> Result<()> {
     tracing::info!("Loading configuration...");
     let config = Config::load("config.toml")?;
     let env_config = EnvConfig::load()?;
+    config::check_mode_consistency(&config, &env_config)?;
 
     tracing::info!("Dry run mode: {}", config.system.dry_run);
     tracing::info!("Paper trading: {}", config.paper_trading.enabled);
@@ -29,7 +35,51 @@ async fn main() -> Result<()> {
 
     // Initialize database
     tracing::info!("Initializing database: {}", config.system.database_path);
-    let db = PositionDatabase::new(&config.system.database_path)?;
+    let db = PositionDatabase::open_with_retry(
+        &config.system.database_path,
+        config.system.db_open_attempts,
+        std::time::Duration::from_millis(config.system.db_open_backoff_ms),
+    )
+    .await?;
+
+    // `--recompute-pnl [--fix]`: audit closed P&L against fills, then exit
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|a| a == "--recompute-pnl") {
+        let fix = args.iter().any(|a| a == "--fix");
+        let discrepancies = execution::recompute::recompute_pnl(&db, fix)?;
+        tracing::info!(
+            "{} closed positions with mismatched P&L{}",
+            discrepancies.len(),
+            if fix { " (corrected)" } else { "" }
+        );
+        return Ok(());
+    }
+
+    // `--export-ledger <path>`: write the paper-trading ledger (JSON if the path ends in .json, else CSV), then exit
+    if let Some(path) = args.iter().position(|a| a == "--export-ledger").and_then(|i| args.get(i + 1)) {
+        let ledger = db.get_ledger()?;
+        if path.ends_with(".json") {
+            monitoring::logger::export_ledger_json(&ledger, path)?;
+        } else {
+            monitoring::logger::export_ledger_csv(&ledger, path)?;
+        }
+        tracing::info!("Exported {} ledger entries to {}", ledger.len(), path);
+        return Ok(());
+    }
+
+    // `--reset-breaker <operator> <note>`: record a manual circuit breaker reset, then exit
+    // A running bot sees the reset in the database and resumes on its next loop
+    if let Some(i) = args.iter().position(|a| a == "--reset-breaker") {
+        let (Some(operator), Some(note)) = (args.get(i + 1), args.get(i + 2)) else {
+            anyhow::bail!("Usage: --reset-breaker <operator> <note>");
+        };
+        if operator.trim().is_empty() || note.trim().is_empty() {
+            anyhow::bail!("Manual circuit breaker reset needs an operator and a note");
+        }
+        let reset = db.record_circuit_breaker_reset(operator, note)?;
+        tracing::info!("Reset {} open circuit breaker events (by {}: {})", reset, operator, note);
+        return Ok(());
+    }
 
     // Perform crash recovery
     execution::persistence::recover_from_crash(&db).await?;
@@ -41,12 +91,66 @@ async fn main() -> Result<()> {
     tracing::info!("✅ Bot initialized successfully");
     tracing::info!("Waiting for trading signals...");
 
-    // TODO: Start weather polling loop
-    // TODO: Start strategy engine
     // TODO: Start WebSocket connection (if arbitrage enabled)
 
-    // Keep running
-    tokio::signal::ctrl_c().await?;
+    // One connection pool and in-flight cap for every outbound API client
+    let http = HttpClient::new(config.infrastructure.max_concurrent_http);
+
+    if config.monitoring.daily_summary_enabled {
+        let alerter = if config.monitoring.telegram_enabled {
+            TelegramAlerter::from_env(&env_config).map(|a| a.with_http(http.clone()))
+        } else {
+            None
+        };
+        tokio::spawn(monitoring::summary::run_daily_summary_task(
+            config.system.database_path.clone(),
+            alerter,
+            config.monitoring.daily_summary_time,
+            chrono::Utc::now(),
+        ));
+    }
+
+    if let Some(dir) = &config.system.backup_dir {
+        tokio::spawn(execution::backup::run_backup_task(
+            config.system.database_path.clone(),
+            dir.clone(),
+            std::time::Duration::from_secs(config.system.backup_interval_secs),
+            config.system.backup_keep,
+        ));
+    }
+
+    if config.strategies.weather.enabled {
+        let mut engine = WeatherEngine::new(config.clone(), &env_config, db, http)?;
+        
+        // Admin signal: `kill -USR1 <pid>` toggles the global pause
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            
+            let pause = engine.pause_switch();
+            // Its own connection, so the new state is saved the moment the signal lands
+            let pause_db = if config.system.persist_pause {
+                Some(PositionDatabase::new(&config.system.database_path)?)
+            } else {
+                None
+            };
+            let mut usr1 = signal(SignalKind::user_defined1())?;
+            tokio::spawn(async move {
+                while usr1.recv().await.is_some() {
+                    if let Err(e) = engine::toggle_pause(&pause, pause_db.as_ref()) {
+                        tracing::warn!("Failed to persist pause state: {}", e);
+                    }
+                }
+            });
+        }
+        
+        tokio::select! {
+            result = engine.run() => result?,
+            _ = tokio::signal::ctrl_c() => {}
+        }
+    } else {
+        tokio::signal::ctrl_c().await?;
+    }
     tracing::info!("Shutting down...");
 
     Ok(())
@@ -0,0 +1,278 @@
+//! Probability distribution helpers shared by forecasting and pricing code
+
+use serde::Deserialize;
+use std::f64::consts::PI;
+
+/// Which `erf` implementation backs the normal CDF
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErfPrecision {
+    /// `erf`: 5-term rational approximation, |error| <= 1.5e-7
+    #[default]
+    Fast,
+    /// `erf_precise`: series / continued fraction, |error| <= 1e-14, several times slower
+    Precise,
+}
+
+/// Standard normal cumulative distribution function
+pub fn normal_cdf(z: f64) -> f64 {
+    0.5 * (1.0 + erf(z / f64::sqrt(2.0)))
+}
+
+/// Normal CDF with a chosen erf; the CDF error is half the erf error
+pub fn normal_cdf_with(z: f64, precision: ErfPrecision) -> f64 {
+    match precision {
+        ErfPrecision::Fast => normal_cdf(z),
+        ErfPrecision::Precise => 0.5 * (1.0 + erf_precise(z / f64::sqrt(2.0))),
+    }
+}
+
+/// Standard normal probability density function
+pub fn normal_pdf(z: f64) -> f64 {
+    (-0.5 * z * z).exp() / (2.0 * PI).sqrt()
+}
+
+/// Inverse standard normal CDF (quantile function), Acklam's rational approximation
+/// Relative error < 1.15e-9; returns ±infinity at p = 0 / 1 and NaN outside [0, 1]
+pub fn inverse_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+    
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+    
+    if p < P_LOW {
+        // Lower tail
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        // Central region
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        // Upper tail, by symmetry
+        -inverse_cdf(1.0 - p)
+    }
+}
+
+/// P(low < X <= high) for X ~ N(mean, sd²)
+/// Used for bucketed "between" markets
+pub fn normal_interval_prob(low: f64, high: f64, mean: f64, sd: f64) -> f64 {
+    if high <= low {
+        return 0.0;
+    }
+    (normal_cdf((high - mean) / sd) - normal_cdf((low - mean) / sd)).max(0.0)
+}
+
+/// Skew-normal CDF with location/scale chosen so the mean and sigma match
+/// F(x) = Φ(z) - 2·T(z, α) where T is Owen's T function
+pub fn skew_normal_cdf(x: f64, mean: f64, sd: f64, alpha: f64) -> f64 {
+    let delta = alpha / (1.0 + alpha * alpha).sqrt();
+    let two_over_pi = 2.0 / PI;
+    let scale = sd / (1.0 - two_over_pi * delta * delta).sqrt();
+    let location = mean - scale * delta * two_over_pi.sqrt();
+    
+    let z = (x - location) / scale;
+    (normal_cdf(z) - 2.0 * owens_t(z, alpha)).clamp(0.0, 1.0)
+}
+
+/// Owen's T function via Simpson's rule
+/// T(h, a) = 1/(2π) ∫₀ᵃ exp(-h²(1+x²)/2) / (1+x²) dx
+pub fn owens_t(h: f64, a: f64) -> f64 {
+    const STEPS: usize = 200; // Even, for Simpson's rule
+    
+    let f = |x: f64| (-0.5 * h * h * (1.0 + x * x)).exp() / (1.0 + x * x);
+    let step = a / STEPS as f64;
+    
+    let mut sum = f(0.0) + f(a);
+    for i in 1..STEPS {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * f(i as f64 * step);
+    }
+    
+    sum * step / 3.0 / (2.0 * PI)
+}
+
+/// Error function approximation (Abramowitz & Stegun 7.1.26)
+/// Maximum absolute error 1.5e-7 over the whole real line
+pub fn erf(x: f64) -> f64 {
+    let a1 =  0.254829592;
+    let a2 = -0.284496736;
+    let a3 =  1.421413741;
+    let a4 = -1.453152027;
+    let a5 =  1.061405429;
+    let p  =  0.3275911;
+    
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    
+    let t = 1.0 / (1.0 + p * x);
+    let y = 1.0 - (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * (-x * x).exp();
+    
+    sign * y
+}
+
+/// Error function to near double precision (absolute error <= 1e-14)
+/// |x| < 3: e^{-x²}-weighted series with all-positive terms (no cancellation);
+/// beyond: erfc continued fraction, evaluated bottom-up
+pub fn erf_precise(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    
+    let value = if x < 3.0 {
+        // erf(x) = 2/√π · e^{-x²} · Σ 2ⁿ x^{2n+1} / (1·3·…·(2n+1))
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        while term > sum * 1e-17 {
+            n += 1.0;
+            term *= 2.0 * x * x / (2.0 * n + 1.0);
+            sum += term;
+        }
+        2.0 / PI.sqrt() * (-x * x).exp() * sum
+    } else {
+        // erfc(x) = e^{-x²}/√π · 1/(x + (1/2)/(x + 1/(x + (3/2)/(x + …))))
+        let mut fraction = x;
+        for k in (1..=60).rev() {
+            fraction = x + (k as f64 / 2.0) / fraction;
+        }
+        1.0 - (-x * x).exp() / (PI.sqrt() * fraction)
+    };
+    
+    sign * value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_normal_cdf() {
+        // Test known values
+        assert!((normal_cdf(0.0) - 0.5).abs() < 0.001);
+        assert!((normal_cdf(1.0) - 0.8413).abs() < 0.01);
+        assert!((normal_cdf(-1.0) - 0.1587).abs() < 0.01);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 0.001);
+    }
+    
+    #[test]
+    fn test_fast_and_precise_erf_agree_within_fast_tolerance() {
+        // Reference values of erf
+        for (x, expected) in [
+            (0.5, 0.520_499_877_813_046_5),
+            (1.0, 0.842_700_792_949_714_9),
+            (2.0, 0.995_322_265_018_952_7),
+            (3.5, 0.999_999_256_901_627_7),
+        ] {
+            assert!((erf_precise(x) - expected).abs() < 1e-14);
+            assert!((erf_precise(-x) + expected).abs() < 1e-14);
+        }
+        
+        let mut x = -6.0;
+        while x <= 6.0 {
+            assert!((erf(x) - erf_precise(x)).abs() <= 1.5e-7, "erf({}) differs", x);
+            let z = x * 2.0;
+            let fast = normal_cdf_with(z, ErfPrecision::Fast);
+            let precise = normal_cdf_with(z, ErfPrecision::Precise);
+            assert!((fast - precise).abs() <= 0.75e-7);
+            x += 0.01;
+        }
+    }
+    
+    #[test]
+    fn test_normal_pdf() {
+        // Peak is 1/sqrt(2π), symmetric
+        assert!((normal_pdf(0.0) - 0.398942).abs() < 1e-6);
+        assert!((normal_pdf(1.0) - 0.241971).abs() < 1e-6);
+        assert_eq!(normal_pdf(-1.5), normal_pdf(1.5));
+    }
+    
+    #[test]
+    fn test_inverse_cdf() {
+        assert!(inverse_cdf(0.5).abs() < 1e-9);
+        assert!((inverse_cdf(0.975) - 1.959964).abs() < 1e-5);
+        assert!((inverse_cdf(0.01) + 2.326348).abs() < 1e-5);
+        assert!((inverse_cdf(0.9) - 1.281552).abs() < 1e-5);
+        
+        // Round trip through the CDF (bounded by erf's 1.5e-7 accuracy)
+        for p in [0.001, 0.05, 0.3, 0.7, 0.95, 0.999] {
+            assert!((normal_cdf(inverse_cdf(p)) - p).abs() < 1e-6);
+        }
+        
+        assert_eq!(inverse_cdf(0.0), f64::NEG_INFINITY);
+        assert_eq!(inverse_cdf(1.0), f64::INFINITY);
+        assert!(inverse_cdf(1.5).is_nan());
+    }
+    
+    #[test]
+    fn test_normal_interval_prob() {
+        // "Between 14 and 16°C" with forecast N(15, 2²): ±0.5σ ≈ 38.3%
+        let prob = normal_interval_prob(14.0, 16.0, 15.0, 2.0);
+        assert!((prob - 0.3829).abs() < 1e-3);
+        
+        // ±1σ ≈ 68.3%, ±1.96σ ≈ 95%
+        assert!((normal_interval_prob(13.0, 17.0, 15.0, 2.0) - 0.6827).abs() < 1e-3);
+        assert!((normal_interval_prob(11.08, 18.92, 15.0, 2.0) - 0.95).abs() < 1e-3);
+        
+        // Adjacent buckets sum to the combined bucket
+        let combined = normal_interval_prob(12.0, 18.0, 15.0, 2.0);
+        let split = normal_interval_prob(12.0, 15.0, 15.0, 2.0) + normal_interval_prob(15.0, 18.0, 15.0, 2.0);
+        assert!((combined - split).abs() < 1e-12);
+        
+        // Empty or inverted interval
+        assert_eq!(normal_interval_prob(16.0, 16.0, 15.0, 2.0), 0.0);
+        assert_eq!(normal_interval_prob(17.0, 13.0, 15.0, 2.0), 0.0);
+    }
+    
+    #[test]
+    fn test_skew_normal_reduces_to_normal() {
+        for x in [-2.0, 0.0, 0.7, 3.0] {
+            assert!((skew_normal_cdf(x, 0.0, 1.0, 0.0) - normal_cdf(x)).abs() < 1e-9);
+        }
+    }
+    
+    #[test]
+    fn test_owens_t_known_values() {
+        // T(0, a) = atan(a) / 2π
+        assert!((owens_t(0.0, 1.0) - 0.125).abs() < 1e-9);
+        assert!(owens_t(1.0, 0.0).abs() < 1e-12);
+    }
+}
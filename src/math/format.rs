@@ -0,0 +1,61 @@
+//! Display formatting for logs and CSV: USD to cents, prices to 3dp, percentages to 1dp.
+//! Rounds half away from zero on the decimal value, so 2.675 shows as 2.68 (not the
+//! f64's 2.67) and tiny negatives don't show as "-0.00". Symbols are left to the caller.
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+pub const USD_DECIMAL_PLACES: u32 = 2;
+pub const PRICE_DECIMAL_PLACES: u32 = 3;
+pub const PCT_DECIMAL_PLACES: u32 = 1;
+
+/// Round for display to `dp` places, always showing all of them
+fn fmt_rounded(value: f64, dp: u32) -> String {
+    let Some(decimal) = Decimal::from_f64(value) else {
+        return format!("{:.*}", dp as usize, value); // NaN / inf
+    };
+    let mut rounded = decimal.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero);
+    if rounded.is_zero() {
+        rounded.set_sign_positive(true);
+    }
+    format!("{:.*}", dp as usize, rounded)
+}
+
+/// USD amount, e.g. 1234.5 -> "1234.50"
+pub fn fmt_usd(value: f64) -> String {
+    fmt_rounded(value, USD_DECIMAL_PLACES)
+}
+
+/// Share price, e.g. 0.4567 -> "0.457"
+pub fn fmt_price(value: f64) -> String {
+    fmt_rounded(value, PRICE_DECIMAL_PLACES)
+}
+
+/// Fraction as a percentage, e.g. 0.1234 -> "12.3"
+pub fn fmt_pct(fraction: f64) -> String {
+    fmt_rounded(fraction * 100.0, PCT_DECIMAL_PLACES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_formatters_round_half_away_from_zero() {
+        assert_eq!(fmt_usd(1234.5), "1234.50");
+        assert_eq!(fmt_usd(2.675), "2.68"); // format!("{:.2}") gives 2.67
+        assert_eq!(fmt_usd(-2.675), "-2.68");
+        assert_eq!(fmt_usd(183.4700000001), "183.47");
+        assert_eq!(fmt_usd(-0.001), "0.00");
+        assert_eq!(fmt_usd(0.0), "0.00");
+        
+        assert_eq!(fmt_price(0.4567), "0.457");
+        assert_eq!(fmt_price(0.0005), "0.001");
+        assert_eq!(fmt_price(0.5), "0.500");
+        
+        assert_eq!(fmt_pct(0.1234), "12.3");
+        assert_eq!(fmt_pct(0.12345), "12.3");
+        assert_eq!(fmt_pct(0.0005), "0.1");
+        assert_eq!(fmt_pct(-0.15), "-15.0");
+    }
+}
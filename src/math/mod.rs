@@ -0,0 +1,3 @@
+pub mod distributions;
+pub mod money;
+pub mod format;
@@ -0,0 +1,55 @@
+//! Fixed-point money: models compute in f64, but balances, costs and P&L are
+//! accumulated and stored as `Decimal` so many small trades don't drift
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+/// Decimal places kept for USD amounts and prices (micro-dollars)
+pub const MONEY_DECIMAL_PLACES: u32 = 6;
+
+/// Convert to fixed point, rounded to `MONEY_DECIMAL_PLACES` (non-finite becomes zero)
+pub fn to_money(value: f64) -> Decimal {
+    Decimal::from_f64(value)
+        .unwrap_or_default()
+        .round_dp(MONEY_DECIMAL_PLACES)
+}
+
+/// Back to f64 for the models; the nearest f64 to an exact decimal amount
+pub fn from_money(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+/// Snap an f64 amount to the money grid, e.g. 183.4700000001 -> 183.47
+pub fn round_money(value: f64) -> f64 {
+    from_money(to_money(value))
+}
+
+/// Sum amounts exactly, rounding each to the money grid first
+pub fn sum_money(values: impl IntoIterator<Item = f64>) -> f64 {
+    from_money(values.into_iter().map(to_money).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_adds_and_subtracts_stay_exact() {
+        // f64 drifts: ten dimes aren't a dollar
+        let float_total: f64 = (0..10).map(|_| 0.1).sum();
+        assert_ne!(float_total, 1.0);
+        
+        let mut balance = to_money(2000.0);
+        for _ in 0..1000 {
+            balance += to_money(0.1);
+            balance -= to_money(0.07);
+        }
+        balance -= to_money(30.0);
+        assert_eq!(balance, to_money(2000.0));
+        assert_eq!(from_money(balance), 2000.0);
+        
+        assert_eq!(sum_money((0..10).map(|_| 0.1)), 1.0);
+        assert_eq!(round_money(183.4700000001), 183.47);
+        assert_eq!(to_money(f64::NAN), Decimal::ZERO);
+    }
+}
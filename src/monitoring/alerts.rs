@@ -1 +1,54 @@
-// TODO: Implement alerts
+use anyhow::{Context, Result};
+use crate::data::http::HttpClient;
+use crate::config::EnvConfig;
+
+/// Telegram bot alerts
+pub struct TelegramAlerter {
+    http: HttpClient,
+    bot_token: String,
+    chat_id: String,
+}
+
+impl TelegramAlerter {
+    pub fn new(bot_token: String, chat_id: String) -> Self {
+        Self {
+            http: HttpClient::default(),
+            bot_token,
+            chat_id,
+        }
+    }
+    
+    /// Share a connection pool and in-flight limit with the other API clients
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+    
+    /// Build from env config if both the token and chat id are set
+    pub fn from_env(env_config: &EnvConfig) -> Option<Self> {
+        match (&env_config.telegram_bot_token, &env_config.telegram_chat_id) {
+            (Some(token), Some(chat_id)) => Some(Self::new(token.clone(), chat_id.clone())),
+            _ => None,
+        }
+    }
+    
+    /// Send a plain-text message
+    pub async fn send(&self, text: &str) -> Result<()> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        
+        let _permit = self.http.permit().await;
+        self.http
+            .post(&url)
+            .json(&serde_json::json!({
+                "chat_id": self.chat_id,
+                "text": text,
+            }))
+            .send()
+            .await
+            .context("Failed to send Telegram message")?
+            .error_for_status()
+            .context("Telegram rejected message")?;
+        
+        Ok(())
+    }
+}
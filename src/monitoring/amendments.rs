@@ -0,0 +1,84 @@
+use anyhow::Result;
+use crate::data::types::Market;
+use crate::execution::store::PositionStore;
+use crate::execution::types::Position;
+use tracing::error;
+
+/// Tag added to positions whose market wording changed after entry
+pub const QUESTION_CHANGED_TAG: &str = "question_changed";
+
+/// Whether the market's question/criteria differ from what the position was opened on
+/// Positions opened before hashes were recorded are never flagged
+pub fn question_changed(position: &Position, market: &Market) -> bool {
+    position
+        .question_hash
+        .as_ref()
+        .is_some_and(|entry_hash| *entry_hash != market.criteria_hash())
+}
+
+/// Tag a position for review if its market was amended; returns whether it was flagged
+pub fn flag_if_amended(store: &dyn PositionStore, position: &Position, market: &Market) -> Result<bool> {
+    if !question_changed(position, market) || position.tags.iter().any(|t| t == QUESTION_CHANGED_TAG) {
+        return Ok(false);
+    }
+    let Some(id) = position.id else {
+        return Ok(false);
+    };
+    
+    error!(
+        "⚠️ Market {} changed its question/criteria since position {} was opened: now \"{}\"",
+        market.id, id, market.question
+    );
+    
+    let mut tags = position.tags.clone();
+    tags.push(QUESTION_CHANGED_TAG.to_string());
+    let note = format!("Question changed after entry; now: {}", market.question);
+    let notes = match &position.notes {
+        Some(existing) => format!("{}\n{}", existing, note),
+        None => note,
+    };
+    store.update_position_annotations(id, &tags, Some(&notes))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::persistence::PositionDatabase;
+    
+    #[test]
+    fn test_changed_question_hash_flags_position() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let mut market: Market = serde_json::from_value(serde_json::json!({
+            "id": "0xamend",
+            "question": "Will London temperature exceed 15°C on 2026-02-17?",
+            "end_date": "2026-02-17T00:00:00Z",
+            "yes_price": 0.5, "yes_ask": 0.5, "no_ask": 0.5,
+            "volume_24h": 10000.0, "yes_liquidity": 5000.0, "no_liquidity": 5000.0,
+            "closed": false, "share_granularity": null,
+            "description": "Resolves on the Heathrow station high."
+        }))
+        .unwrap();
+        
+        let mut position = Position {
+            side: None,
+            question_hash: Some(market.criteria_hash()),
+            ..Position::fixture("0xamend")
+        };
+        position.id = Some(db.insert_position(&position).unwrap());
+        
+        // Cosmetic whitespace/case edits are not amendments
+        market.question = "Will London temperature  exceed 15°C on 2026-02-17?".to_string();
+        assert!(!flag_if_amended(&db, &position, &market).unwrap());
+        
+        market.question = "Will London temperature exceed 17°C on 2026-02-17?".to_string();
+        assert!(flag_if_amended(&db, &position, &market).unwrap());
+        
+        let flagged = db.get_positions_by_tag(QUESTION_CHANGED_TAG).unwrap();
+        assert_eq!(flagged.len(), 1);
+        assert!(flagged[0].notes.as_deref().unwrap().contains("17°C"));
+        
+        // Already flagged: not flagged again
+        assert!(!flag_if_amended(&db, &flagged[0], &market).unwrap());
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use crate::data::types::ProbabilisticForecast;
+use crate::execution::store::PositionReader;
+use crate::execution::types::Position;
+use crate::strategies::types::Side;
+use crate::math::format::fmt_usd;
+use tracing::warn;
+
+/// Expected profit of holding `position` to resolution, net of the fee on winnings
+///
+/// The forecast's edge over the entry price is weighted by its confidence, so an
+/// uncertain forecast can't keep a position looking profitable on its own. The fee is
+/// charged on profit (payout less cost) and only when the position wins.
+pub fn expected_value(position: &Position, current_forecast: &ProbabilisticForecast, fee_rate: f64) -> f64 {
+    let (win_prob, shares) = match position.side {
+        Some(Side::No) => (1.0 - current_forecast.probability, position.no_shares),
+        _ => (current_forecast.probability, position.yes_shares),
+    };
+    
+    let confidence = current_forecast.confidence.clamp(0.0, 1.0);
+    let effective_prob = position.entry_price + (win_prob - position.entry_price) * confidence;
+    
+    let fee = fee_rate * (shares - position.cost).max(0.0);
+    effective_prob * (shares - fee) - position.cost
+}
+
+/// Open positions whose current EV is negative, with that EV (worst first)
+/// Positions without a current forecast are skipped
+pub fn negative_ev_positions(
+    db: &impl PositionReader,
+    forecasts: &HashMap<String, ProbabilisticForecast>,
+    fee_rate: f64,
+) -> Result<Vec<(Position, f64)>> {
+    let mut flagged: Vec<(Position, f64)> = db
+        .get_open_positions()?
+        .into_iter()
+        .filter_map(|position| {
+            let forecast = forecasts.get(&position.market_id)?;
+            let ev = expected_value(&position, forecast, fee_rate);
+            (ev < 0.0).then_some((position, ev))
+        })
+        .collect();
+    flagged.sort_by(|a, b| a.1.total_cmp(&b.1));
+    
+    for (position, ev) in &flagged {
+        warn!(
+            "Position {:?} on {} has negative EV: ${} (cost ${})",
+            position.id, position.market_id, fmt_usd(*ev), fmt_usd(position.cost)
+        );
+    }
+    Ok(flagged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::persistence::PositionDatabase;
+    
+    fn forecast(probability: f64) -> ProbabilisticForecast {
+        ProbabilisticForecast {
+            probability,
+            confidence: 0.9,
+            mean_temp: 30.0,
+            std_dev: 1.5,
+            model: "test".to_string(),
+        }
+    }
+    
+    #[test]
+    fn test_position_turning_negative_ev_is_flagged() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let position = Position::fixture("0xabc");
+        db.insert_position(&position).unwrap();
+        
+        // At entry the forecast backed the trade
+        assert!(expected_value(&position, &forecast(0.60), 0.02) > 0.0);
+        let mut forecasts = HashMap::new();
+        forecasts.insert("0xabc".to_string(), forecast(0.60));
+        assert!(negative_ev_positions(&db, &forecasts, 0.02).unwrap().is_empty());
+        
+        // Closer to resolution the forecast has moved against it
+        forecasts.insert("0xabc".to_string(), forecast(0.30));
+        let flagged = negative_ev_positions(&db, &forecasts, 0.02).unwrap();
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].0.market_id, "0xabc");
+        assert!(flagged[0].1 < 0.0);
+        
+        // Fees alone can tip a break-even position negative: 40% x ($100 - 2% of $60 profit) - $40
+        assert!((expected_value(&position, &forecast(0.40), 0.02) - (-0.48)).abs() < 1e-9);
+    }
+}
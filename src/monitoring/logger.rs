@@ -2,7 +2,8 @@ use anyhow::Result;
 use chrono::Utc;
 use std::fs::OpenOptions;
 use std::io::Write;
-use crate::execution::types::Position;
+use crate::execution::types::{LedgerEntry, Position};
+use crate::math::format::{fmt_price, fmt_usd};
 
 pub struct CsvLogger {
     log_path: String,
@@ -37,21 +38,18 @@ impl CsvLogger {
             None => "BOTH".to_string(),
         };
         
-        let pnl_str = match position.pnl {
-            Some(pnl) => format!("{:.2}", pnl),
-            None => "".to_string(),
-        };
+        let pnl_str = position.pnl.map(fmt_usd).unwrap_or_default();
         
         writeln!(
             file,
-            "{},{},{},{},{:.3},{:.2},{:.2},{},{}",
+            "{},{},{},{},{},{:.2},{},{},{}",
             position.opened_at.to_rfc3339(),
             position.market_id,
             position.strategy,
             side_str,
-            position.entry_price,
+            fmt_price(position.entry_price),
             position.yes_shares + position.no_shares,
-            position.cost,
+            fmt_usd(position.cost),
             pnl_str,
             position.status
         )?;
@@ -75,3 +73,31 @@ impl CsvLogger {
         Ok(())
     }
 }
+
+/// Write a paper-trading ledger as CSV
+pub fn export_ledger_csv(entries: &[LedgerEntry], path: &str) -> Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    writeln!(file, "timestamp,cause,market_id,position_id,amount,balance_after")?;
+    
+    for entry in entries {
+        writeln!(
+            file,
+            "{},{},{},{},{},{}",
+            entry.timestamp.to_rfc3339(),
+            entry.cause.as_str(),
+            entry.market_id.as_deref().unwrap_or(""),
+            entry.position_id.map(|id| id.to_string()).unwrap_or_default(),
+            fmt_usd(entry.amount),
+            fmt_usd(entry.balance_after)
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Write a paper-trading ledger as a JSON array
+pub fn export_ledger_json(entries: &[LedgerEntry], path: &str) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    serde_json::to_writer_pretty(file, entries)?;
+    Ok(())
+}
@@ -1,3 +1,7 @@
 pub mod logger;
 pub mod metrics;
 pub mod alerts;
+pub mod summary;
+pub mod ev;
+pub mod amendments;
+pub mod scale_out;
@@ -0,0 +1,140 @@
+use anyhow::Result;
+use crate::execution::persistence::PositionDatabase;
+use crate::execution::simulator::{PaperTradingSimulator, PriceSource};
+use crate::execution::store::PositionStore;
+use crate::execution::types::Position;
+use crate::strategies::types::Side;
+use crate::math::format::{fmt_pct, fmt_price, fmt_usd};
+use tracing::info;
+
+/// Tag added once a position has been partially closed, so it's only scaled out once
+pub const SCALED_OUT_TAG: &str = "scaled_out";
+
+/// A recommended partial close of a winning position
+#[derive(Debug, Clone)]
+pub struct ScaleOut {
+    pub position: Position,
+    /// Current price of the held side
+    pub mark: f64,
+    /// Unrealized gain as a fraction of cost
+    pub gain_pct: f64,
+    pub shares: f64,
+}
+
+/// Mark-to-market gain of a position as a fraction of its cost
+pub fn unrealized_gain_pct(position: &Position, mark: f64) -> f64 {
+    if position.cost <= 0.0 {
+        return 0.0;
+    }
+    (held_shares(position) * mark - position.cost) / position.cost
+}
+
+fn held_shares(position: &Position) -> f64 {
+    match position.side {
+        Some(Side::No) => position.no_shares,
+        _ => position.yes_shares,
+    }
+}
+
+/// Open positions up at least `min_gain_pct`, each with `fraction` of its shares to sell
+/// Positions without a current price, or already scaled out, are skipped
+pub fn scale_out_candidates(
+    db: &PositionDatabase,
+    prices: &impl PriceSource,
+    min_gain_pct: f64,
+    fraction: f64,
+) -> Result<Vec<ScaleOut>> {
+    Ok(db
+        .get_open_positions()?
+        .into_iter()
+        .filter(|p| !p.tags.iter().any(|t| t == SCALED_OUT_TAG))
+        .filter_map(|position| {
+            let side = position.side.clone().unwrap_or(Side::Yes);
+            let mark = prices.price(&position.market_id, &side)?;
+            let gain_pct = unrealized_gain_pct(&position, mark);
+            let shares = held_shares(&position) * fraction.clamp(0.0, 1.0);
+            (gain_pct >= min_gain_pct && shares > 0.0).then_some(ScaleOut { position, mark, gain_pct, shares })
+        })
+        .collect())
+}
+
+/// Sell the recommended shares at the mark (the held side's bid); returns the proceeds
+/// The sale is ledgered against the position as a realized leg and its cost basis is left alone;
+/// settlement adds the proceeds back, so P&L still covers the whole trade
+pub fn apply_scale_out(store: &dyn PositionStore, simulator: &PaperTradingSimulator, scale_out: &ScaleOut) -> Result<f64> {
+    let position = &scale_out.position;
+    let Some(id) = position.id else {
+        return Ok(0.0);
+    };
+    
+    let side = position.side.clone().unwrap_or(Side::Yes);
+    let proceeds = simulator.sell_holdings(id, &position.market_id, &side, scale_out.shares, scale_out.mark);
+    let (yes_shares, no_shares) = match position.side {
+        Some(Side::No) => (position.yes_shares, position.no_shares - scale_out.shares),
+        _ => (position.yes_shares - scale_out.shares, position.no_shares),
+    };
+    store.reduce_position(id, yes_shares, no_shares, position.cost)?;
+    
+    let mut tags = position.tags.clone();
+    tags.push(SCALED_OUT_TAG.to_string());
+    let note = format!(
+        "Scaled out {} shares at ${} (+{}%) for ${}",
+        scale_out.shares, fmt_price(scale_out.mark), fmt_pct(scale_out.gain_pct), fmt_usd(proceeds)
+    );
+    let notes = match &position.notes {
+        Some(existing) => format!("{}\n{}", existing, note),
+        None => note.clone(),
+    };
+    store.update_position_annotations(id, &tags, Some(&notes))?;
+    
+    info!("💰 Position {} on {}: {}", id, position.market_id, note);
+    Ok(proceeds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PaperTradingConfig;
+    use crate::execution::settlement::{settle_resolved_positions, Resolution};
+    use chrono::{Duration, Utc};
+    
+    #[test]
+    fn test_position_past_profit_take_is_partially_closed() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let position = Position::fixture("0xwin");
+        db.insert_position(&position).unwrap();
+        let simulator = PaperTradingSimulator::new(PaperTradingConfig::default(), 1.0)
+            .with_open_positions(std::slice::from_ref(&position));
+        
+        // Up 25%: below a 50% profit-take
+        let price = |mark: f64| move |_: &str, _: &Side| Some(mark);
+        assert!(scale_out_candidates(&db, &price(0.50), 0.50, 0.5).unwrap().is_empty());
+        
+        // Repriced to 0.70: up 75%, sell half
+        let candidates = scale_out_candidates(&db, &price(0.70), 0.50, 0.5).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert!((candidates[0].gain_pct - 0.75).abs() < 1e-9);
+        assert_eq!(candidates[0].shares, 50.0);
+        
+        let balance = simulator.balance();
+        let proceeds = apply_scale_out(&db, &simulator, &candidates[0]).unwrap();
+        assert!((proceeds - 35.0).abs() < 1e-9);
+        assert!((simulator.balance() - balance - 35.0).abs() < 1e-9);
+        
+        let open = &db.get_open_positions().unwrap()[0];
+        assert_eq!(open.yes_shares, 50.0);
+        assert_eq!(open.cost, 40.0);
+        assert!(open.tags.contains(&SCALED_OUT_TAG.to_string()));
+        
+        // Only scaled out once
+        assert!(scale_out_candidates(&db, &price(0.90), 0.50, 0.5).unwrap().is_empty());
+        
+        // YES wins: $50 payout + $35 sale - $40 cost
+        for entry in simulator.take_ledger() {
+            db.record_ledger_entry(&entry).unwrap();
+        }
+        let resolution = Resolution { market_id: "0xwin".to_string(), winner: Side::Yes, resolved_at: Utc::now() };
+        settle_resolved_positions(&db, &db, &simulator, &[resolution], Duration::zero(), Utc::now()).unwrap();
+        assert_eq!(db.get_closed_positions().unwrap()[0].pnl, Some(45.0));
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, NaiveTime, Utc};
+use std::fmt;
+use crate::execution::persistence::PositionDatabase;
+use crate::execution::store::PositionReader;
+use crate::math::money::sum_money;
+use crate::monitoring::alerts::TelegramAlerter;
+use crate::math::format::{fmt_pct, fmt_usd};
+use tracing::{info, warn};
+
+/// Trading performance over one UTC day
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerformanceSummary {
+    pub date: NaiveDate,
+    pub trades: usize,
+    pub closed: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub win_rate: Option<f64>,
+    pub realized_pnl: f64,
+    pub open_positions: usize,
+    pub breaker_trips: usize,
+    /// Set when the bot started partway through the day
+    pub covered_from: Option<DateTime<Utc>>,
+}
+
+impl PerformanceSummary {
+    /// Compute the summary for `date`, noting partial coverage if the bot started that day
+    pub fn for_day(
+        db: &impl PositionReader,
+        date: NaiveDate,
+        started_at: DateTime<Utc>,
+    ) -> Result<Self> {
+        let pnls = db.get_closed_pnls_on(date)?;
+        let wins = pnls.iter().filter(|pnl| **pnl > 0.0).count();
+        let losses = pnls.iter().filter(|pnl| **pnl < 0.0).count();
+        
+        let win_rate = if pnls.is_empty() {
+            None
+        } else {
+            Some(wins as f64 / pnls.len() as f64)
+        };
+        
+        let covered_from = if started_at.date_naive() == date {
+            Some(started_at)
+        } else {
+            None
+        };
+        
+        Ok(Self {
+            date,
+            trades: db.count_trades_on(date)?,
+            closed: pnls.len(),
+            wins,
+            losses,
+            win_rate,
+            realized_pnl: sum_money(pnls.iter().copied()),
+            open_positions: db.count_open_positions()?,
+            breaker_trips: db.count_circuit_breaker_events_on(date)?,
+            covered_from,
+        })
+    }
+}
+
+impl fmt::Display for PerformanceSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "📊 Daily summary for {}", self.date)?;
+        if let Some(from) = self.covered_from {
+            writeln!(f, "(partial day: running since {} UTC)", from.format("%H:%M"))?;
+        }
+        writeln!(f, "Trades opened:  {}", self.trades)?;
+        writeln!(f, "Closed:         {} ({}W / {}L)", self.closed, self.wins, self.losses)?;
+        match self.win_rate {
+            Some(rate) => writeln!(f, "Win rate:       {}%", fmt_pct(rate))?,
+            None => writeln!(f, "Win rate:       n/a")?,
+        }
+        writeln!(f, "Realized P&L:   ${}", fmt_usd(self.realized_pnl))?;
+        writeln!(f, "Open positions: {}", self.open_positions)?;
+        write!(f, "Breaker trips:  {}", self.breaker_trips)
+    }
+}
+
+/// Next time the summary is due after `now`: today at `at` (UTC) if still ahead, else tomorrow
+fn next_summary_at(now: DateTime<Utc>, at: NaiveTime) -> DateTime<Utc> {
+    let today = now.date_naive().and_time(at).and_utc();
+    if today > now {
+        today
+    } else {
+        today + ChronoDuration::days(1)
+    }
+}
+
+/// Emit a summary of the last complete UTC day (the daily limits' day) each day at `at` UTC
+/// Opens its own connection so it doesn't contend with the engine's
+pub async fn run_daily_summary_task(
+    db_path: String,
+    alerter: Option<TelegramAlerter>,
+    at: NaiveTime,
+    started_at: DateTime<Utc>,
+) -> Result<()> {
+    let db = PositionDatabase::open_readonly(&db_path)?;
+    
+    loop {
+        let now = Utc::now();
+        let due = next_summary_at(now, at);
+        tokio::time::sleep((due - now).to_std().unwrap_or_default()).await;
+        
+        let day = due.date_naive() - ChronoDuration::days(1);
+        let summary = match PerformanceSummary::for_day(&db, day, started_at) {
+            Ok(summary) => summary,
+            Err(e) => {
+                // A locked or busy database shouldn't end the task; try again tomorrow
+                warn!("Failed to compute daily summary for {}: {}", day, e);
+                continue;
+            }
+        };
+        info!("\n{}", summary);
+        
+        if let Some(alerter) = &alerter {
+            if let Err(e) = alerter.send(&summary.to_string()).await {
+                warn!("Failed to send daily summary: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::types::Position;
+    
+    fn position(market_id: &str) -> Position {
+        Position {
+            yes_shares: 80.0,
+            entry_price: 0.50,
+            ..Position::fixture(market_id)
+        }
+    }
+    
+    #[test]
+    fn test_summary_fires_at_configured_time() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let six = NaiveTime::from_hms_opt(6, 0, 0).unwrap();
+        
+        assert_eq!(next_summary_at(at("2026-02-17T03:00:00Z"), six), at("2026-02-17T06:00:00Z"));
+        assert_eq!(next_summary_at(at("2026-02-17T06:00:00Z"), six), at("2026-02-18T06:00:00Z"));
+        assert_eq!(next_summary_at(at("2026-02-17T23:59:00Z"), NaiveTime::MIN), at("2026-02-18T00:00:00Z"));
+    }
+    
+    #[test]
+    fn test_summary_for_synthetic_day() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        
+        let win = db.insert_position(&position("win")).unwrap();
+        let loss = db.insert_position(&position("loss")).unwrap();
+        let other_win = db.insert_position(&position("win-2")).unwrap();
+        db.insert_position(&position("still-open")).unwrap();
+        
+        db.update_position_status(win, "closed", Some(40.0)).unwrap();
+        db.update_position_status(loss, "closed", Some(-40.0)).unwrap();
+        db.update_position_status(other_win, "closed", Some(10.0)).unwrap();
+        db.log_circuit_breaker_event("Latency(6s)", None).unwrap();
+        
+        let today = Utc::now().date_naive();
+        let started_long_ago = Utc::now() - ChronoDuration::days(3);
+        let summary = PerformanceSummary::for_day(&db, today, started_long_ago).unwrap();
+        
+        assert_eq!(summary.date, today);
+        assert_eq!(summary.trades, 4);
+        assert_eq!(summary.closed, 3);
+        assert_eq!(summary.wins, 2);
+        assert_eq!(summary.losses, 1);
+        assert!((summary.win_rate.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert!((summary.realized_pnl - 10.0).abs() < 1e-9);
+        assert_eq!(summary.open_positions, 1);
+        assert_eq!(summary.breaker_trips, 1);
+        assert!(summary.covered_from.is_none());
+    }
+    
+    #[test]
+    fn test_first_partial_day_is_flagged() {
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let started_at = Utc::now();
+        
+        let summary = PerformanceSummary::for_day(&db, started_at.date_naive(), started_at).unwrap();
+        
+        assert_eq!(summary.covered_from, Some(started_at));
+        assert_eq!(summary.win_rate, None);
+        assert!(summary.to_string().contains("partial day"));
+    }
+}
@@ -1 +1,195 @@
-// TODO: Implement sum_to_one
+use anyhow::Result;
+use crate::config::{ArbitrageStrategyConfig, LeggedAction};
+use crate::data::cache::PriceCache;
+use crate::data::types::Market;
+use crate::execution::persistence::PositionDatabase;
+use crate::execution::risk::{CircuitBreaker, CircuitBreakerReason};
+use crate::execution::simulator::PaperTradingSimulator;
+use crate::execution::types::{Fill, Order, OrderType, Token};
+use crate::math::format::fmt_price;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// How a YES + NO arb basket ended up
+#[derive(Debug, Clone)]
+pub enum ArbOutcome {
+    /// Both legs filled as submitted
+    Filled { yes: Fill, no: Fill },
+    /// One leg missed and was completed by crossing the spread
+    Hedged { yes: Fill, no: Fill },
+    /// One leg missed and the other was sold back
+    Unwound { filled: Fill, proceeds: f64 },
+    /// Neither leg filled; nothing held
+    NotFilled,
+}
+
+/// Cache key for a market's ask on one token
+fn ask_key(market_id: &str, token: &Token) -> String {
+    format!("{}:{:?}:ask", market_id, token)
+}
+
+/// Remember a priced market's asks for arb pricing
+pub fn cache_asks(cache: &PriceCache, market: &Market) {
+    cache.insert(ask_key(&market.id, &Token::Yes), market.yes_ask, "sum_to_one_arb");
+    cache.insert(ask_key(&market.id, &Token::No), market.no_ask, "sum_to_one_arb");
+}
+
+/// (YES, NO) asks to price an arb on, only if both were quoted within `max_age`
+/// (`max_age_for_trade_arb_ms`): an older quote may already be gone from the book
+pub fn fresh_arb_asks(cache: &PriceCache, market_id: &str, max_age: Duration) -> Option<(f64, f64)> {
+    let yes = cache.get_fresh(&ask_key(market_id, &Token::Yes), max_age)?;
+    let no = cache.get_fresh(&ask_key(market_id, &Token::No), max_age)?;
+    Some((yes, no))
+}
+
+/// Submit both legs of a sum-to-one arb, handling a legged fill per `config.on_legged`
+/// An unwind trips the breaker (`LeggedPositionStuck`) for manual review
+pub async fn execute_arb(
+    simulator: &PaperTradingSimulator,
+    breaker: &CircuitBreaker,
+    db: &PositionDatabase,
+    yes_order: &Order,
+    no_order: &Order,
+    config: &ArbitrageStrategyConfig,
+) -> Result<ArbOutcome> {
+    let yes_fill = simulator.execute_order(yes_order).await?.fill();
+    let no_fill = simulator.execute_order(no_order).await?.fill();
+    
+    let (filled, missing, filled_is_yes) = match (yes_fill, no_fill) {
+        (Some(yes), Some(no)) => return Ok(ArbOutcome::Filled { yes, no }),
+        (None, None) => return Ok(ArbOutcome::NotFilled),
+        (Some(yes), None) => (yes, no_order, true),
+        (None, Some(no)) => (no, yes_order, false),
+    };
+    warn!("Arb on {} legged: {:?} leg missed", missing.market_id, missing.token);
+    
+    if config.on_legged == LeggedAction::Hedge {
+        // Pay up to the tolerance, but never past break-even for the basket
+        let limit = (missing.price + config.hedge_price_tolerance).min(1.0 - filled.price);
+        if limit > 0.0 {
+            let hedge = Order {
+                price: limit,
+                order_type: OrderType::FOK,
+                improvement_ticks: 0,
+                ..missing.clone()
+            };
+            if let Some(hedge_fill) = simulator.execute_hedge(&hedge).await?.fill() {
+                info!("Hedged legged arb on {} at ${}", missing.market_id, fmt_price(hedge_fill.price));
+                let (yes, no) = if filled_is_yes {
+                    (filled, hedge_fill)
+                } else {
+                    (hedge_fill, filled)
+                };
+                return Ok(ArbOutcome::Hedged { yes, no });
+            }
+            warn!("Hedge for {} at ${} failed, unwinding", missing.market_id, fmt_price(limit));
+        }
+    }
+    
+    let proceeds = simulator.unwind(&filled);
+    breaker.trigger(CircuitBreakerReason::LeggedPositionStuck, db)?;
+    Ok(ArbOutcome::Unwound { filled, proceeds })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::strategies::types::Side;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+    
+    /// Arb legs where the NO leg never fills on first submission
+    fn legs() -> (Order, Order) {
+        let yes = Order {
+            market_id: "0xarb".to_string(),
+            side: Side::Yes,
+            token: Token::Yes,
+            price: 0.47,
+            size: 50.0,
+            order_type: OrderType::FOK,
+            improvement_ticks: 0,
+        };
+        let no = Order {
+            side: Side::No,
+            token: Token::No,
+            price: 0.50,
+            improvement_ticks: 1,
+            ..yes.clone()
+        };
+        (yes, no)
+    }
+    
+    fn simulator(hedge_fill_rate: f64) -> PaperTradingSimulator {
+        let mut config = Config::for_test().paper_trading;
+        config.fill_rate = 1.0;
+        config.improved_fill_decay = 0.0; // Improved orders never fill
+        config.hedge_fill_rate = hedge_fill_rate;
+        config.seed = Some(7);
+        PaperTradingSimulator::new(config, 1.0)
+    }
+    
+    #[tokio::test]
+    async fn test_legged_arb_hedges_without_unwind_or_breaker() {
+        let mut config = Config::for_test().strategies.arbitrage;
+        config.on_legged = LeggedAction::Hedge;
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let breaker = CircuitBreaker::new();
+        let sim = simulator(1.0);
+        let (yes, no) = legs();
+        
+        let outcome = execute_arb(&sim, &breaker, &db, &yes, &no, &config).await.unwrap();
+        let ArbOutcome::Hedged { yes: yes_fill, no: no_fill } = outcome else {
+            panic!("expected a hedge, got {:?}", outcome);
+        };
+        assert!((no_fill.price - 0.51).abs() < 1e-9);
+        assert!(yes_fill.price + no_fill.price <= 1.0);
+        assert!(!breaker.is_triggered());
+        
+        // Default behavior unwinds and trips the breaker
+        config.on_legged = LeggedAction::Unwind;
+        let outcome = execute_arb(&sim, &breaker, &db, &yes, &no, &config).await.unwrap();
+        assert!(matches!(outcome, ArbOutcome::Unwound { .. }));
+        assert!(breaker.is_triggered());
+    }
+    
+    #[test]
+    fn test_arb_is_not_priced_off_aging_quotes() {
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock = now.clone();
+        let cache = PriceCache::new().with_clock(move || *clock.lock().unwrap());
+        let max_age = Duration::from_millis(Config::for_test().infrastructure.max_age_for_trade_arb_ms);
+        
+        let market: Market = serde_json::from_value(serde_json::json!({
+            "id": "0xarb", "question": "", "end_date": "2026-02-17T00:00:00Z",
+            "yes_price": 0.47, "yes_ask": 0.47, "no_ask": 0.50,
+            "volume_24h": 0.0, "yes_liquidity": 0.0, "no_liquidity": 0.0,
+            "closed": false, "share_granularity": null
+        }))
+        .unwrap();
+        cache_asks(&cache, &market);
+        assert_eq!(fresh_arb_asks(&cache, "0xarb", max_age), Some((0.47, 0.50)));
+        
+        // Still cached, but older than the trade threshold
+        *now.lock().unwrap() += max_age + Duration::from_millis(1);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(fresh_arb_asks(&cache, "0xarb", max_age), None);
+    }
+    
+    #[tokio::test]
+    async fn test_failed_hedge_falls_back_to_unwind() {
+        let mut config = Config::for_test().strategies.arbitrage;
+        config.on_legged = LeggedAction::Hedge;
+        let db = PositionDatabase::new(":memory:").unwrap();
+        let breaker = CircuitBreaker::new();
+        let sim = simulator(0.0);
+        let (yes, no) = legs();
+        
+        let outcome = execute_arb(&sim, &breaker, &db, &yes, &no, &config).await.unwrap();
+        let ArbOutcome::Unwound { filled, proceeds } = outcome else {
+            panic!("expected an unwind, got {:?}", outcome);
+        };
+        assert!(proceeds < filled.cost);
+        assert!(breaker.is_triggered());
+    }
+}
@@ -1,22 +1,55 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+/// Serialized as "YES" / "NO", as stored in the database
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum Side {
     Yes,
     No,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Serialized by `name()`, e.g. "weather_edge"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Strategy {
     WeatherEdge,
     SumToOneArb,
 }
 
-#[derive(Debug, Clone)]
+impl Strategy {
+    /// Name stored in the `strategy` column of positions
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::WeatherEdge => "weather_edge",
+            Strategy::SumToOneArb => "sum_to_one_arb",
+        }
+    }
+}
+
+/// How a signal's order is priced
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderIntent {
+    /// Rest at `entry_price` until filled (GTC)
+    Limit,
+    /// Take up to `entry_price` now or not at all (FOK): a marketable limit, never a worse fill
+    FillOrKill,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Signal {
     pub market_id: String,
     pub strategy: Strategy,
     pub side: Option<Side>,
     pub entry_price: f64,
+    /// Rest at `entry_price`, or fill at up to it immediately
+    pub intent: OrderIntent,
     pub size: f64,
     pub edge: Option<f64>,
     pub confidence: f64,
+    /// Underlying event shared by correlated markets (weather only)
+    pub event_key: Option<String>,
+    /// Market activity at signal time, for the volume/liquidity gates
+    pub market_volume_24h: Option<f64>,
+    pub market_liquidity: Option<f64>,
 }
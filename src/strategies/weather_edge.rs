@@ -1,28 +1,171 @@
 use anyhow::Result;
-use crate::config::WeatherStrategyConfig;
-use crate::data::types::Market;
-use crate::data::weather::WeatherClient;
-use crate::data::gamma_api::{parse_weather_question, Comparison};
-use crate::strategies::types::{Signal, Side, Strategy};
+use chrono::{DateTime, Utc};
+use crate::config::{EdgeBasis, EntryOrder, ProbabilityBounds, SourcesFailedAction, WeatherStrategyConfig};
+use crate::data::types::{BookLevel, ForecastKind, Market, ProbabilisticForecast};
+use crate::data::weather::{probability_above, ForecastProvider, NoaaProvider, OpenMeteoProvider, WeatherClient};
+use crate::data::gamma_api::{
+    check_outcome_alignment, parse_weather_question, Comparison, ParseOptions, UnsupportedIndex, UnsupportedLanguage,
+    WeatherMarketInfo,
+};
+use crate::execution::types::{DeadLetter, Rejection};
+use crate::strategies::types::{OrderIntent, Signal, Side, Strategy};
+use crate::math::distributions::normal_interval_prob;
+use crate::math::format::{fmt_pct, fmt_price, fmt_usd};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
 use tracing::{info, warn};
 
+/// Forecasts further apart than this (in probability) aren't traded
+pub const MAX_FORECAST_DISAGREEMENT: f64 = 0.10;
+
+/// A source skipped because it's about to publish an update; not a failure
+#[derive(Debug, thiserror::Error)]
+#[error("{source_name} is within {minutes} minutes of a scheduled update, deferring")]
+pub struct ForecastDeferred {
+    source_name: String,
+    minutes: u32,
+}
+
+fn is_deferral(e: &anyhow::Error) -> bool {
+    e.is::<ForecastDeferred>()
+}
+
+/// Forecasts that passed the agreement, sigma, lead-time and confidence gates, blended
+#[derive(Debug, Clone, Copy)]
+pub struct ForecastBlend {
+    /// Mean P(temp > threshold) across sources
+    pub probability: f64,
+    pub mean_temp: f64,
+    pub std_dev: f64,
+    /// After the single-source and lead-time factors
+    pub confidence: f64,
+    /// Multiplier on Kelly size from the same factors
+    pub size_factor: f64,
+}
+
 pub struct WeatherEdgeStrategy {
     config: WeatherStrategyConfig,
-    weather_client: WeatherClient,
+    /// Built once from `config`, templates compiled
+    parse_options: ParseOptions,
+    /// Forecasts are averaged across however many providers there are, and must all agree
+    providers: Vec<Box<dyn ForecastProvider>>,
+    /// Markets declined since the last `take_rejections`
+    rejections: Mutex<Vec<Rejection>>,
+    /// Questions that failed to parse since last taken
+    dead_letters: Mutex<Vec<DeadLetter>>,
+    /// Consecutive losing trades, refreshed by the engine each cycle
+    loss_streak: AtomicU32,
+    /// Analyses in a row where every forecast source failed
+    sources_failed_streak: AtomicU32,
 }
 
 impl WeatherEdgeStrategy {
-    pub fn new(config: WeatherStrategyConfig, weather_client: WeatherClient) -> Self {
-        Self {
+    /// NOAA cross-validated against Open-Meteo
+    pub fn new(config: WeatherStrategyConfig, weather_client: WeatherClient) -> Result<Self> {
+        Self::with_providers(
             config,
-            weather_client,
+            vec![
+                Box::new(NoaaProvider(weather_client.clone())),
+                Box::new(OpenMeteoProvider(weather_client)),
+            ],
+        )
+    }
+    
+    /// Errors if a question template doesn't compile (already rejected at config load)
+    pub fn with_providers(config: WeatherStrategyConfig, providers: Vec<Box<dyn ForecastProvider>>) -> Result<Self> {
+        Ok(Self {
+            parse_options: ParseOptions::from_config(&config)?,
+            config,
+            providers,
+            rejections: Mutex::new(Vec::new()),
+            dead_letters: Mutex::new(Vec::new()),
+            loss_streak: AtomicU32::new(0),
+            sources_failed_streak: AtomicU32::new(0),
+        })
+    }
+    
+    /// Update the number of losing trades in a row (from closed-position P&L)
+    pub fn set_loss_streak(&self, losses: u32) {
+        self.loss_streak.store(losses, Ordering::Relaxed);
+    }
+    
+    /// Order intent for a new position, per `entry_order`
+    fn entry_intent(&self) -> OrderIntent {
+        match self.config.entry_order {
+            EntryOrder::Limit => OrderIntent::Limit,
+            EntryOrder::Market => OrderIntent::FillOrKill,
         }
     }
     
+    /// Size multiplier from the current losing streak (1.0 unless de-sized)
+    fn loss_streak_multiplier(&self) -> f64 {
+        let threshold = self.config.loss_streak_trades;
+        let losses = self.loss_streak.load(Ordering::Relaxed);
+        if threshold > 0 && losses >= threshold {
+            self.config.loss_streak_size_multiplier
+        } else {
+            1.0
+        }
+    }
+    
+    /// Every forecast source failed: count it toward the breaker and skip
+    fn sources_failed(&self, market: Option<&Market>) {
+        let streak = self.sources_failed_streak.fetch_add(1, Ordering::Relaxed) + 1;
+        warn!("All forecast sources failed ({} in a row), skipping", streak);
+        if let Some(market) = market {
+            self.reject(market, None, None, "all_sources_failed", format!("{} in a row", streak));
+        }
+    }
+    
+    /// Every failing source is mid-update: skip until next cycle without counting toward the breaker
+    fn deferred(&self, market: Option<&Market>) {
+        info!("Forecast sources updating, deferring to next cycle");
+        if let Some(market) = market {
+            self.reject(market, None, None, "update_window", "sources mid-update".to_string());
+        }
+    }
+    
+    /// Start counting all-sources failures afresh, e.g. once the breaker they tripped is reset
+    pub fn clear_sources_failed_streak(&self) {
+        self.sources_failed_streak.store(0, Ordering::Relaxed);
+    }
+    
+    /// Consecutive all-sources failures, when configured to escalate and past the limit
+    pub fn sources_failed_escalation(&self) -> Option<u32> {
+        let streak = self.sources_failed_streak.load(Ordering::Relaxed);
+        let escalate = self.config.on_sources_failed == SourcesFailedAction::Breaker
+            && streak >= self.config.sources_failed_limit.max(1);
+        escalate.then_some(streak)
+    }
+    
+    /// Drain markets declined during analysis
+    pub fn take_rejections(&self) -> Vec<Rejection> {
+        std::mem::take(&mut *self.rejections.lock().unwrap())
+    }
+    
+    /// Unparseable questions since the last call, for the dead-letter log
+    pub fn take_dead_letters(&self) -> Vec<DeadLetter> {
+        std::mem::take(&mut *self.dead_letters.lock().unwrap())
+    }
+    
+    fn reject(&self, market: &Market, side: Option<Side>, edge: Option<f64>, reason: &str, detail: String) {
+        self.rejections.lock().unwrap().push(Rejection {
+            market_id: market.id.clone(),
+            strategy: Strategy::WeatherEdge.name().to_string(),
+            side,
+            entry_price: None,
+            size: None,
+            edge,
+            reason: reason.to_string(),
+            detail,
+        });
+    }
+    
     /// Analyze a weather market for trading opportunities
     /// This is the core strategy algorithm that combines:
     /// 1. NOAA probabilistic forecasts
-    /// 2. Open-Meteo cross-validation
+    /// 2. Open-Meteo cross-validation (or any other configured providers)
     /// 3. Edge calculation vs market price
     /// 4. Corrected Kelly position sizing
     pub async fn analyze_weather_market(
@@ -32,10 +175,26 @@ impl WeatherEdgeStrategy {
         max_position_pct: f64,
     ) -> Result<Option<Signal>> {
         // 1. Parse market question
-        let market_info = match parse_weather_question(&market.question) {
+        let parsed = parse_weather_question(&market.question, &self.parse_options);
+        let market_info = match parsed {
             Ok(info) => info,
+            Err(e) if e.downcast_ref::<UnsupportedLanguage>().is_some() => {
+                info!("Skipping non-English market {}: {}", market.id, market.question);
+                self.reject(market, None, None, "unsupported_language", market.question.clone());
+                return Ok(None);
+            }
+            Err(e) if e.downcast_ref::<UnsupportedIndex>().is_some() => {
+                info!("Skipping non-temperature market {}: {} ({})", market.id, market.question, e);
+                self.reject(market, None, None, "unsupported_index", e.to_string());
+                return Ok(None);
+            }
             Err(e) => {
                 warn!("Failed to parse market question: {} - {}", market.question, e);
+                self.dead_letters.lock().unwrap().push(DeadLetter {
+                    market_id: market.id.clone(),
+                    question: market.question.clone(),
+                    error: format!("{:#}", e),
+                });
                 return Ok(None);
             }
         };
@@ -45,91 +204,472 @@ impl WeatherEdgeStrategy {
             market_info.city, market_info.threshold
         );
         
-        // 2. Fetch NOAA probabilistic forecast
-        let noaa_forecast = self.weather_client
-            .fetch_probabilistic_forecast(&market_info.city, market_info.threshold)
-            .await?;
+        // No point fetching forecasts to compare against placeholder prices
+        if !market.priced {
+            info!("Market {} not yet priced, skipping", market.id);
+            self.reject(market, None, None, "unpriced", "no order book prices yet".to_string());
+            return Ok(None);
+        }
         
-        info!(
-            "NOAA forecast: {:.1}% probability (mean={:.1}°C, std_dev={:.1}°C)",
-            noaa_forecast.probability * 100.0,
-            noaa_forecast.mean_temp,
-            noaa_forecast.std_dev
-        );
+        // The forecast is for the parsed comparison; YES must pay out on that same outcome
+        if let Err(detail) = check_outcome_alignment(&market.outcomes, &market_info.comparison) {
+            warn!("Outcome labels don't match question for {}: {}", market.id, detail);
+            self.reject(market, None, None, "outcome_mismatch", detail);
+            return Ok(None);
+        }
         
-        // 3. Cross-validate with Open-Meteo
-        let open_meteo_forecast = self.weather_client
-            .fetch_open_meteo(&market_info.city, market_info.threshold)
-            .await?;
+        // 2-3. Fetch every provider's forecast (NOAA, cross-validated with Open-Meteo)
+        // Each provider gets its own timeout, so a slow one can't hold up the rest
+        let threshold = effective_threshold(&market_info, self.config.boundary_tolerance_c);
+        let city = market_info.city.as_str();
+        let resolution_time = market_info.resolution_time(market.end_date);
+        if !self.config.ensemble {
+            // Failover: the first source (in priority order) that answers is the only one used
+            let mut all_deferred = true;
+            for provider in self.providers_for(city) {
+                match self.fetch_forecast(provider, city, threshold, resolution_time).await {
+                    Ok(forecast) => {
+                        self.sources_failed_streak.store(0, Ordering::Relaxed);
+                        return self.evaluate_forecasts(market, &market_info, vec![Ok(forecast)], capital, max_position_pct);
+                    }
+                    Err(e) => {
+                        all_deferred &= is_deferral(&e);
+                        warn!("{} forecast failed, trying next source: {}", provider.name(), e);
+                    }
+                }
+            }
+            if all_deferred {
+                self.deferred(Some(market));
+            } else {
+                self.sources_failed(Some(market));
+            }
+            return Ok(None);
+        }
+        
+        let forecasts = self.fetch_agreeing_forecasts(city, threshold, resolution_time).await;
+        self.evaluate_forecasts(market, &market_info, forecasts, capital, max_position_pct)
+    }
+    
+    /// Every provider's forecast, re-fetched up to `disagreement_retries` times while they disagree
+    async fn fetch_agreeing_forecasts(
+        &self,
+        city: &str,
+        threshold: f64,
+        resolution_time: DateTime<Utc>,
+    ) -> Vec<Result<ProbabilisticForecast>> {
+        let mut forecasts = self.fetch_all_forecasts(city, threshold, resolution_time).await;
+        
+        // A stale source can cause a momentary gap; forecasts update, so look again before giving up
+        let retries = self.config.disagreement_retries;
+        for attempt in 1..=retries {
+            let spread = forecast_spread(&forecasts);
+            if spread <= MAX_FORECAST_DISAGREEMENT {
+                break;
+            }
+            info!(
+                "Forecasts differ by {}%, re-fetching in {}ms (retry {}/{})",
+                fmt_pct(spread), self.config.disagreement_retry_delay_ms, attempt, retries
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(self.config.disagreement_retry_delay_ms)).await;
+            forecasts = self.fetch_all_forecasts(city, threshold, resolution_time).await;
+        }
+        forecasts
+    }
+    
+    /// Blended forecast that `market` resolves YES, for re-checking a held position
+    /// None if the question doesn't parse or no source answers
+    pub async fn current_forecast(&self, market: &Market) -> Option<ProbabilisticForecast> {
+        let info = parse_weather_question(&market.question, &self.parse_options).ok()?;
+        let threshold = effective_threshold(&info, self.config.boundary_tolerance_c);
+        let forecasts: Vec<ProbabilisticForecast> = self
+            .fetch_all_forecasts(&info.city, threshold, info.resolution_time(market.end_date))
+            .await
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect();
+        if forecasts.is_empty() {
+            return None;
+        }
+        
+        let n = forecasts.len() as f64;
+        let average = |field: fn(&ProbabilisticForecast) -> f64| forecasts.iter().map(field).sum::<f64>() / n;
+        let (mean_temp, std_dev) = (average(|f| f.mean_temp), average(|f| f.std_dev));
+        let probability = match info.comparison {
+            Comparison::Above => average(|f| f.probability),
+            Comparison::Below => 1.0 - average(|f| f.probability),
+            Comparison::Exactly => {
+                let (low, high) = info.exact_band(self.config.exactly_half_width);
+                normal_interval_prob(low, high, mean_temp, std_dev)
+            }
+        };
+        
+        Some(ProbabilisticForecast {
+            probability,
+            confidence: average(|f| f.confidence),
+            mean_temp,
+            std_dev,
+            model: "blend".to_string(),
+        })
+    }
+    
+    /// Every provider's forecast, in registration order
+    async fn fetch_all_forecasts(
+        &self,
+        city: &str,
+        threshold: f64,
+        resolution_time: DateTime<Utc>,
+    ) -> Vec<Result<ProbabilisticForecast>> {
+        let fetches = self.providers
+            .iter()
+            .map(|provider| self.fetch_forecast(provider.as_ref(), city, threshold, resolution_time));
+        futures::future::join_all(fetches).await
+    }
+    
+    /// Fetch forecasts for an event's city the way single markets do (failover or ensemble),
+    /// put them through the same gates, and analyze its ladder
+    pub async fn analyze_event_ladder(
+        &self,
+        markets_for_event: &[Market],
+        held_market_ids: &[String],
+        capital: f64,
+        max_position_pct: f64,
+    ) -> Option<Signal> {
+        let (market, info) = markets_for_event
+            .iter()
+            .find_map(|m| parse_weather_question(&m.question, &self.parse_options).ok().map(|info| (m, info)))?;
+        let threshold = effective_threshold(&info, self.config.boundary_tolerance_c);
+        let resolution_time = info.resolution_time(market.end_date);
+        
+        // Only the distribution (mean, std dev) prices the ladder; agreement is judged at this threshold
+        let forecasts = if self.config.ensemble {
+            self.fetch_agreeing_forecasts(&info.city, threshold, resolution_time).await
+        } else {
+            let mut answer = None;
+            let mut all_deferred = true;
+            for provider in self.providers_for(&info.city) {
+                match self.fetch_forecast(provider, &info.city, threshold, resolution_time).await {
+                    Ok(forecast) => {
+                        answer = Some(forecast);
+                        break;
+                    }
+                    Err(e) => {
+                        all_deferred &= is_deferral(&e);
+                        warn!("{} forecast failed for ladder: {}", provider.name(), e);
+                    }
+                }
+            }
+            let Some(forecast) = answer else {
+                if all_deferred {
+                    self.deferred(None);
+                } else {
+                    self.sources_failed(None);
+                }
+                return None;
+            };
+            vec![Ok(forecast)]
+        };
+        
+        let blend = match self.blend_forecasts(market, forecasts) {
+            Ok(blend) => blend?,
+            Err(e) => {
+                warn!("Skipping ladder for {}: {}", info.city, e);
+                return None;
+            }
+        };
+        self.analyze_threshold_ladder(markets_for_event, &blend, held_market_ids, capital, max_position_pct)
+    }
+    
+    /// Price every threshold of one city/day from a single blended distribution, under the
+    /// configured probability model, and return the most mispriced one as a signal.
+    /// Thresholds already held are skipped.
+    pub fn analyze_threshold_ladder(
+        &self,
+        markets_for_event: &[Market],
+        forecast: &ForecastBlend,
+        held_market_ids: &[String],
+        capital: f64,
+        max_position_pct: f64,
+    ) -> Option<Signal> {
+        if forecast.std_dev <= 0.0 {
+            return None;
+        }
+        
+        let (market, info, prob, side, edge) = markets_for_event
+            .iter()
+            .filter(|m| m.priced && !held_market_ids.contains(&m.id))
+            .filter_map(|market| {
+                let info = parse_weather_question(&market.question, &self.parse_options).ok()?;
+                check_outcome_alignment(&market.outcomes, &info.comparison).ok()?;
+                
+                let threshold = effective_threshold(&info, self.config.boundary_tolerance_c);
+                let above = probability_above(
+                    &self.config.probability_model,
+                    self.config.erf_precision,
+                    forecast.mean_temp,
+                    threshold,
+                    forecast.std_dev,
+                );
+                let (prob, bounds) = match info.comparison {
+                    Comparison::Above => (above, self.config.above_prob_bounds),
+                    Comparison::Below => (1.0 - above, self.config.below_prob_bounds),
+                    Comparison::Exactly => {
+                        let (low, high) = info.exact_band(self.config.exactly_half_width);
+                        let prob = normal_interval_prob(low, high, forecast.mean_temp, forecast.std_dev);
+                        (prob, self.config.above_prob_bounds)
+                    }
+                };
+                let prob = clamp_forecast_probability(prob, bounds);
+                
+                let (side, edge) = calculate_edge(prob, market, self.config.edge_basis);
+                (edge >= self.config.min_edge_for_liquidity(market.liquidity()))
+                    .then_some((market, info, prob, side, edge))
+            })
+            .max_by(|a, b| a.4.total_cmp(&b.4))?;
+        
+        let entry_price = match side {
+            Side::Yes => market.yes_ask,
+            Side::No => market.no_ask,
+        };
+        let yes_equivalent_price = match side {
+            Side::Yes => entry_price,
+            Side::No => 1.0 - entry_price,
+        };
+        if (prob > yes_equivalent_price) != (side == Side::Yes) {
+            return None; // Mid-price edge gone at the ask
+        }
+        let size = forecast.size_factor * self.loss_streak_multiplier() * calculate_kelly_position(
+            capital,
+            prob,
+            yes_equivalent_price,
+            self.config.kelly_fraction,
+            max_position_pct,
+        );
         
         info!(
-            "Open-Meteo forecast: {:.1}% probability",
-            open_meteo_forecast.probability * 100.0
+            "Ladder pick {}: {} {:?} at ${}, forecast {}%, edge {}% ({} thresholds)",
+            market.id, info.threshold, side, fmt_price(entry_price), fmt_pct(prob), fmt_pct(edge),
+            markets_for_event.len()
         );
         
-        // Check forecast agreement (within 10%)
-        let forecast_diff = (noaa_forecast.probability - open_meteo_forecast.probability).abs();
-        if forecast_diff > 0.10 {
-            warn!(
-                "Forecast disagreement >10% ({:.1}%), skipping trade",
-                forecast_diff * 100.0
-            );
-            return Ok(None);
+        Some(Signal {
+            market_id: market.id.clone(),
+            strategy: Strategy::WeatherEdge,
+            side: Some(side),
+            entry_price,
+            intent: self.entry_intent(),
+            size,
+            edge: Some(edge),
+            confidence: forecast.confidence,
+            event_key: Some(info.event_key(info.target_date.unwrap_or_else(|| market.end_date.date_naive()))),
+            market_volume_24h: Some(market.volume_24h),
+            market_liquidity: Some(market.liquidity()),
+        })
+    }
+    
+    /// Group markets into threshold ladders by event key (city + day); unparseable markets are left out
+    pub fn group_by_event(&self, markets: &[Market]) -> BTreeMap<String, Vec<Market>> {
+        let mut ladders: BTreeMap<String, Vec<Market>> = BTreeMap::new();
+        for market in markets {
+            if let Ok(info) = parse_weather_question(&market.question, &self.parse_options) {
+                let key = info.event_key(info.target_date.unwrap_or_else(|| market.end_date.date_naive()));
+                ladders.entry(key).or_default().push(market.clone());
+            }
+        }
+        ladders
+    }
+    
+    /// Providers in priority order for `city`: configured names first, the rest as registered
+    pub fn providers_for(&self, city: &str) -> Vec<&dyn ForecastProvider> {
+        let priority = self.config.provider_priority_for(city);
+        let mut ordered: Vec<&dyn ForecastProvider> = self.providers.iter().map(|p| p.as_ref()).collect();
+        ordered.sort_by_key(|p| {
+            priority
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(p.name()))
+                .unwrap_or(usize::MAX)
+        });
+        ordered
+    }
+    
+    /// One provider's forecast, bounded by its timeout
+    async fn fetch_forecast(
+        &self,
+        provider: &dyn ForecastProvider,
+        city: &str,
+        threshold: f64,
+        resolution_time: DateTime<Utc>,
+    ) -> Result<ProbabilisticForecast> {
+        if self.config.in_update_window(provider.name(), chrono::Utc::now()) {
+            return Err(ForecastDeferred {
+                source_name: provider.name().to_string(),
+                minutes: self.config.update_avoid_minutes,
+            }
+            .into());
         }
         
-        // Use average of both forecasts
-        let forecast_prob = (noaa_forecast.probability + open_meteo_forecast.probability) / 2.0;
+        let timeout = self.config.provider_timeout(provider.name());
+        tokio::time::timeout(timeout, provider.forecast(city, threshold, resolution_time, ForecastKind::Temperature))
+            .await
+            .unwrap_or_else(|_| {
+                Err(anyhow::anyhow!("{} timed out after {}ms", provider.name(), timeout.as_millis()))
+            })
+    }
+    
+    /// Turn fetched forecasts (one per provider, in order) into a signal (steps 3-7 of the analysis)
+    fn evaluate_forecasts(
+        &self,
+        market: &Market,
+        market_info: &WeatherMarketInfo,
+        forecasts: Vec<Result<ProbabilisticForecast>>,
+        capital: f64,
+        max_position_pct: f64,
+    ) -> Result<Option<Signal>> {
+        let Some(ForecastBlend { probability: forecast_prob, mean_temp, std_dev: sigma, confidence, size_factor }) =
+            self.blend_forecasts(market, forecasts)?
+        else {
+            return Ok(None);
+        };
         
         // Adjust for comparison type (above vs below)
-        let forecast_prob_adjusted = match market_info.comparison {
-            Comparison::Above => forecast_prob,
-            Comparison::Below => 1.0 - forecast_prob,
+        let (forecast_prob_adjusted, bounds) = match market_info.comparison {
+            Comparison::Above => (forecast_prob, self.config.above_prob_bounds),
+            Comparison::Below => (1.0 - forecast_prob, self.config.below_prob_bounds),
+            // A point on a continuous forecast is ~0; price the band that rounds to the threshold
+            Comparison::Exactly => {
+                let (low, high) = market_info.exact_band(self.config.exactly_half_width);
+                (normal_interval_prob(low, high, mean_temp, sigma), self.config.above_prob_bounds)
+            }
         };
+        let forecast_prob_adjusted = clamp_forecast_probability(forecast_prob_adjusted, bounds);
         
         // 4. Calculate edge
-        let market_prob = market.yes_price;
-        let edge = (forecast_prob_adjusted - market_prob).abs();
+        let (side, edge) = calculate_edge(forecast_prob_adjusted, market, self.config.edge_basis);
         
         info!(
-            "Edge calculation: forecast={:.1}%, market={:.1}%, edge={:.1}%",
-            forecast_prob_adjusted * 100.0,
-            market_prob * 100.0,
-            edge * 100.0
+            "Edge calculation: forecast={}%, market={}%, edge={}% ({:?})",
+            fmt_pct(forecast_prob_adjusted),
+            fmt_pct(market.yes_price),
+            fmt_pct(edge),
+            self.config.edge_basis
         );
+        let (best_yes, best_no) = market.best_asks();
+        let (book_ask, best_ask) = match side {
+            Side::Yes => (market.yes_ask, best_yes),
+            Side::No => (market.no_ask, best_no),
+        };
+        if best_ask < book_ask {
+            info!(
+                "{:?} quoted at ${} on another venue vs the book's ${}; edge is taken at the book, where the order goes",
+                side,
+                fmt_price(best_ask),
+                fmt_price(book_ask)
+            );
+        }
         
-        // 5. Check minimum edge threshold
-        if edge < self.config.min_edge {
+        // 5. Check minimum edge threshold (higher for thin markets)
+        let min_edge = self.config.min_edge_for_liquidity(market.liquidity());
+        if edge < min_edge {
             info!(
-                "Edge {:.1}% below minimum {:.1}%, skipping",
-                edge * 100.0,
-                self.config.min_edge * 100.0
+                "Edge {}% below minimum {}% (liquidity ${}), skipping",
+                fmt_pct(edge),
+                fmt_pct(min_edge),
+                fmt_usd(market.liquidity())
+            );
+            self.reject(
+                market,
+                Some(side),
+                Some(edge),
+                "edge_below_min",
+                format!("edge {}% < {}%", fmt_pct(edge), fmt_pct(min_edge)),
             );
             return Ok(None);
         }
         
-        // 6. Determine side (bet YES if forecast > market, NO otherwise)
-        let side = if forecast_prob_adjusted > market_prob {
-            Side::Yes
-        } else {
-            Side::No
-        };
-        
+        // 6. Enter at the chosen side's ask
         let entry_price = match side {
             Side::Yes => market.yes_ask,
             Side::No => market.no_ask,
         };
         
         // 7. Calculate position size using CORRECTED Kelly
-        let size = calculate_kelly_position(
+        // Kelly takes YES-denominated prices, so a NO entry at no_ask is passed as 1 - no_ask
+        let yes_equivalent_price = match side {
+            Side::Yes => entry_price,
+            Side::No => 1.0 - entry_price,
+        };
+        
+        // Mid-price edge can vanish at the ask; Kelly would then size the opposite side
+        let kelly_bets_yes = forecast_prob_adjusted > yes_equivalent_price;
+        if kelly_bets_yes != (side == Side::Yes) {
+            info!("No edge left at the {:?} ask ${}, skipping", side, fmt_price(entry_price));
+            let detail = format!("no edge at {:?} ask ${}", side, fmt_price(entry_price));
+            self.reject(market, Some(side), Some(edge), "no_edge_at_ask", detail);
+            return Ok(None);
+        }
+        
+        let streak_factor = self.loss_streak_multiplier();
+        if streak_factor < 1.0 {
+            info!(
+                "{} losses in a row: sizing at {:.0}%",
+                self.loss_streak.load(Ordering::Relaxed),
+                streak_factor * 100.0
+            );
+        }
+        
+        let kelly_size = size_factor * streak_factor * calculate_kelly_position(
             capital,
             forecast_prob_adjusted,
-            entry_price,
+            yes_equivalent_price,
+            self.config.kelly_fraction,
             max_position_pct,
         );
         
+        // Plumbing tests in production: a fixed tiny size, still subject to risk checks
+        let size = match self.config.force_position_size_usd {
+            Some(forced) => {
+                info!("Forcing position size ${} (Kelly would be ${})", fmt_usd(forced), fmt_usd(kelly_size));
+                forced
+            }
+            None => kelly_size,
+        };
+        
+        // Walking a thin book costs more than the top-of-book ask: price the edge at the average fill
+        let asks = match side {
+            Side::Yes => &market.yes_asks,
+            Side::No => &market.no_asks,
+        };
+        let (entry_price, edge) = if self.config.price_impact && !asks.is_empty() {
+            let Some(avg_price) = estimate_fill_price(asks, size) else {
+                info!("Book too thin for ${} on {}, skipping", fmt_usd(size), market.id);
+                let detail = format!("book can't absorb ${}", fmt_usd(size));
+                self.reject(market, Some(side), Some(edge), "insufficient_depth", detail);
+                return Ok(None);
+            };
+            let win_prob = match side {
+                Side::Yes => forecast_prob_adjusted,
+                Side::No => 1.0 - forecast_prob_adjusted,
+            };
+            let impacted_edge = win_prob - avg_price;
+            info!(
+                "Price impact: ${} fills at avg ${} vs ask ${}, edge {}%",
+                fmt_usd(size), fmt_price(avg_price), fmt_price(entry_price), fmt_pct(impacted_edge)
+            );
+            if impacted_edge < min_edge {
+                let detail = format!(
+                    "edge {}% at avg fill ${} < {}%",
+                    fmt_pct(impacted_edge), fmt_price(avg_price), fmt_pct(min_edge)
+                );
+                self.reject(market, Some(side), Some(impacted_edge), "edge_below_min_after_impact", detail);
+                return Ok(None);
+            }
+            (avg_price, impacted_edge)
+        } else {
+            (entry_price, edge)
+        };
+        
         info!(
-            "Signal generated: side={:?}, price=${:.2}, size=${:.2}, edge={:.1}%",
-            side, entry_price, size, edge * 100.0
+            "Signal generated: side={:?}, price=${}, size=${}, edge={}%",
+            side, fmt_price(entry_price), fmt_usd(size), fmt_pct(edge)
         );
         
         Ok(Some(Signal {
@@ -137,22 +677,268 @@ impl WeatherEdgeStrategy {
             strategy: Strategy::WeatherEdge,
             side: Some(side),
             entry_price,
+            intent: self.entry_intent(),
             size,
             edge: Some(edge),
-            confidence: (noaa_forecast.confidence + open_meteo_forecast.confidence) / 2.0,
+            confidence,
+            event_key: Some(market_info.event_key(
+                market_info.target_date.unwrap_or_else(|| market.end_date.date_naive()),
+            )),
+            market_volume_24h: Some(market.volume_24h),
+            market_liquidity: Some(market.liquidity()),
+        }))
+    }
+    
+    /// Blend fetched forecasts that pass the source, agreement, sigma and confidence gates
+    /// Declined markets are recorded as rejections (or deferrals) against `market`
+    fn blend_forecasts(
+        &self,
+        market: &Market,
+        forecasts: Vec<Result<ProbabilisticForecast>>,
+    ) -> Result<Option<ForecastBlend>> {
+        let total_sources = forecasts.len();
+        let mut available = Vec::new();
+        let mut first_error = None;
+        let mut all_deferred = true;
+        for (i, forecast) in forecasts.into_iter().enumerate() {
+            let name = self.providers.get(i).map_or("forecast", |p| p.name());
+            match forecast {
+                Ok(forecast) => {
+                    info!(
+                        "{} forecast: {}% probability (mean={:.1}°C, std_dev={:.1}°C)",
+                        forecast.model,
+                        fmt_pct(forecast.probability),
+                        forecast.mean_temp,
+                        forecast.std_dev
+                    );
+                    available.push(forecast);
+                }
+                Err(e) => {
+                    warn!("{} forecast failed: {}", name, e);
+                    all_deferred &= is_deferral(&e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        
+        if available.is_empty() {
+            if all_deferred {
+                self.deferred(Some(market));
+            } else {
+                self.sources_failed(Some(market));
+            }
+            return Ok(None);
+        }
+        self.sources_failed_streak.store(0, Ordering::Relaxed);
+        
+        if let Some(e) = first_error {
+            if !self.config.allow_single_source {
+                // Every source is needed, and the missing ones will be back after their update
+                if all_deferred {
+                    self.deferred(Some(market));
+                    return Ok(None);
+                }
+                return Err(e);
+            }
+        }
+        
+        // Check forecast agreement (within 10%)
+        let forecast_diff = spread_of(available.iter().map(|f| f.probability));
+        if forecast_diff > MAX_FORECAST_DISAGREEMENT {
+            warn!(
+                "Forecast disagreement >10% ({}%), skipping trade",
+                fmt_pct(forecast_diff)
+            );
+            self.reject(
+                market,
+                None,
+                None,
+                "forecast_disagreement",
+                format!("forecasts differ by {}%", fmt_pct(forecast_diff)),
+            );
+            return Ok(None);
+        }
+        
+        // Use average of the available forecasts
+        let n = available.len() as f64;
+        
+        // Too uncertain to be worth a slot: Kelly would size it tiny anyway
+        let sigma = available.iter().map(|f| f.std_dev).sum::<f64>() / n;
+        if let Some(max_sigma) = self.config.max_forecast_sigma {
+            if sigma > max_sigma {
+                info!("Forecast sigma {:.1}°C above maximum {:.1}°C, skipping", sigma, max_sigma);
+                self.reject(
+                    market,
+                    None,
+                    None,
+                    "high_sigma",
+                    format!("sigma {:.1}°C > {:.1}°C", sigma, max_sigma),
+                );
+                return Ok(None);
+            }
+        }
+        
+        let forecast_prob = available.iter().map(|f| f.probability).sum::<f64>() / n;
+        let mut confidence = available.iter().map(|f| f.confidence).sum::<f64>() / n;
+        let mut size_factor = 1.0;
+        
+        if available.len() == 1 && total_sources > 1 {
+            // No cross-validation: trust (and size) the remaining source less
+            let factor = self.config.single_source_confidence_factor;
+            warn!(
+                "Continuing with {} alone at {:.0}% confidence",
+                available[0].model, factor * 100.0
+            );
+            confidence *= factor;
+            size_factor = factor;
+        }
+        
+        // Distrust long-horizon forecasts: scales confidence and, with it, Kelly size
+        let lead_hours = (market.end_date - chrono::Utc::now()).num_minutes() as f64 / 60.0;
+        let lead_factor = lead_time_confidence_factor(lead_hours, self.config.lead_time_confidence_decay);
+        if lead_factor < 1.0 {
+            info!(
+                "Lead time {:.0}h: confidence x{:.2} ({}% -> {}%)",
+                lead_hours,
+                lead_factor,
+                fmt_pct(confidence),
+                fmt_pct(confidence * lead_factor)
+            );
+        }
+        let confidence = confidence * lead_factor;
+        let size_factor = size_factor * lead_factor;
+        
+        if confidence < self.config.min_confidence {
+            info!(
+                "Forecast confidence {}% below minimum {}%, skipping",
+                fmt_pct(confidence),
+                fmt_pct(self.config.min_confidence)
+            );
+            self.reject(
+                market,
+                None,
+                None,
+                "low_confidence",
+                format!("confidence {}% < {}%", fmt_pct(confidence), fmt_pct(self.config.min_confidence)),
+            );
+            return Ok(None);
+        }
+        
+        Ok(Some(ForecastBlend {
+            probability: forecast_prob,
+            mean_temp: available.iter().map(|f| f.mean_temp).sum::<f64>() / n,
+            std_dev: sigma,
+            confidence,
+            size_factor,
         }))
     }
 }
 
+/// Gap between the highest and lowest probability among the forecasts that came back
+fn forecast_spread(forecasts: &[Result<ProbabilisticForecast>]) -> f64 {
+    spread_of(forecasts.iter().filter_map(|f| f.as_ref().ok()).map(|f| f.probability))
+}
+
+fn spread_of(probabilities: impl Iterator<Item = f64>) -> f64 {
+    let (min_prob, max_prob) = probabilities.fold((1.0_f64, 0.0_f64), |(lo, hi), p| (lo.min(p), hi.max(p)));
+    (max_prob - min_prob).max(0.0)
+}
+
+/// Average price paid spending `usd` up the ask levels, or None if the book can't absorb it
+pub fn estimate_fill_price(asks: &[BookLevel], usd: f64) -> Option<f64> {
+    if usd <= 0.0 {
+        return asks.first().map(|level| level.price);
+    }
+    
+    let mut remaining = usd;
+    let mut shares = 0.0;
+    for level in asks {
+        let level_cost = level.price * level.size;
+        if level_cost >= remaining {
+            shares += remaining / level.price;
+            return Some(usd / shares);
+        }
+        remaining -= level_cost;
+        shares += level.size;
+    }
+    None
+}
+
+/// Confidence multiplier for a forecast `lead_hours` out
+/// `decay` applies once per full day beyond the first 24h (1.0 = no penalty)
+pub fn lead_time_confidence_factor(lead_hours: f64, decay: f64) -> f64 {
+    let extra_days = ((lead_hours - 24.0) / 24.0).max(0.0);
+    decay.clamp(0.0, 1.0).powf(extra_days)
+}
+
+/// Pick a side and its edge for a YES forecast
+/// Mid: bet YES if forecast > mid, edge = |forecast - mid|
+/// Ask: bet the side with more edge at its own book ask, where the order goes (may be negative)
+/// Callers check `market.priced` first; placeholder prices give a meaningless edge
+pub fn calculate_edge(forecast_prob: f64, market: &Market, basis: EdgeBasis) -> (Side, f64) {
+    match basis {
+        EdgeBasis::Mid => {
+            let side = if forecast_prob > market.yes_price {
+                Side::Yes
+            } else {
+                Side::No
+            };
+            (side, (forecast_prob - market.yes_price).abs())
+        }
+        EdgeBasis::Ask => {
+            let yes_edge = forecast_prob - market.yes_ask;
+            let no_edge = (1.0 - forecast_prob) - market.no_ask;
+            if yes_edge >= no_edge {
+                (Side::Yes, yes_edge)
+            } else {
+                (Side::No, no_edge)
+            }
+        }
+    }
+}
+
+/// Clamp a YES probability into the tradable band
+/// Threshold to ask providers for P(temp > x), given how the question treats the boundary
+/// Temps settle in whole degrees: "exceeds 15" needs a reading of 15.5+, "reaches 15" only 14.5+,
+/// so strict comparisons move the boundary away from YES by `tolerance` and inclusive ones toward it.
+/// With zero tolerance a forecast centred on the threshold stays at 50/50 either way.
+pub fn effective_threshold(info: &WeatherMarketInfo, tolerance: f64) -> f64 {
+    match (&info.comparison, info.inclusive) {
+        (Comparison::Above, false) | (Comparison::Below, true) => info.threshold + tolerance,
+        (Comparison::Above, true) | (Comparison::Below, false) => info.threshold - tolerance,
+        (Comparison::Exactly, _) => info.threshold,
+    }
+}
+
+/// Pulls unreliable tail forecasts toward the middle, which shrinks both edge and Kelly size
+pub fn clamp_forecast_probability(prob: f64, bounds: ProbabilityBounds) -> f64 {
+    let clamped = prob.clamp(bounds.floor, bounds.ceiling.max(bounds.floor));
+    if clamped != prob {
+        info!(
+            "Forecast {}% outside tradable band, clamped to {}%",
+            fmt_pct(prob),
+            fmt_pct(clamped)
+        );
+    }
+    clamped
+}
+
 /// Calculate position size using CORRECTED Kelly Criterion
 /// Formula: f* = (bp - q) / b
 /// where b = odds, p = win_prob, q = lose_prob
+/// Both `forecast_prob` and `market_price` are YES-denominated
 pub fn calculate_kelly_position(
     capital: f64,
     forecast_prob: f64,
     market_price: f64,
+    kelly_fraction: f64,
     max_position_pct: f64,
 ) -> f64 {
+    // Prices at 0 or 1 give zero/infinite odds
+    if !(0.0..=1.0).contains(&forecast_prob) || market_price <= 0.0 || market_price >= 1.0 {
+        return 0.0;
+    }
+    
     // Determine which side we're betting
     let (win_prob, bet_price) = if forecast_prob > market_price {
         (forecast_prob, market_price) // Bet YES
@@ -160,15 +946,21 @@ pub fn calculate_kelly_position(
         (1.0 - forecast_prob, 1.0 - market_price) // Bet NO
     };
     
+    // The chosen side must be underpriced relative to our win probability;
+    // anything else means the YES/NO inputs were mixed up by the caller
+    if win_prob <= bet_price {
+        return 0.0;
+    }
+    
     // Calculate odds: (1 - price) / price
     let odds = (1.0 - bet_price) / bet_price;
     
     // CORRECTED Kelly Criterion: f* = (bp - q) / b
     let lose_prob = 1.0 - win_prob;
-    let kelly_fraction = (odds * win_prob - lose_prob) / odds;
+    let full_kelly = (odds * win_prob - lose_prob) / odds;
     
-    // Use 25% fractional Kelly for safety
-    let fractional_kelly = kelly_fraction * 0.25;
+    // Fractional Kelly for safety (25% by default)
+    let fractional_kelly = full_kelly * kelly_fraction;
     
     // Calculate position
     let position = capital * fractional_kelly.max(0.0); // No negative positions
@@ -182,6 +974,10 @@ pub fn calculate_kelly_position(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{Config, LiquidityEdgePoint};
+    use crate::data::types::{ProbabilityModel, TemperatureUnit, VenueQuote};
+    use crate::execution::persistence::PositionDatabase;
+    use crate::execution::types::Position;
     
     #[test]
     fn test_kelly_position_sizing() {
@@ -198,28 +994,979 @@ mod tests {
         // max_position = $2,000 * 0.10 = $200
         // FINAL: min($285, $200) = $200
         
-        let size = calculate_kelly_position(2000.0, 0.85, 0.65, 0.10);
+        let size = calculate_kelly_position(2000.0, 0.85, 0.65, 0.25, 0.10);
         assert!((size - 200.0).abs() < 1.0);
     }
     
     #[test]
     fn test_kelly_with_small_edge() {
         // Small edge should produce small position
-        let size = calculate_kelly_position(2000.0, 0.52, 0.50, 0.10);
+        let size = calculate_kelly_position(2000.0, 0.52, 0.50, 0.25, 0.10);
         assert!(size < 50.0);
     }
     
     #[test]
     fn test_kelly_with_large_edge() {
         // Large edge should hit max position constraint
-        let size = calculate_kelly_position(2000.0, 0.95, 0.50, 0.10);
+        let size = calculate_kelly_position(2000.0, 0.95, 0.50, 0.25, 0.10);
         assert!((size - 200.0).abs() < 1.0); // Should hit 10% max
     }
     
     #[test]
     fn test_kelly_betting_no() {
         // Forecast 20%, market 65% -> bet NO
-        let size = calculate_kelly_position(2000.0, 0.20, 0.65, 0.10);
+        let size = calculate_kelly_position(2000.0, 0.20, 0.65, 0.25, 0.10);
         assert!(size > 0.0); // Should generate valid position
     }
+    
+    #[test]
+    fn test_kelly_no_side_matches_analytic_value() {
+        // Forecast YES 20%, market YES $0.65 -> bet NO
+        // win_prob = 1 - 0.20 = 0.80, bet_price = 1 - 0.65 = 0.35
+        // odds = (1.0 - 0.35) / 0.35 = 1.857
+        // kelly = (1.857 * 0.80 - 0.20) / 1.857 = 0.6923
+        // fractional (25%): 0.6923 * 0.25 = 0.1731
+        // position = $2,000 * 0.1731 = $346.15 (uncapped)
+        let size = calculate_kelly_position(2000.0, 0.20, 0.65, 0.25, 1.0);
+        assert!((size - 346.15).abs() < 0.01);
+        
+        // Forecast YES 40%, market YES $0.50 -> bet NO
+        // win_prob = 0.60, bet_price = 0.50, odds = 1.0
+        // kelly = (1.0 * 0.60 - 0.40) / 1.0 = 0.20
+        // fractional (25%): 0.05 -> $100 (under the $200 cap)
+        let size = calculate_kelly_position(2000.0, 0.40, 0.50, 0.25, 0.10);
+        assert!((size - 100.0).abs() < 0.01);
+    }
+    
+    #[test]
+    fn test_kelly_no_side_mirrors_yes_side() {
+        // Betting NO at (f, m) is betting YES at (1 - f, 1 - m)
+        let no_size = calculate_kelly_position(2000.0, 0.30, 0.45, 0.25, 1.0);
+        let yes_size = calculate_kelly_position(2000.0, 0.70, 0.55, 0.25, 1.0);
+        assert!(no_size > 0.0);
+        assert!((no_size - yes_size).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_tail_forecast_is_clamped_and_desized() {
+        // "Above" market with a 1% forecast tail, market at $0.40 -> bet NO
+        let bounds = ProbabilityBounds { floor: 0.10, ceiling: 0.90 };
+        let clamped = clamp_forecast_probability(0.01, bounds);
+        assert_eq!(clamped, 0.10);
+        
+        let raw_size = calculate_kelly_position(2000.0, 0.01, 0.40, 0.25, 1.0);
+        let clamped_size = calculate_kelly_position(2000.0, clamped, 0.40, 0.25, 1.0);
+        assert!(clamped_size > 0.0);
+        assert!(clamped_size < raw_size);
+        
+        // Inside the band is untouched
+        assert_eq!(clamp_forecast_probability(0.55, bounds), 0.55);
+    }
+    
+    fn wide_spread_market() -> Market {
+        Market {
+            id: "0xabc".to_string(),
+            question: "Will NYC temperature exceed 60°F on 2026-02-17?".to_string(),
+            end_date: chrono::Utc::now(),
+            yes_price: 0.50,
+            yes_ask: 0.56,
+            no_ask: 0.56,
+            volume_24h: 10_000.0,
+            yes_liquidity: 5_000.0,
+            no_liquidity: 5_000.0,
+            closed: false,
+            share_granularity: None,
+            tick_size: None,
+            min_order_size: None,
+            quoted_yes_ask: None,
+            quoted_no_ask: None,
+            priced: true,
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            yes_asks: Vec::new(),
+            no_asks: Vec::new(),
+            token_ids: Vec::new(),
+            description: None,
+        }
+    }
+    
+    #[test]
+    fn test_ask_edge_is_smaller_than_mid_edge_for_wide_spread() {
+        let market = wide_spread_market();
+        
+        // YES: 0.70 - 0.50 mid = 20%, 0.70 - 0.56 ask = 14%
+        let (mid_side, mid_edge) = calculate_edge(0.70, &market, EdgeBasis::Mid);
+        let (ask_side, ask_edge) = calculate_edge(0.70, &market, EdgeBasis::Ask);
+        assert_eq!(mid_side, Side::Yes);
+        assert_eq!(ask_side, Side::Yes);
+        assert!((mid_edge - 0.20).abs() < 1e-9);
+        assert!((ask_edge - 0.14).abs() < 1e-9);
+        
+        // NO: 1 - 0.35 = 0.65 vs NO ask 0.56 -> 9%, vs 15% at mid
+        let (ask_side, ask_edge) = calculate_edge(0.35, &market, EdgeBasis::Ask);
+        assert_eq!(ask_side, Side::No);
+        assert!((ask_edge - 0.09).abs() < 1e-9);
+        assert!(ask_edge < calculate_edge(0.35, &market, EdgeBasis::Mid).1);
+    }
+    
+    #[test]
+    fn test_edge_is_taken_at_the_book_not_other_venues() {
+        let mut market = wide_spread_market();
+        let now = chrono::Utc::now();
+        let quotes = [
+            VenueQuote { source: "amm".to_string(), yes_ask: Some(0.52), no_ask: Some(0.60), as_of: Some(now) },
+            VenueQuote { source: "otc".to_string(), yes_ask: None, no_ask: Some(0.54), as_of: Some(now) },
+            VenueQuote { source: "undated".to_string(), yes_ask: Some(0.40), no_ask: Some(0.40), as_of: None },
+            VenueQuote {
+                source: "stale".to_string(),
+                yes_ask: Some(0.30),
+                no_ask: None,
+                as_of: Some(chrono::Utc::now() - chrono::Duration::hours(2)),
+            },
+        ];
+        market.apply_best_quotes(&quotes, chrono::Duration::minutes(15), now);
+        
+        // The cheapest fresh quote per side is kept; undated and stale ones are skipped
+        assert_eq!(market.best_asks(), (0.52, 0.54));
+        
+        // But the order goes to the book, so edge is 0.70 - 0.56, not 0.70 - 0.52
+        let (side, edge) = calculate_edge(0.70, &market, EdgeBasis::Ask);
+        assert_eq!(side, Side::Yes);
+        assert!((edge - 0.14).abs() < 1e-9);
+        assert_eq!((market.yes_ask, market.no_ask), (0.56, 0.56));
+    }
+    
+    fn forecast(probability: f64, model: &str) -> ProbabilisticForecast {
+        ProbabilisticForecast {
+            probability,
+            confidence: 0.90,
+            mean_temp: 18.0,
+            std_dev: 2.5,
+            model: model.to_string(),
+        }
+    }
+    
+    #[test]
+    fn test_large_size_in_thin_book_worsens_entry_and_edge() {
+        let mut config = Config::for_test().strategies.weather;
+        config.price_impact = true;
+        config.edge_basis = EdgeBasis::Ask;
+        config.min_edge = 0.05;
+        let strategy = WeatherEdgeStrategy::new(config, WeatherClient::new(None, ProbabilityModel::Normal, None)).unwrap();
+        
+        let info_market = wide_spread_market();
+        let info = parse_weather_question(&info_market.question, &ParseOptions::default()).unwrap();
+        let evaluate = |market: &Market| {
+            strategy
+                .evaluate_forecasts(
+                    market,
+                    &info,
+                    vec![Ok(forecast(0.80, "NOAA")), Ok(forecast(0.80, "Open-Meteo"))],
+                    2000.0,
+                    1.0,
+                )
+                .unwrap()
+        };
+        
+        // No depth known: priced at the top-of-book ask
+        let top = evaluate(&wide_spread_market()).unwrap();
+        assert!((top.entry_price - 0.56).abs() < 1e-9);
+        
+        // Only 50 shares at the ask, then the book thins out
+        let mut thin = wide_spread_market();
+        thin.apply_depth(
+            vec![
+                BookLevel { price: 0.64, size: 1_000.0 },
+                BookLevel { price: 0.56, size: 50.0 },
+                BookLevel { price: 0.60, size: 100.0 },
+            ],
+            Vec::new(),
+        );
+        let impacted = evaluate(&thin).unwrap();
+        assert!(impacted.entry_price > top.entry_price);
+        assert!(impacted.edge.unwrap() < top.edge.unwrap());
+        assert!((impacted.size - top.size).abs() < 1e-9);
+        
+        let avg = estimate_fill_price(&thin.yes_asks, top.size).unwrap();
+        assert!((impacted.entry_price - avg).abs() < 1e-9);
+        
+        // More than the whole book
+        assert!(estimate_fill_price(&thin.yes_asks, 10_000.0).is_none());
+    }
+    
+    #[test]
+    fn test_thin_market_requires_more_edge_than_deep_one() {
+        let mut config = Config::for_test().strategies.weather;
+        config.min_edge = 0.10;
+        config.min_edge_liquidity_curve = vec![
+            LiquidityEdgePoint { liquidity: 5_000.0, min_edge: 0.20 },
+            LiquidityEdgePoint { liquidity: 20_000.0, min_edge: 0.10 },
+        ];
+        assert!((config.min_edge_for_liquidity(12_500.0) - 0.15).abs() < 1e-9);
+        assert_eq!(config.min_edge_for_liquidity(1_000.0), 0.20);
+        assert_eq!(config.min_edge_for_liquidity(100_000.0), 0.10);
+        let strategy = WeatherEdgeStrategy::new(config, WeatherClient::new(None, ProbabilityModel::Normal, None)).unwrap();
+        
+        let info = parse_weather_question(&wide_spread_market().question, &ParseOptions::default()).unwrap();
+        // 0.65 vs 0.50 mid: 15% edge either way
+        let evaluate = |market: &Market| {
+            strategy
+                .evaluate_forecasts(
+                    market,
+                    &info,
+                    vec![Ok(forecast(0.65, "NOAA")), Ok(forecast(0.65, "Open-Meteo"))],
+                    2000.0,
+                    1.0,
+                )
+                .unwrap()
+        };
+        
+        let mut thin = wide_spread_market();
+        thin.yes_liquidity = 1_500.0;
+        thin.no_liquidity = 1_500.0;
+        let mut deep = wide_spread_market();
+        deep.yes_liquidity = 25_000.0;
+        deep.no_liquidity = 25_000.0;
+        
+        assert!(evaluate(&thin).is_none());
+        assert!(evaluate(&deep).is_some());
+    }
+    
+    #[test]
+    fn test_ladder_picks_most_mispriced_threshold() {
+        let config = Config::for_test().strategies.weather;
+        let strategy = WeatherEdgeStrategy::new(config, WeatherClient::new(None, ProbabilityModel::Normal, None)).unwrap();
+        let rung = |id: &str, threshold: u32, yes_price: f64| Market {
+            id: id.to_string(),
+            question: format!("Will London temperature exceed {}°C on 2026-02-17?", threshold),
+            yes_price,
+            yes_ask: yes_price + 0.01,
+            no_ask: 1.0 - yes_price + 0.01,
+            yes_liquidity: 12_500.0,
+            no_liquidity: 12_500.0,
+            ..wide_spread_market()
+        };
+        // One distribution (17 ± 2°C) prices all three: P(>15) = 0.84, P(>17) = 0.50, P(>19) = 0.16
+        let ladder = vec![rung("0x15", 15, 0.60), rung("0x17", 17, 0.20), rung("0x19", 19, 0.10)];
+        let forecast = ForecastBlend { probability: 0.5, mean_temp: 17.0, std_dev: 2.0, confidence: 0.9, size_factor: 1.0 };
+        
+        let signal = strategy.analyze_threshold_ladder(&ladder, &forecast, &[], 2000.0, 0.10).unwrap();
+        assert_eq!(signal.market_id, "0x17");
+        assert_eq!(signal.side, Some(Side::Yes));
+        assert!((signal.edge.unwrap() - 0.30).abs() < 1e-6);
+        assert_eq!(signal.event_key.as_deref(), Some("london:2026-02-17:temperature"));
+        
+        // Already holding the 17°C rung: next best is 15°C; the 19°C rung never clears min edge
+        let held = vec!["0x17".to_string()];
+        let signal = strategy.analyze_threshold_ladder(&ladder, &forecast, &held, 2000.0, 0.10).unwrap();
+        assert_eq!(signal.market_id, "0x15");
+        let held = vec!["0x17".to_string(), "0x15".to_string()];
+        assert!(strategy.analyze_threshold_ladder(&ladder, &forecast, &held, 2000.0, 0.10).is_none());
+    }
+    
+    #[test]
+    fn test_single_source_signal_when_noaa_is_down() {
+        let mut config = Config::for_test().strategies.weather;
+        config.allow_single_source = true;
+        config.single_source_confidence_factor = 0.5;
+        let strategy = WeatherEdgeStrategy::new(
+            config,
+            WeatherClient::new(None, ProbabilityModel::Normal, None),
+        ).unwrap();
+        
+        let market = wide_spread_market();
+        let info = parse_weather_question(&market.question, &ParseOptions::default()).unwrap();
+        
+        let both = strategy
+            .evaluate_forecasts(
+                &market,
+                &info,
+                vec![Ok(forecast(0.80, "NOAA")), Ok(forecast(0.80, "Open-Meteo"))],
+                2000.0,
+                1.0,
+            )
+            .unwrap()
+            .unwrap();
+        
+        let single = strategy
+            .evaluate_forecasts(
+                &market,
+                &info,
+                vec![Err(anyhow::anyhow!("NOAA API error: 503")), Ok(forecast(0.80, "Open-Meteo"))],
+                2000.0,
+                1.0,
+            )
+            .unwrap()
+            .unwrap();
+        
+        assert_eq!(single.side, Some(Side::Yes));
+        assert!((single.confidence - 0.45).abs() < 1e-9);
+        assert!(single.confidence < both.confidence);
+        assert!((single.size - both.size * 0.5).abs() < 1e-9);
+        
+        // Both down: nothing to trade on
+        let none = strategy
+            .evaluate_forecasts(
+                &market,
+                &info,
+                vec![Err(anyhow::anyhow!("NOAA down")), Err(anyhow::anyhow!("Open-Meteo down"))],
+                2000.0,
+                1.0,
+            )
+            .unwrap();
+        assert!(none.is_none());
+    }
+    
+    #[test]
+    fn test_high_sigma_forecast_is_skipped() {
+        let mut config = Config::for_test().strategies.weather;
+        config.max_forecast_sigma = Some(4.0);
+        let strategy = WeatherEdgeStrategy::new(config, WeatherClient::new(None, ProbabilityModel::Normal, None)).unwrap();
+        
+        let market = wide_spread_market();
+        let info = parse_weather_question(&market.question, &ParseOptions::default()).unwrap();
+        let wide = |model: &str| ProbabilisticForecast { std_dev: 5.0, ..forecast(0.80, model) };
+        
+        let signal = strategy
+            .evaluate_forecasts(&market, &info, vec![Ok(wide("NOAA")), Ok(wide("Open-Meteo"))], 2000.0, 1.0)
+            .unwrap();
+        assert!(signal.is_none());
+        assert_eq!(strategy.take_rejections()[0].reason, "high_sigma");
+        
+        // Same forecast within the cap still trades
+        let signal = strategy
+            .evaluate_forecasts(
+                &market,
+                &info,
+                vec![Ok(forecast(0.80, "NOAA")), Ok(forecast(0.80, "Open-Meteo"))],
+                2000.0,
+                1.0,
+            )
+            .unwrap();
+        assert!(signal.is_some());
+    }
+    
+    #[tokio::test]
+    async fn test_unpriced_market_yields_no_signal() {
+        let strategy = WeatherEdgeStrategy::new(
+            Config::for_test().strategies.weather,
+            WeatherClient::new(None, ProbabilityModel::Normal, None),
+        ).unwrap();
+        
+        // Fresh from Gamma: 0.5 placeholders that would show a 30% "edge"
+        let mut market = wide_spread_market();
+        market.priced = false;
+        market.yes_price = 0.5;
+        market.yes_ask = 0.5;
+        market.no_ask = 0.5;
+        
+        // Skipped before any forecast is fetched
+        let signal = strategy.analyze_weather_market(&market, 2000.0, 1.0).await.unwrap();
+        assert!(signal.is_none());
+        assert_eq!(strategy.take_rejections()[0].reason, "unpriced");
+    }
+    
+    #[test]
+    fn test_longer_lead_time_lowers_confidence() {
+        let mut config = Config::for_test().strategies.weather;
+        config.lead_time_confidence_decay = 0.9;
+        let strategy = WeatherEdgeStrategy::new(
+            config,
+            WeatherClient::new(None, ProbabilityModel::Normal, None),
+        ).unwrap();
+        
+        let mut near = wide_spread_market();
+        near.end_date = chrono::Utc::now() + chrono::Duration::hours(24);
+        let mut far = wide_spread_market();
+        far.end_date = chrono::Utc::now() + chrono::Duration::hours(72);
+        let info = parse_weather_question(&near.question, &ParseOptions::default()).unwrap();
+        
+        let evaluate = |market: &Market| {
+            strategy
+                .evaluate_forecasts(
+                    market,
+                    &info,
+                    vec![Ok(forecast(0.80, "NOAA")), Ok(forecast(0.80, "Open-Meteo"))],
+                    2000.0,
+                    1.0,
+                )
+                .unwrap()
+                .unwrap()
+        };
+        let near_signal = evaluate(&near);
+        let far_signal = evaluate(&far);
+        
+        // 72h is two days past 24h: 0.9^2 = 0.81
+        assert!((near_signal.confidence - 0.90).abs() < 1e-3);
+        assert!((far_signal.confidence - 0.90 * 0.81).abs() < 1e-3);
+        assert!(far_signal.size < near_signal.size);
+    }
+    
+    #[test]
+    fn test_loss_streak_halves_size_until_a_win() {
+        let mut config = Config::for_test().strategies.weather;
+        config.loss_streak_trades = 3;
+        config.loss_streak_size_multiplier = 0.5;
+        let strategy = WeatherEdgeStrategy::new(
+            config,
+            WeatherClient::new(None, ProbabilityModel::Normal, None),
+        ).unwrap();
+        let db = PositionDatabase::new(":memory:").unwrap();
+        
+        let market = wide_spread_market();
+        let info = parse_weather_question(&market.question, &ParseOptions::default()).unwrap();
+        let size_after = |pnls: &[f64]| {
+            for pnl in pnls {
+                let id = db.insert_position(&Position {
+                    entry_price: 0.50,
+                    cost: 50.0,
+                    ..Position::fixture("0xold")
+                }).unwrap();
+                db.update_position_status(id, "closed", Some(*pnl)).unwrap();
+            }
+            strategy.set_loss_streak(db.consecutive_losses().unwrap());
+            strategy
+                .evaluate_forecasts(
+                    &market,
+                    &info,
+                    vec![Ok(forecast(0.80, "NOAA")), Ok(forecast(0.80, "Open-Meteo"))],
+                    2000.0,
+                    1.0,
+                )
+                .unwrap()
+                .unwrap()
+                .size
+        };
+        
+        let full = size_after(&[]);
+        assert!((size_after(&[-10.0, -10.0]) - full).abs() < 1e-9);
+        assert!((size_after(&[-10.0]) - full * 0.5).abs() < 1e-9);
+        assert!((size_after(&[5.0]) - full).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_exactly_market_priced_as_whole_degree_band() {
+        let config = Config::for_test().strategies.weather;
+        let strategy = WeatherEdgeStrategy::new(config, WeatherClient::new(None, ProbabilityModel::Normal, None)).unwrap();
+        
+        let mut market = wide_spread_market();
+        market.question = "Will the high in NYC be exactly 60°F on 2026-02-17?".to_string();
+        market.yes_price = 0.02;
+        market.yes_ask = 0.03;
+        market.yes_liquidity = 12_500.0;
+        market.no_liquidity = 12_500.0;
+        let info = parse_weather_question(&market.question, &ParseOptions::default()).unwrap();
+        assert_eq!(info.comparison, Comparison::Exactly);
+        assert_eq!(info.unit, TemperatureUnit::Fahrenheit);
+        
+        // Forecast centred on 60°F (15.56°C), sigma 1.5°C: [59.5, 60.5)°F holds ~15%, not ~0
+        let centred = |model: &str| ProbabilisticForecast {
+            mean_temp: (60.0 - 32.0) * 5.0 / 9.0,
+            std_dev: 1.5,
+            ..forecast(0.5, model)
+        };
+        let signal = strategy
+            .evaluate_forecasts(&market, &info, vec![Ok(centred("NOAA")), Ok(centred("Open-Meteo"))], 2000.0, 1.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(signal.side, Some(Side::Yes));
+        let prob = signal.edge.unwrap() + market.yes_price;
+        assert!((0.14..0.16).contains(&prob), "exact-band probability {}", prob);
+    }
+    
+    #[test]
+    fn test_threshold_at_mean_by_comparison_and_tolerance() {
+        use crate::math::distributions::normal_cdf;
+        
+        // Forecast mean sits exactly on the 15°C threshold
+        let mean = 15.0;
+        let yes_probability = |comparison: Comparison, inclusive: bool, tolerance: f64| {
+            let info = WeatherMarketInfo {
+                city: "London".to_string(),
+                threshold: 15.0,
+                unit: TemperatureUnit::Celsius,
+                comparison: comparison.clone(),
+                inclusive,
+                target_date: None,
+                timezone: chrono_tz::Europe::London,
+                kind: ForecastKind::Temperature,
+            };
+            let above = 1.0 - normal_cdf((effective_threshold(&info, tolerance) - mean) / 2.5);
+            match comparison {
+                Comparison::Above => above,
+                Comparison::Below => 1.0 - above,
+                Comparison::Exactly => unreachable!(),
+            }
+        };
+        
+        // Exact boundary: a coin flip whichever way the question is phrased
+        for comparison in [Comparison::Above, Comparison::Below] {
+            for inclusive in [false, true] {
+                assert!((yes_probability(comparison.clone(), inclusive, 0.0) - 0.5).abs() < 1e-9);
+            }
+        }
+        
+        // Half a degree of rounding: landing on 15 counts for "reaches"/"at most", not "exceeds"/"below"
+        let p_exceeds = yes_probability(Comparison::Above, false, 0.5);
+        let p_reaches = yes_probability(Comparison::Above, true, 0.5);
+        let p_below = yes_probability(Comparison::Below, false, 0.5);
+        let p_at_most = yes_probability(Comparison::Below, true, 0.5);
+        assert!((p_exceeds - 0.4207).abs() < 1e-3);
+        assert!((p_reaches - 0.5793).abs() < 1e-3);
+        assert!((p_below - 0.4207).abs() < 1e-3);
+        assert!((p_at_most - 0.5793).abs() < 1e-3);
+        
+        // Complementary questions still sum to one
+        assert!((p_exceeds + p_at_most - 1.0).abs() < 1e-9);
+        assert!((p_reaches + p_below - 1.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_forced_position_size_replaces_kelly() {
+        let mut config = Config::for_test().strategies.weather;
+        config.force_position_size_usd = Some(5.0);
+        let strategy = WeatherEdgeStrategy::new(
+            config,
+            WeatherClient::new(None, ProbabilityModel::Normal, None),
+        ).unwrap();
+        
+        let market = wide_spread_market();
+        let info = parse_weather_question(&market.question, &ParseOptions::default()).unwrap();
+        for probability in [0.70, 0.80] {
+            let signal = strategy
+                .evaluate_forecasts(
+                    &market,
+                    &info,
+                    vec![Ok(forecast(probability, "NOAA")), Ok(forecast(probability, "Open-Meteo"))],
+                    2000.0,
+                    1.0,
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(signal.size, 5.0);
+        }
+    }
+    
+    /// Canned forecasts, no HTTP
+    struct MockProvider(f64);
+    
+    impl ForecastProvider for MockProvider {
+        fn name(&self) -> &str {
+            "mock"
+        }
+        
+        fn forecast<'a>(
+            &'a self,
+            _city: &'a str,
+            _threshold: f64,
+            _resolution_time: chrono::DateTime<chrono::Utc>,
+            _kind: ForecastKind,
+        ) -> futures::future::BoxFuture<'a, Result<ProbabilisticForecast>> {
+            Box::pin(async move { Ok(forecast(self.0, "mock")) })
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_analyze_with_mock_providers() {
+        let config = Config::for_test().strategies.weather;
+        let strategy = WeatherEdgeStrategy::with_providers(
+            config.clone(),
+            vec![Box::new(MockProvider(0.80)), Box::new(MockProvider(0.78))],
+        ).unwrap();
+        
+        let signal = strategy
+            .analyze_weather_market(&wide_spread_market(), 2000.0, 1.0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(signal.side, Some(Side::Yes));
+        assert!((signal.entry_price - 0.56).abs() < 1e-9);
+        assert!(signal.edge.unwrap() > 0.0);
+        
+        // Providers that disagree by more than 10% are not traded
+        let strategy = WeatherEdgeStrategy::with_providers(
+            config,
+            vec![Box::new(MockProvider(0.80)), Box::new(MockProvider(0.60))],
+        ).unwrap();
+        let signal = strategy
+            .analyze_weather_market(&wide_spread_market(), 2000.0, 1.0)
+            .await
+            .unwrap();
+        assert!(signal.is_none());
+        assert_eq!(strategy.take_rejections()[0].reason, "forecast_disagreement");
+    }
+    
+    #[tokio::test]
+    async fn test_ladder_goes_through_the_single_market_gates() {
+        let rung = |id: &str, threshold: u32, yes_price: f64| Market {
+            id: id.to_string(),
+            question: format!("Will London temperature exceed {}°C on 2026-02-17?", threshold),
+            yes_price,
+            yes_ask: yes_price + 0.01,
+            no_ask: 1.0 - yes_price + 0.01,
+            ..wide_spread_market()
+        };
+        let ladder = vec![rung("0x15", 15, 0.60), rung("0x18", 18, 0.20)];
+        let config = Config::for_test().strategies.weather;
+        let ladder_signal = |providers: Vec<Box<dyn ForecastProvider>>, config: WeatherStrategyConfig| {
+            let strategy = WeatherEdgeStrategy::with_providers(config, providers).unwrap();
+            let ladder = ladder.clone();
+            async move {
+                let signal = strategy.analyze_event_ladder(&ladder, &[], 2000.0, 0.10).await;
+                (signal, strategy.take_rejections())
+            }
+        };
+        
+        let (signal, _) = ladder_signal(vec![Box::new(MockProvider(0.80)), Box::new(MockProvider(0.78))], config.clone()).await;
+        assert_eq!(signal.unwrap().market_id, "0x18");
+        
+        // Sources that disagree don't price a ladder either
+        let (signal, rejections) =
+            ladder_signal(vec![Box::new(MockProvider(0.80)), Box::new(MockProvider(0.60))], config.clone()).await;
+        assert!(signal.is_none());
+        assert_eq!(rejections[0].reason, "forecast_disagreement");
+        
+        let mut tight = config;
+        tight.max_forecast_sigma = Some(2.0);
+        let (signal, rejections) = ladder_signal(vec![Box::new(MockProvider(0.80)), Box::new(MockProvider(0.78))], tight).await;
+        assert!(signal.is_none());
+        assert_eq!(rejections[0].reason, "high_sigma");
+    }
+    
+    #[tokio::test]
+    async fn test_any_number_of_providers_are_blended() {
+        let config = Config::for_test().strategies.weather;
+        let analyze = |providers: Vec<Box<dyn ForecastProvider>>| {
+            let strategy = WeatherEdgeStrategy::with_providers(config.clone(), providers).unwrap();
+            async move { strategy.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap() }
+        };
+        
+        // Three sources averaging 0.78 price the same as one source saying 0.78
+        let single = analyze(vec![Box::new(MockProvider(0.78))]).await.unwrap();
+        let blended = analyze(vec![
+            Box::new(MockProvider(0.80)),
+            Box::new(MockProvider(0.78)),
+            Box::new(MockProvider(0.76)),
+        ])
+        .await
+        .unwrap();
+        assert!((blended.edge.unwrap() - single.edge.unwrap()).abs() < 1e-9);
+        assert!((blended.size - single.size).abs() < 1e-9);
+        
+        // Agreement is checked across all of them: one outlier among three vetoes the trade
+        let vetoed = analyze(vec![
+            Box::new(MockProvider(0.80)),
+            Box::new(MockProvider(0.78)),
+            Box::new(MockProvider(0.62)),
+        ])
+        .await;
+        assert!(vetoed.is_none());
+    }
+    
+    /// Answers eventually, but not within any sensible window
+    struct SlowProvider;
+    
+    impl ForecastProvider for SlowProvider {
+        fn name(&self) -> &str {
+            "slow"
+        }
+        
+        fn forecast<'a>(
+            &'a self,
+            _city: &'a str,
+            _threshold: f64,
+            _resolution_time: chrono::DateTime<chrono::Utc>,
+            _kind: ForecastKind,
+        ) -> futures::future::BoxFuture<'a, Result<ProbabilisticForecast>> {
+            Box::pin(async move {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                Ok(forecast(0.80, "slow"))
+            })
+        }
+    }
+    
+    /// Fixed forecast under a given provider name
+    struct NamedProvider(&'static str, f64);
+    
+    impl ForecastProvider for NamedProvider {
+        fn name(&self) -> &str {
+            self.0
+        }
+        
+        fn forecast<'a>(
+            &'a self,
+            _city: &'a str,
+            _threshold: f64,
+            _resolution_time: chrono::DateTime<chrono::Utc>,
+            _kind: ForecastKind,
+        ) -> futures::future::BoxFuture<'a, Result<ProbabilisticForecast>> {
+            Box::pin(async move { Ok(forecast(self.1, self.0)) })
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_forecast_inside_update_window_defers_trade() {
+        use chrono::{NaiveTime, TimeZone, Utc};
+        
+        let mut config = Config::for_test().strategies.weather;
+        config.update_avoid_minutes = 10;
+        config.forecast_update_times.insert(
+            "mock".to_string(),
+            vec![NaiveTime::from_hms_opt(6, 0, 0).unwrap(), NaiveTime::from_hms_opt(0, 5, 0).unwrap()],
+        );
+        let at = |h, m| Utc.with_ymd_and_hms(2026, 2, 17, h, m, 0).unwrap();
+        assert!(config.in_update_window("mock", at(5, 52)));
+        assert!(config.in_update_window("MOCK", at(6, 10)));
+        assert!(!config.in_update_window("mock", at(6, 11)));
+        assert!(config.in_update_window("mock", at(23, 58))); // Across midnight
+        assert!(!config.in_update_window("other", at(6, 0)));
+        
+        // Every source is mid-update right now: nothing to trade on
+        let now = Utc::now().time();
+        config.forecast_update_times.insert("mock".to_string(), vec![now]);
+        config.on_sources_failed = SourcesFailedAction::Breaker;
+        config.sources_failed_limit = 1;
+        let strategy = WeatherEdgeStrategy::with_providers(
+            config,
+            vec![Box::new(MockProvider(0.80)), Box::new(MockProvider(0.78))],
+        ).unwrap();
+        let signal = strategy.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap();
+        assert!(signal.is_none());
+        
+        // ...but that's a deferral, not a source failure: nothing toward the breaker
+        assert_eq!(strategy.take_rejections()[0].reason, "update_window");
+        assert_eq!(strategy.sources_failed_escalation(), None);
+        
+        // One source updating doesn't excuse another that's down
+        let strategy = WeatherEdgeStrategy::with_providers(
+            strategy.config.clone(),
+            vec![Box::new(MockProvider(0.80)), Box::new(FailingProvider)],
+        ).unwrap();
+        assert!(strategy.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap().is_none());
+        assert_eq!(strategy.take_rejections()[0].reason, "all_sources_failed");
+        assert_eq!(strategy.sources_failed_escalation(), Some(1));
+        
+        // A breaker reset starts the count over
+        strategy.clear_sources_failed_streak();
+        assert_eq!(strategy.sources_failed_escalation(), None);
+    }
+    
+    /// Returns each forecast in turn, then repeats the last
+    struct UpdatingProvider(std::sync::Mutex<Vec<f64>>);
+    
+    impl ForecastProvider for UpdatingProvider {
+        fn name(&self) -> &str {
+            "updating"
+        }
+        
+        fn forecast<'a>(
+            &'a self,
+            _city: &'a str,
+            _threshold: f64,
+            _resolution_time: chrono::DateTime<chrono::Utc>,
+            _kind: ForecastKind,
+        ) -> futures::future::BoxFuture<'a, Result<ProbabilisticForecast>> {
+            let mut queue = self.0.lock().unwrap();
+            let probability = if queue.len() > 1 { queue.remove(0) } else { queue[0] };
+            Box::pin(async move { Ok(forecast(probability, "updating")) })
+        }
+    }
+    
+    /// Always down
+    struct FailingProvider;
+    
+    impl ForecastProvider for FailingProvider {
+        fn name(&self) -> &str {
+            "failing"
+        }
+        
+        fn forecast<'a>(
+            &'a self,
+            _city: &'a str,
+            _threshold: f64,
+            _resolution_time: chrono::DateTime<chrono::Utc>,
+            _kind: ForecastKind,
+        ) -> futures::future::BoxFuture<'a, Result<ProbabilisticForecast>> {
+            Box::pin(async move { Err(anyhow::anyhow!("503 Service Unavailable")) })
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_all_sources_failing_skips_or_escalates_per_config() {
+        let config = Config::for_test().strategies.weather;
+        let failing = |config: WeatherStrategyConfig| {
+            WeatherEdgeStrategy::with_providers(config, vec![Box::new(FailingProvider), Box::new(FailingProvider)]).unwrap()
+        };
+        
+        // "skip": the market is passed over, never escalated
+        let strategy = failing(WeatherStrategyConfig { on_sources_failed: SourcesFailedAction::Skip, ..config.clone() });
+        for _ in 0..5 {
+            let signal = strategy.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap();
+            assert!(signal.is_none());
+        }
+        assert_eq!(strategy.take_rejections()[0].reason, "all_sources_failed");
+        assert_eq!(strategy.sources_failed_escalation(), None);
+        
+        // "breaker": escalates once `sources_failed_limit` markets in a row have failed
+        let strategy = failing(WeatherStrategyConfig {
+            on_sources_failed: SourcesFailedAction::Breaker,
+            sources_failed_limit: 2,
+            ..config.clone()
+        });
+        strategy.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap();
+        assert_eq!(strategy.sources_failed_escalation(), None);
+        strategy.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap();
+        assert_eq!(strategy.sources_failed_escalation(), Some(2));
+        
+        // Also in failover mode, and a source answering again clears the streak
+        let strategy = WeatherEdgeStrategy::with_providers(
+            WeatherStrategyConfig {
+                ensemble: false,
+                on_sources_failed: SourcesFailedAction::Breaker,
+                sources_failed_limit: 1,
+                ..config
+            },
+            vec![Box::new(FailingProvider), Box::new(UpdatingProvider(std::sync::Mutex::new(vec![0.80])))],
+        ).unwrap();
+        strategy.sources_failed(None);
+        assert_eq!(strategy.sources_failed_escalation(), Some(1));
+        strategy.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap();
+        assert_eq!(strategy.sources_failed_escalation(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_refetch_resolves_stale_forecast_disagreement() {
+        let mut config = Config::for_test().strategies.weather;
+        config.disagreement_retry_delay_ms = 10;
+        // First fetch is stale (0.60 vs 0.80); the re-fetch has caught up (0.79)
+        let strategy = |config: WeatherStrategyConfig| {
+            WeatherEdgeStrategy::with_providers(
+                config,
+                vec![
+                    Box::new(MockProvider(0.80)),
+                    Box::new(UpdatingProvider(std::sync::Mutex::new(vec![0.60, 0.79]))),
+                ],
+            ).unwrap()
+        };
+        
+        let no_retry = strategy(config.clone());
+        assert!(no_retry.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap().is_none());
+        assert_eq!(no_retry.take_rejections()[0].reason, "forecast_disagreement");
+        
+        config.disagreement_retries = 1;
+        let signal = strategy(config)
+            .analyze_weather_market(&wide_spread_market(), 2000.0, 1.0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(signal.side, Some(Side::Yes));
+    }
+    
+    #[tokio::test]
+    async fn test_european_city_prefers_configured_ecmwf_first() {
+        let mut config = Config::for_test().strategies.weather;
+        config.ensemble = false;
+        config.provider_priority = vec!["NOAA".to_string()];
+        config.city_provider_priority.insert("London".to_string(), vec!["ECMWF".to_string(), "NOAA".to_string()]);
+        let strategy = WeatherEdgeStrategy::with_providers(
+            config,
+            vec![
+                Box::new(NamedProvider("Open-Meteo", 0.75)),
+                Box::new(NamedProvider("NOAA", 0.80)),
+                Box::new(NamedProvider("ECMWF", 0.70)),
+            ],
+        ).unwrap();
+        
+        let names = |city: &str| -> Vec<String> {
+            strategy.providers_for(city).iter().map(|p| p.name().to_string()).collect()
+        };
+        assert_eq!(names("London"), vec!["ECMWF", "NOAA", "Open-Meteo"]);
+        assert_eq!(names("New York"), vec!["NOAA", "Open-Meteo", "ECMWF"]);
+        
+        // Failover mode trades on the first source in the city's order
+        let mut london = wide_spread_market();
+        london.question = "Will London temperature exceed 15°C on 2026-02-17?".to_string();
+        let signal = strategy.analyze_weather_market(&london, 2000.0, 1.0).await.unwrap().unwrap();
+        assert!((signal.edge.unwrap() - 0.20).abs() < 1e-9); // 0.70 vs 0.50 mid
+        
+        let signal = strategy.analyze_weather_market(&wide_spread_market(), 2000.0, 1.0).await.unwrap().unwrap();
+        assert!((signal.edge.unwrap() - 0.30).abs() < 1e-9); // NOAA's 0.80
+    }
+    
+    #[tokio::test]
+    async fn test_timed_out_provider_is_dropped_from_ensemble() {
+        let mut config = Config::for_test().strategies.weather;
+        config.allow_single_source = true;
+        config.provider_timeouts_ms.insert("slow".to_string(), 50);
+        let strategy = WeatherEdgeStrategy::with_providers(
+            config,
+            vec![Box::new(SlowProvider), Box::new(MockProvider(0.80))],
+        ).unwrap();
+        
+        let started = std::time::Instant::now();
+        let signal = strategy
+            .analyze_weather_market(&wide_spread_market(), 2000.0, 1.0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        
+        // Went ahead on the fast source alone, at reduced confidence
+        assert_eq!(signal.side, Some(Side::Yes));
+        assert!((signal.confidence - 0.90 * 0.5).abs() < 1e-9);
+    }
+    
+    #[tokio::test]
+    async fn test_mislabeled_outcomes_skip_the_trade() {
+        let config = Config::for_test().strategies.weather;
+        let strategy = WeatherEdgeStrategy::with_providers(
+            config,
+            vec![Box::new(MockProvider(0.80)), Box::new(MockProvider(0.80))],
+        ).unwrap();
+        
+        // "exceed" parses as Above, but the first (YES) token is labelled Below
+        let mut market = wide_spread_market();
+        market.outcomes = vec!["Below 60°F".to_string(), "Above 60°F".to_string()];
+        
+        let signal = strategy.analyze_weather_market(&market, 2000.0, 1.0).await.unwrap();
+        assert!(signal.is_none());
+        assert_eq!(strategy.take_rejections()[0].reason, "outcome_mismatch");
+    }
+    
+    #[tokio::test]
+    async fn test_unparseable_question_is_dead_lettered() {
+        let config = Config::for_test().strategies.weather;
+        let strategy = WeatherEdgeStrategy::with_providers(config, vec![Box::new(MockProvider(0.80))]).unwrap();
+        let db = PositionDatabase::new(":memory:").unwrap();
+        
+        // City and comparison are there, the threshold isn't
+        let mut market = wide_spread_market();
+        market.question = "Will NYC temperature exceed the record high on 2026-02-17?".to_string();
+        assert!(strategy.analyze_weather_market(&market, 2000.0, 1.0).await.unwrap().is_none());
+        
+        for letter in strategy.take_dead_letters() {
+            db.record_dead_letter(&letter).unwrap();
+        }
+        let letters = db.get_dead_letters().unwrap();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].0.market_id, "0xabc");
+        assert_eq!(letters[0].0.question, market.question);
+        assert!(letters[0].0.error.contains("Could not extract temperature"));
+        assert_eq!(letters[0].1, 1);
+        
+        // Seen again next cycle: one record, counted twice
+        strategy.analyze_weather_market(&market, 2000.0, 1.0).await.unwrap();
+        db.record_dead_letter(&strategy.take_dead_letters()[0]).unwrap();
+        assert_eq!(db.get_dead_letters().unwrap()[0].1, 2);
+    }
+    
+    #[test]
+    fn test_kelly_guards_degenerate_inputs() {
+        // No edge on either side
+        assert_eq!(calculate_kelly_position(2000.0, 0.50, 0.50, 0.25, 0.10), 0.0);
+        // Prices at the bounds have undefined odds
+        assert_eq!(calculate_kelly_position(2000.0, 0.20, 1.0, 0.25, 0.10), 0.0);
+        assert_eq!(calculate_kelly_position(2000.0, 0.80, 0.0, 0.25, 0.10), 0.0);
+        // Probability outside [0, 1]
+        assert_eq!(calculate_kelly_position(2000.0, 1.20, 0.50, 0.25, 0.10), 0.0);
+    }
 }